@@ -1,4 +1,4 @@
-{% if request.target == "clash" or request.target == "clashr" %}
+{% if request.target == "clash" or request.target == "clashr" or request.target == "mihomo" or request.target == "stash" %}
 
 port: {{ default(global.clash.http_port, "7890") }}
 socks-port: {{ default(global.clash.socks_port, "7891") }}
@@ -35,6 +35,16 @@ dns-server = 119.29.29.29,223.5.5.5
 [Script]
 http-request https?:\/\/.*\.iqiyi\.com\/.*authcookie= script-path=https://raw.githubusercontent.com/NobyDa/Script/master/iQIYI-DailyBonus/iQIYI.js
 
+{% endif %}
+{% if request.target == "shadowrocket" %}
+
+[General]
+bypass-system = true
+skip-proxy = 127.0.0.1,192.168.0.0/16,10.0.0.0/8,172.16.0.0/12,100.64.0.0/10,localhost,*.local,captive.apple.com
+bypass-tun = 192.168.0.0/16,10.0.0.0/8,172.16.0.0/12
+dns-server = system,223.5.5.5,119.29.29.29
+ipv6 = false
+
 {% endif %}
 {% if request.target == "loon" %}
 
@@ -309,15 +319,15 @@ enhanced-mode-by-rule = true
                 "server": "dns_resolver"
             },
             {
-                "geosite": [
-                    "category-ads-all"
+                "rule_set": [
+                    "geosite-category-ads-all"
                 ],
-                "server": "dns_block",
+                "server": "block",
                 "disable_cache": true
             },
             {
-                "geosite": [
-                    "geolocation-!cn"
+                "rule_set": [
+                    "geosite-geolocation-!cn"
                 ],
                 "query_type": [
                     "A",
@@ -326,8 +336,8 @@ enhanced-mode-by-rule = true
                 "server": "dns_fakeip"
             },
             {
-                "geosite": [
-                    "geolocation-!cn"
+                "rule_set": [
+                    "geosite-geolocation-!cn"
                 ],
                 "server": "dns_proxy"
             }
@@ -382,6 +392,22 @@ enhanced-mode-by-rule = true
     "outbounds": [],
     "route": {
         "rules": [],
+        "rule_set": [
+            {
+                "tag": "geosite-category-ads-all",
+                "type": "remote",
+                "format": "binary",
+                "url": "https://raw.githubusercontent.com/SagerNet/sing-geosite/rule-set/geosite-category-ads-all.srs",
+                "download_detour": "DIRECT"
+            },
+            {
+                "tag": "geosite-geolocation-!cn",
+                "type": "remote",
+                "format": "binary",
+                "url": "https://raw.githubusercontent.com/SagerNet/sing-geosite/rule-set/geosite-geolocation-!cn.srs",
+                "download_detour": "DIRECT"
+            }
+        ],
         "auto_detect_interface": true
     },
     "experimental": {
@@ -397,3 +423,44 @@ enhanced-mode-by-rule = true
 }
 
 {% endif %}
+{% if request.target == "xray" %}
+
+{
+    "log": {
+        "loglevel": "warning"
+    },
+    "dns": {
+        "servers": [
+            "https://1.1.1.1/dns-query",
+            "223.5.5.5"
+        ]
+    },
+    "inbounds": [
+        {
+            "tag": "socks-in",
+            "protocol": "socks",
+            "listen": "127.0.0.1",
+            "port": 10808,
+            "settings": {
+                "udp": true
+            },
+            "sniffing": {
+                "enabled": true,
+                "destOverride": ["http", "tls", "quic"]
+            }
+        },
+        {
+            "tag": "http-in",
+            "protocol": "http",
+            "listen": "127.0.0.1",
+            "port": 10809
+        }
+    ],
+    "outbounds": [],
+    "routing": {
+        "domainStrategy": "IPIfNonMatch",
+        "rules": []
+    }
+}
+
+{% endif %}
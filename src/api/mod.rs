@@ -4,6 +4,7 @@ pub mod admin;
 pub mod init;
 #[cfg(target_arch = "wasm32")]
 pub mod rules;
+pub mod ruleset;
 #[cfg(target_arch = "wasm32")]
 pub mod short_urls;
 pub mod sub;
@@ -13,6 +14,7 @@ pub use admin::*;
 pub use init::*;
 #[cfg(target_arch = "wasm32")]
 pub use rules::*;
+pub use ruleset::*;
 #[cfg(target_arch = "wasm32")]
 pub use short_urls::*;
 pub use sub::*;
@@ -0,0 +1,292 @@
+use log::{debug, error};
+use serde::{Deserialize, Serialize};
+
+use crate::api::SubResponse;
+use crate::generator::ruleconvert::ruleset_to_clash_str::ruleset_to_clash_payload;
+use crate::generator::ruleconvert::ruleset_to_sing_box::{
+    ruleset_to_sing_box_source, SINGBOX_RULESET_TYPE,
+};
+use crate::generator::ruleconvert::ruleset_to_surge::ruleset_to_surge_list;
+use crate::models::ruleset::{get_ruleset_type_from_url, RulesetType, RULESET_TYPES};
+use crate::models::SubconverterTarget;
+use crate::rulesets::ruleset::fetch_ruleset;
+use crate::settings::settings::init_settings;
+use crate::utils::base64::url_safe_base64_decode;
+use crate::utils::http::parse_proxy;
+use crate::Settings;
+
+#[cfg(target_arch = "wasm32")]
+use {js_sys::Promise, wasm_bindgen::prelude::*, wasm_bindgen_futures::future_to_promise};
+
+/// Query parameters for fetching a single converted ruleset
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct GetRulesetQuery {
+    /// Output format of the ruleset
+    #[serde(rename = "type")]
+    pub ruleset_type: Option<u32>,
+
+    /// URL-safe Base64 encoded ruleset path, may carry a type prefix such as `clash-domain:`
+    pub url: Option<String>,
+
+    /// URL-safe Base64 encoded policy group
+    pub group: Option<String>,
+
+    /// Clash flavour a rule provider payload is served for, plain Clash if omitted
+    pub target: Option<String>,
+}
+
+/// Split the type prefix off a ruleset path
+fn split_ruleset_path(rule_path_typed: &str) -> (RulesetType, &str) {
+    match get_ruleset_type_from_url(rule_path_typed) {
+        Some(ruleset_type) => {
+            let prefix_len = RULESET_TYPES
+                .iter()
+                .find(|(prefix, t)| **t == ruleset_type && rule_path_typed.starts_with(*prefix))
+                .map(|(prefix, _)| prefix.len())
+                .unwrap_or(0);
+            (ruleset_type, &rule_path_typed[prefix_len..])
+        }
+        None => (RulesetType::default(), rule_path_typed),
+    }
+}
+
+/// Whether a ruleset path may be fetched on behalf of a client
+///
+/// The API mode makes no difference here: rulesets referenced by external
+/// configurations are served too, but local files only when they resolve into
+/// the configured rules directory.
+fn is_servable_ruleset(rule_path: &str, global: &Settings) -> bool {
+    if rule_path.starts_with("http://") || rule_path.starts_with("https://") {
+        return true;
+    }
+    match (
+        std::fs::canonicalize(rule_path),
+        std::fs::canonicalize(&global.rules_path),
+    ) {
+        (Ok(path), Ok(rules_dir)) => path.starts_with(rules_dir),
+        _ => false,
+    }
+}
+
+/// Handler serving a ruleset converted for the client referencing it
+///
+/// Generated configurations point at this endpoint instead of inlining the
+/// rules, so clients download and refresh each ruleset on their own.
+pub async fn getruleset_process(
+    query: GetRulesetQuery,
+) -> Result<SubResponse, Box<dyn std::error::Error>> {
+    if Settings::current().pref_path.is_empty() {
+        debug!("Global config not initialized, reloading");
+        init_settings("").await?;
+    }
+    let global = Settings::current().clone();
+
+    let (Some(ruleset_type), Some(url)) = (query.ruleset_type, query.url.as_deref()) else {
+        return Ok(SubResponse::error("Invalid request!".to_string(), 400));
+    };
+
+    let rule_path_typed = url_safe_base64_decode(url);
+    let (rule_type, rule_path) = split_ruleset_path(&rule_path_typed);
+
+    if !is_servable_ruleset(rule_path, &global) {
+        return Ok(SubResponse::error("Forbidden".to_string(), 403));
+    }
+
+    let proxy = parse_proxy(&global.proxy_ruleset);
+    let content = match fetch_ruleset(
+        rule_path,
+        &proxy,
+        global.cache_ruleset,
+        global.async_fetch_ruleset,
+    )
+    .await
+    {
+        Ok(content) if !content.is_empty() => content,
+        Ok(_) => return Ok(SubResponse::error("Ruleset is empty".to_string(), 400)),
+        Err(e) => {
+            error!("Failed to fetch ruleset '{}': {}", rule_path, e);
+            return Ok(SubResponse::error(
+                format!("Failed to fetch ruleset: {}", e),
+                400,
+            ));
+        }
+    };
+
+    match ruleset_type {
+        // Surge and Loon RULE-SET list
+        1 => Ok(SubResponse::ok(
+            ruleset_to_surge_list(&content, rule_type, 4, None),
+            "text/plain".to_string(),
+        )),
+        // Quantumult X filter_remote list, every rule carries the policy group
+        2 => {
+            let Some(group) = query.group.as_deref().map(url_safe_base64_decode) else {
+                return Ok(SubResponse::error("Invalid request!".to_string(), 400));
+            };
+            Ok(SubResponse::ok(
+                ruleset_to_surge_list(&content, rule_type, -1, Some(&group)),
+                "text/plain".to_string(),
+            ))
+        }
+        // Clash domain, ipcidr and classical rule provider payloads
+        3 | 4 | 6 => {
+            let target = query
+                .target
+                .as_deref()
+                .and_then(SubconverterTarget::from_str)
+                .unwrap_or(SubconverterTarget::Clash);
+            match ruleset_to_clash_payload(&content, rule_type, ruleset_type, &target) {
+                Some(payload) => Ok(SubResponse::ok(payload, "text/yaml".to_string())),
+                None => Ok(SubResponse::error("Invalid request!".to_string(), 400)),
+            }
+        }
+        SINGBOX_RULESET_TYPE => {
+            let source = ruleset_to_sing_box_source(&content, rule_type);
+            Ok(SubResponse::ok(
+                serde_json::to_string_pretty(&source)?,
+                "application/json".to_string(),
+            ))
+        }
+        _ => Ok(SubResponse::error("Invalid request!".to_string(), 400)),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn getruleset_process_wasm(query_json: &str) -> Promise {
+    let query = match serde_json::from_str::<GetRulesetQuery>(query_json) {
+        Ok(q) => q,
+        Err(e) => {
+            return Promise::reject(&JsValue::from_str(&format!("Failed to parse query: {}", e)));
+        }
+    };
+
+    let future = async move {
+        match getruleset_process(query).await {
+            Ok(response) => match serde_json::to_string(&response) {
+                Ok(json) => Ok(JsValue::from_str(&json)),
+                Err(e) => Err(JsValue::from_str(&format!(
+                    "Failed to serialize response: {}",
+                    e
+                ))),
+            },
+            Err(e) => Err(JsValue::from_str(&format!(
+                "Ruleset processing error: {}",
+                e
+            ))),
+        }
+    };
+
+    future_to_promise(future)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_ruleset_path() {
+        assert_eq!(
+            split_ruleset_path("clash-domain:https://example.com/reject.yaml"),
+            (RulesetType::ClashDomain, "https://example.com/reject.yaml")
+        );
+        assert_eq!(
+            split_ruleset_path("rules/LocalAreaNetwork.list"),
+            (RulesetType::Surge, "rules/LocalAreaNetwork.list")
+        );
+        assert_eq!(
+            split_ruleset_path("singbox:https://example.com/geosite-cn.json"),
+            (RulesetType::SingBox, "https://example.com/geosite-cn.json")
+        );
+    }
+
+    #[test]
+    fn test_servable_rulesets() {
+        let dir = tempfile::tempdir().unwrap();
+        let rules_dir = dir.path().join("rules");
+        std::fs::create_dir(&rules_dir).unwrap();
+        let ruleset = rules_dir.join("reject.list");
+        std::fs::write(&ruleset, "DOMAIN-SUFFIX,ads.example.com").unwrap();
+        let secret = dir.path().join("pref.ini");
+        std::fs::write(&secret, "api_access_token=secret").unwrap();
+
+        let ruleset = ruleset.to_str().unwrap().to_string();
+        let escaped = format!("{}/../pref.ini", rules_dir.to_str().unwrap());
+        for api_mode in [false, true] {
+            let global = Settings {
+                api_mode,
+                rules_path: rules_dir.to_str().unwrap().to_string(),
+                ..Default::default()
+            };
+
+            assert!(is_servable_ruleset(
+                "https://example.com/reject.list",
+                &global
+            ));
+            // Not listed in custom_rulesets, as for external configurations
+            assert!(is_servable_ruleset(&ruleset, &global));
+            assert!(!is_servable_ruleset(secret.to_str().unwrap(), &global));
+            assert!(!is_servable_ruleset(&escaped, &global));
+            assert!(!is_servable_ruleset("/etc/passwd", &global));
+            assert!(!is_servable_ruleset("data:text/plain,GEOIP,CN", &global));
+        }
+    }
+
+    #[test]
+    fn test_surge_rule_lists() {
+        let content = "payload:\n  - '+.example.com'\n  - '1.1.1.0/24'\n";
+
+        assert_eq!(
+            ruleset_to_surge_list(content, RulesetType::ClashDomain, 4, None),
+            "DOMAIN-SUFFIX,example.com\nIP-CIDR,1.1.1.0/24"
+        );
+        assert_eq!(
+            ruleset_to_surge_list(content, RulesetType::ClashDomain, -1, Some("Proxy")),
+            "DOMAIN-SUFFIX,example.com,Proxy\nIP-CIDR,1.1.1.0/24,Proxy"
+        );
+    }
+
+    #[test]
+    fn test_clash_rule_payloads() {
+        let content = "DOMAIN-SUFFIX,example.com\nDOMAIN-KEYWORD,ads\nIP-CIDR,1.1.1.0/24,no-resolve\nUSER-AGENT,Example*";
+
+        assert_eq!(
+            ruleset_to_clash_payload(content, RulesetType::Surge, 3, &SubconverterTarget::Clash)
+                .unwrap(),
+            "payload:\n  - '+.example.com'\n"
+        );
+        assert_eq!(
+            ruleset_to_clash_payload(content, RulesetType::Surge, 4, &SubconverterTarget::Clash)
+                .unwrap(),
+            "payload:\n  - '1.1.1.0/24'\n"
+        );
+        assert_eq!(
+            ruleset_to_clash_payload(content, RulesetType::Surge, 6, &SubconverterTarget::Clash).unwrap(),
+            "payload:\n  - 'DOMAIN-SUFFIX,example.com'\n  - 'DOMAIN-KEYWORD,ads'\n  - 'IP-CIDR,1.1.1.0/24,no-resolve'\n"
+        );
+        assert!(ruleset_to_clash_payload(
+            content,
+            RulesetType::Surge,
+            5,
+            &SubconverterTarget::Clash
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_mihomo_rule_payloads() {
+        let content = "DOMAIN-SUFFIX,example.com\nGEOSITE,category-ads\nAND,((NETWORK,UDP),(DST-PORT,443))\nUSER-AGENT,Example*";
+
+        assert_eq!(
+            ruleset_to_clash_payload(content, RulesetType::Surge, 6, &SubconverterTarget::Mihomo)
+                .unwrap(),
+            "payload:\n  - 'DOMAIN-SUFFIX,example.com'\n  - 'GEOSITE,category-ads'\n  - 'AND,((NETWORK,UDP),(DST-PORT,443))'\n"
+        );
+        // Plain Clash drops the mihomo-only rules
+        assert_eq!(
+            ruleset_to_clash_payload(content, RulesetType::Surge, 6, &SubconverterTarget::Clash)
+                .unwrap(),
+            "payload:\n  - 'DOMAIN-SUFFIX,example.com'\n"
+        );
+    }
+}
@@ -219,6 +219,15 @@ pub struct SubconverterQuery {
     )]
     pub expand: Option<bool>,
 
+    /// Targeted sing-box version, e.g. 1.10 for the legacy schema
+    pub singbox_version: Option<String>,
+    /// Reference domain set rulesets as Surge `DOMAIN-SET` rules
+    #[serde(
+        default,
+        deserialize_with = "bool_deserializer::deserialize_option_bool"
+    )]
+    pub surge_domain_set: Option<bool>,
+
     /// Singbox specific parameters
     #[serde(default)]
     pub singbox: HashMap<String, String>,
@@ -408,8 +417,9 @@ pub async fn sub_process(
 
     builder.append_proxy_type(query.append_type.unwrap_or(global.append_type));
 
+    // Clash expands rulesets unless rule providers are asked for with expand=false
     let mut arg_expand_rulesets = query.expand;
-    if target.is_clash() && query.script.is_none() {
+    if target.is_clash() && query.script.is_none() && query.expand.is_none() {
         arg_expand_rulesets = Some(true);
     }
 
@@ -425,11 +435,22 @@ pub async fn sub_process(
     builder.clash_new_field_name(query.new_name.unwrap_or(global.clash_use_new_field));
     builder.clash_script(query.script.unwrap_or_default());
     builder.clash_classical_ruleset(query.classic.unwrap_or_default());
+    builder.singbox_version(
+        query
+            .singbox_version
+            .clone()
+            .unwrap_or(global.singbox_version.clone()),
+    );
+    builder.surge_domain_set(query.surge_domain_set.unwrap_or_default());
     let nodelist = query.list.unwrap_or_default();
     builder.nodelist(nodelist);
 
     if arg_expand_rulesets != Some(true) {
         builder.clash_new_field_name(true);
+        // Reference local and converted rulesets through /getruleset
+        if arg_expand_rulesets == Some(false) {
+            builder.remote_ruleset_prefix(global.managed_config_prefix.clone());
+        }
     } else {
         builder.managed_config_prefix(global.managed_config_prefix.clone());
         builder.clash_script(false);
@@ -454,8 +475,10 @@ pub async fn sub_process(
         quan_rule_base: global.quan_base.clone(),
         quanx_rule_base: global.quanx_base.clone(),
         loon_rule_base: global.loon_base.clone(),
+        shadowrocket_rule_base: global.shadowrocket_base.clone(),
         sssub_rule_base: global.ssub_base.clone(),
         singbox_rule_base: global.singbox_base.clone(),
+        xray_rule_base: global.xray_base.clone(),
     };
     builder.rule_bases(rule_bases.clone());
     builder.template_args(template_args.clone());
@@ -636,8 +659,12 @@ pub async fn sub_process(
             let content_type = match target {
                 SubconverterTarget::Clash
                 | SubconverterTarget::ClashR
+                | SubconverterTarget::Mihomo
+                | SubconverterTarget::Stash
                 | SubconverterTarget::SingBox => "application/yaml",
-                SubconverterTarget::SSSub | SubconverterTarget::SSD => "application/json",
+                SubconverterTarget::SSSub | SubconverterTarget::SSD | SubconverterTarget::Xray => {
+                    "application/json"
+                }
                 _ => "text/plain",
             };
 
@@ -1,12 +1,14 @@
 use crate::generator::config::group::group_generate;
 use crate::generator::config::remark::process_remark;
 use crate::generator::ruleconvert::ruleset_to_surge::ruleset_to_surge;
+use crate::models::proxy_node::combined::CombinedProxy;
 use crate::models::{
     BalanceStrategy, ExtraSettings, Proxy, ProxyGroupConfigs, ProxyGroupType, ProxyType,
     RulesetContent,
 };
 use crate::utils::ini_reader::IniReader;
 use crate::utils::string::join;
+use crate::utils::tribool::TriboolExt;
 use log::error;
 use std::collections::HashMap;
 
@@ -210,6 +212,123 @@ pub async fn proxy_to_loon(
                     ));
                 }
             }
+            ProxyType::Vless => {
+                let vless = match &node.combined_proxy {
+                    Some(CombinedProxy::Vless(vless)) => vless,
+                    _ => continue,
+                };
+
+                proxy = format!("VLESS,{},{},\"{}\"", hostname, port, vless.uuid);
+
+                match vless.network.as_deref().unwrap_or("tcp") {
+                    "tcp" => {
+                        proxy.push_str(",transport=tcp");
+                    }
+                    "ws" => {
+                        let ws_host = vless
+                            .ws_headers
+                            .as_ref()
+                            .and_then(|headers| headers.get("Host"))
+                            .map(|host| host.as_str())
+                            .unwrap_or(host);
+                        proxy.push_str(&format!(
+                            ",transport=ws,path={},host={}",
+                            vless.ws_path.as_deref().unwrap_or(path),
+                            ws_host
+                        ));
+                    }
+                    "http" => {
+                        let http_host = vless
+                            .http_headers
+                            .as_ref()
+                            .and_then(|headers| headers.get("Host"))
+                            .and_then(|hosts| hosts.first())
+                            .map(|host| host.as_str())
+                            .unwrap_or(host);
+                        proxy.push_str(&format!(
+                            ",transport=http,path={},host={}",
+                            vless.http_path.as_deref().unwrap_or(path),
+                            http_host
+                        ));
+                    }
+                    _ => continue,
+                }
+
+                proxy.push_str(&format!(",over-tls={}", vless.tls));
+
+                if vless.tls {
+                    let sni = vless.servername.as_deref().unwrap_or(host);
+                    if !sni.is_empty() {
+                        proxy.push_str(&format!(",sni={}", sni));
+                    }
+                }
+
+                if let Some(flow) = vless.flow.as_deref().filter(|flow| !flow.is_empty()) {
+                    proxy.push_str(&format!(",flow={}", flow));
+                }
+
+                // REALITY
+                if let Some(public_key) = &vless.reality_public_key {
+                    proxy.push_str(&format!(",public-key=\"{}\"", public_key));
+
+                    if let Some(short_id) = &vless.reality_short_id {
+                        proxy.push_str(&format!(",short-id={}", short_id));
+                    }
+                }
+
+                if let Some(scv) = vless.skip_cert_verify.define(scv) {
+                    proxy.push_str(&format!(",skip-cert-verify={}", scv));
+                }
+            }
+            ProxyType::Hysteria2 => {
+                proxy = format!("Hysteria2,{},{},\"{}\"", hostname, port, password);
+
+                if let Some(sni) = node.sni.as_deref().filter(|sni| !sni.is_empty()) {
+                    proxy.push_str(&format!(",sni={}", sni));
+                }
+
+                if obfs == "salamander" && !obfsparam.is_empty() {
+                    proxy.push_str(&format!(",salamander-password={}", obfsparam));
+                } else if !obfs.is_empty() {
+                    continue;
+                }
+
+                if node.down_speed > 0 {
+                    proxy.push_str(&format!(",download-bandwidth={}", node.down_speed));
+                }
+
+                if let Some(scv) = scv {
+                    proxy.push_str(&format!(",skip-cert-verify={}", scv));
+                }
+            }
+            ProxyType::AnyTls => {
+                let anytls = match &node.combined_proxy {
+                    Some(CombinedProxy::AnyTls(anytls)) => anytls,
+                    _ => continue,
+                };
+
+                proxy = format!("AnyTLS,{},{},\"{}\"", hostname, port, anytls.password);
+
+                if let Some(sni) = anytls.sni.as_deref().or(node.sni.as_deref()) {
+                    proxy.push_str(&format!(",sni={}", sni));
+                }
+
+                if let Some(interval) = anytls.idle_session_check_interval {
+                    proxy.push_str(&format!(",idle-session-check-interval={}", interval));
+                }
+
+                if let Some(timeout) = anytls.idle_session_timeout {
+                    proxy.push_str(&format!(",idle-session-timeout={}", timeout));
+                }
+
+                if let Some(min_idle) = anytls.min_idle_session {
+                    proxy.push_str(&format!(",min-idle-session={}", min_idle));
+                }
+
+                if let Some(scv) = anytls.skip_cert_verify.define(scv) {
+                    proxy.push_str(&format!(",skip-cert-verify={}", scv));
+                }
+            }
             ProxyType::Socks5 => {
                 proxy = format!("socks5,{},{}", hostname, port);
 
@@ -456,6 +575,100 @@ async fn ruleset_to_loon(
         -4,
         overwrite_original_rules,
         managed_config_prefix,
+        false,
     )
     .await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::test_utils::{
+        block_on, nodes, rule_generator_settings, ruleset, select_group,
+    };
+    use crate::models::RulesetType;
+    use crate::utils::base64::url_safe_base64_encode;
+
+    const NODES: &str = r#"
+proxies:
+  - { name: reality, type: vless, server: vless.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, servername: sni.example.com, flow: xtls-rprx-vision, reality-opts: { public-key: pbk, short-id: sid } }
+  - { name: vless-ws, type: vless, server: ws.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000003, tls: true, network: ws, ws-opts: { path: /ws, headers: { Host: cdn.example.com } } }
+  - { name: hy2, type: hysteria2, server: hy2.example.com, port: 443, password: pw, obfs: salamander, obfs-password: secret, down: 200Mbps, sni: hy2.example.com }
+  - { name: anytls, type: anytls, server: anytls.example.com, port: 443, password: pw, sni: anytls.example.com, skip-cert-verify: true }
+"#;
+
+    fn generate(rulesets: &mut Vec<RulesetContent>, remote_path_prefix: &str) -> String {
+        let mut ext = rule_generator_settings();
+        ext.managed_config_prefix = remote_path_prefix.to_string();
+        block_on(proxy_to_loon(
+            &mut nodes(NODES),
+            "[General]\nskip-proxy = 127.0.0.1\n",
+            rulesets,
+            &vec![select_group()],
+            &mut ext,
+        ))
+    }
+
+    #[test]
+    fn test_loon_proxies() {
+        let output = generate(&mut Vec::new(), "");
+
+        assert!(output.contains(
+            "reality = VLESS,vless.example.com,443,\"00000000-0000-0000-0000-000000000002\",transport=tcp,over-tls=true,sni=sni.example.com,flow=xtls-rprx-vision,public-key=\"pbk\",short-id=sid"
+        ));
+        assert!(output.contains(
+            "vless-ws = VLESS,ws.example.com,443,\"00000000-0000-0000-0000-000000000003\",transport=ws,path=/ws,host=cdn.example.com,over-tls=true"
+        ));
+        assert!(output.contains(
+            "hy2 = Hysteria2,hy2.example.com,443,\"pw\",sni=hy2.example.com,salamander-password=secret,download-bandwidth=200"
+        ));
+        assert!(output.contains(
+            "anytls = AnyTLS,anytls.example.com,443,\"pw\",sni=anytls.example.com,skip-cert-verify=true"
+        ));
+        assert!(output.contains("Proxy = select,reality,vless-ws,hy2,anytls"));
+    }
+
+    #[test]
+    fn test_loon_remote_rule() {
+        let surge_list = "https://example.com/rules/Netflix.list";
+        let clash_yaml = "https://example.com/rules/reject.yaml";
+        let mut rulesets = vec![
+            ruleset(surge_list, RulesetType::Surge, "DOMAIN-SUFFIX,netflix.com"),
+            ruleset(
+                clash_yaml,
+                RulesetType::ClashDomain,
+                "payload:\n  - '+.ads.example.com'\n",
+            ),
+        ];
+        rulesets[1].rule_path_typed = format!("clash-domain:{}", clash_yaml);
+
+        // Surge lists are referenced as they are, Clash payloads are expanded
+        let output = generate(&mut rulesets, "");
+        assert!(output.contains(
+            "[Remote Rule]\nhttps://example.com/rules/Netflix.list, policy=Proxy, tag=Netflix, enabled=true"
+        ));
+        assert!(output.contains("DOMAIN-SUFFIX,ads.example.com,Proxy"));
+
+        // Clash payloads go through /getruleset once a prefix is available
+        let output = generate(&mut rulesets, "http://127.0.0.1:25500");
+        assert!(output.contains(&format!(
+            "http://127.0.0.1:25500/getruleset?type=1&url={}, policy=Proxy, tag=reject, enabled=true",
+            url_safe_base64_encode(&rulesets[1].rule_path_typed)
+        )));
+        assert!(!output.contains("DOMAIN-SUFFIX,ads.example.com,Proxy"));
+    }
+
+    #[test]
+    fn test_loon_logical_rules() {
+        let mut rulesets = vec![ruleset(
+            "https://example.com/rules/logical.yaml",
+            RulesetType::ClashClassical,
+            "payload:\n  - 'AND,((DOMAIN-SUFFIX,example.com), (NOT,((DEST-PORT,443))))'\n  - 'OR,((DOMAIN,a.com),(GEOSITE,cn))'\n",
+        )];
+
+        let output = generate(&mut rulesets, "");
+        assert!(output.contains("AND,((DOMAIN-SUFFIX,example.com),(NOT,((DEST-PORT,443)))),Proxy"));
+        // GEOSITE cannot be expressed in Loon, the whole rule is dropped
+        assert!(!output.contains("GEOSITE"));
+    }
+}
@@ -276,6 +276,7 @@ async fn proxy_to_mellow_internal(
             0,
             ext.overwrite_original_rules,
             "",
+            false,
         )
         .await;
     }
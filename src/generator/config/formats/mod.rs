@@ -2,8 +2,12 @@ pub mod loon;
 pub mod mellow;
 pub mod quan;
 pub mod quanx;
+pub mod shadowrocket;
 pub mod singbox;
 pub mod single;
 pub mod ss_sub;
 pub mod ssd;
 pub mod surge;
+#[cfg(test)]
+pub mod test_utils;
+pub mod xray;
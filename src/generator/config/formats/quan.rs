@@ -453,6 +453,7 @@ async fn proxy_to_quan_internal(
             -2,
             ext.overwrite_original_rules,
             "",
+            false,
         )
         .await;
     }
@@ -1,12 +1,13 @@
 use crate::generator::config::group::group_generate;
 use crate::generator::config::remark::process_remark;
 use crate::generator::ruleconvert::ruleset_to_surge::ruleset_to_surge;
+use crate::models::proxy_node::combined::CombinedProxy;
 use crate::models::{
     ExtraSettings, Proxy, ProxyGroupConfigs, ProxyGroupType, ProxyType, RulesetContent,
 };
 use crate::utils::ini_reader::IniReader;
 use crate::utils::string::{hash, join, replace_all_distinct, trim};
-use crate::utils::tribool::BoolTriboolExt;
+use crate::utils::tribool::{BoolTriboolExt, TriboolExt};
 use crate::utils::url::get_url_arg;
 use log::error;
 
@@ -292,6 +293,60 @@ async fn proxy_to_quanx_internal(
                     _proxy_str.push_str(", over-tls=false");
                 }
             }
+            ProxyType::Vless => {
+                let vless = match &node.combined_proxy {
+                    Some(CombinedProxy::Vless(vless)) => vless,
+                    _ => continue,
+                };
+
+                _proxy_str = format!(
+                    "vless = {}:{}, method=none, password={}",
+                    hostname, port, vless.uuid
+                );
+
+                let sni = vless.servername.as_deref().unwrap_or(host);
+                match vless.network.as_deref().unwrap_or("tcp") {
+                    "ws" => {
+                        let ws_host = vless
+                            .ws_headers
+                            .as_ref()
+                            .and_then(|headers| headers.get("Host"))
+                            .map(|host| host.as_str())
+                            .unwrap_or(sni);
+                        _proxy_str.push_str(&format!(
+                            ", obfs={}, obfs-host={}, obfs-uri={}",
+                            if vless.tls { "wss" } else { "ws" },
+                            ws_host,
+                            vless.ws_path.as_deref().unwrap_or(path)
+                        ));
+                    }
+                    "tcp" => {
+                        if vless.tls {
+                            _proxy_str.push_str(&format!(", obfs=over-tls, obfs-host={}", sni));
+                        }
+                    }
+                    _ => continue,
+                }
+
+                if let Some(flow) = vless.flow.as_deref().filter(|flow| !flow.is_empty()) {
+                    _proxy_str.push_str(&format!(", vless-flow={}", flow));
+                }
+
+                // REALITY
+                if let Some(public_key) = &vless.reality_public_key {
+                    _proxy_str.push_str(&format!(", reality-base64-pubkey={}", public_key));
+
+                    if let Some(short_id) = &vless.reality_short_id {
+                        _proxy_str.push_str(&format!(", reality-hex-shortid={}", short_id));
+                    }
+                }
+
+                if vless.tls {
+                    if let Some(scv) = vless.skip_cert_verify.define(scv) {
+                        _proxy_str.push_str(&format!(", tls-verification={}", !scv));
+                    }
+                }
+            }
             ProxyType::Trojan => {
                 _proxy_str = format!("trojan = {}:{}, password={}", hostname, port, password);
 
@@ -365,6 +420,7 @@ async fn proxy_to_quanx_internal(
             && !scv.is_undef()
             && node.proxy_type != ProxyType::Shadowsocks
             && node.proxy_type != ProxyType::ShadowsocksR
+            && node.proxy_type != ProxyType::Vless
         {
             _proxy_str.push_str(&format!(
                 ", tls-verification={}",
@@ -488,7 +544,83 @@ async fn proxy_to_quanx_internal(
             -1,
             ext.overwrite_original_rules,
             &ext.managed_config_prefix,
+            false,
         )
         .await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::test_utils::{
+        block_on, nodes, rule_generator_settings, ruleset, select_group,
+    };
+    use crate::models::RulesetType;
+    use crate::utils::base64::url_safe_base64_encode;
+
+    const NODES: &str = r#"
+proxies:
+  - { name: reality, type: vless, server: vless.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, servername: sni.example.com, flow: xtls-rprx-vision, reality-opts: { public-key: pbk, short-id: sid } }
+  - { name: vless-ws, type: vless, server: ws.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000003, tls: true, skip-cert-verify: true, network: ws, ws-opts: { path: /ws, headers: { Host: cdn.example.com } } }
+"#;
+
+    fn generate(rulesets: &mut Vec<RulesetContent>, remote_path_prefix: &str) -> String {
+        let mut ext = rule_generator_settings();
+        ext.managed_config_prefix = remote_path_prefix.to_string();
+        block_on(proxy_to_quanx(
+            &mut nodes(NODES),
+            "[general]\nnetwork_check_url=http://www.gstatic.com/generate_204\n",
+            rulesets,
+            &vec![select_group()],
+            &mut ext,
+        ))
+    }
+
+    #[test]
+    fn test_quanx_vless() {
+        let output = generate(&mut Vec::new(), "");
+
+        assert!(output.contains(
+            "vless = vless.example.com:443, method=none, password=00000000-0000-0000-0000-000000000002, obfs=over-tls, obfs-host=sni.example.com, vless-flow=xtls-rprx-vision, reality-base64-pubkey=pbk, reality-hex-shortid=sid, tag=reality"
+        ));
+        assert!(output.contains(
+            "vless = ws.example.com:443, method=none, password=00000000-0000-0000-0000-000000000003, obfs=wss, obfs-host=cdn.example.com, obfs-uri=/ws, tls-verification=false, tag=vless-ws"
+        ));
+    }
+
+    #[test]
+    fn test_quanx_filter_remote() {
+        let quanx_list = "https://example.com/rules/Netflix.list";
+        let surge_list = "https://example.com/surge/Netflix.list";
+        let mut rulesets = vec![
+            ruleset(
+                quanx_list,
+                RulesetType::Quanx,
+                "HOST-SUFFIX,netflix.com,Proxy",
+            ),
+            ruleset(
+                surge_list,
+                RulesetType::Surge,
+                "DOMAIN-SUFFIX,nflxvideo.net",
+            ),
+        ];
+        rulesets[0].update_interval = 86400;
+
+        // Surge lists are expanded into filter_local without a prefix
+        let output = generate(&mut rulesets, "");
+        assert!(output.contains(
+            "[filter_remote]\nhttps://example.com/rules/Netflix.list, tag=Netflix, force-policy=Proxy, update-interval=86400, enabled=true"
+        ));
+        assert!(output.contains("DOMAIN-SUFFIX,nflxvideo.net,Proxy"));
+
+        // and referenced through /getruleset with one, under a unique tag
+        let output = generate(&mut rulesets, "http://127.0.0.1:25500");
+        assert!(output.contains(&format!(
+            "http://127.0.0.1:25500/getruleset?type=2&url={}&group={}, tag=Netflix_2, force-policy=Proxy, enabled=true",
+            url_safe_base64_encode(surge_list),
+            url_safe_base64_encode("Proxy")
+        )));
+        assert!(!output.contains("DOMAIN-SUFFIX,nflxvideo.net,Proxy"));
+    }
+}
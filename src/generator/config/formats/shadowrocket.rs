@@ -0,0 +1,504 @@
+use crate::generator::config::group::group_generate;
+use crate::generator::config::remark::process_remark;
+use crate::generator::ruleconvert::ruleset_to_surge::ruleset_to_surge;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::{
+    ExtraSettings, Proxy, ProxyGroupConfigs, ProxyGroupType, ProxyType, RulesetContent,
+};
+use crate::utils::ini_reader::IniReader;
+use crate::utils::string::{join, to_lower};
+use crate::utils::tribool::TriboolExt;
+use log::error;
+
+/// Appends a `, key=true|false` option if the value is defined
+fn push_bool_option(proxy: &mut String, key: &str, value: Option<bool>) {
+    if let Some(value) = value {
+        proxy.push_str(&format!(", {}={}", key, value));
+    }
+}
+
+/// Appends a `, key=value` option if the value is not empty
+fn push_option(proxy: &mut String, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        if !value.is_empty() {
+            proxy.push_str(&format!(", {}={}", key, value));
+        }
+    }
+}
+
+/// Appends the transport options shared by VMess and VLESS nodes
+///
+/// Returns false if the transport cannot be expressed in Shadowrocket.
+fn push_transport(
+    proxy: &mut String,
+    network: &str,
+    host: &str,
+    path: &str,
+    service_name: &str,
+) -> bool {
+    match network {
+        "" | "tcp" => {}
+        "ws" => {
+            proxy.push_str(&format!(", ws=true, ws-path={}", path));
+            if !host.is_empty() {
+                proxy.push_str(&format!(", ws-headers=Host:{}", host));
+            }
+        }
+        "grpc" => {
+            proxy.push_str(", grpc=true");
+            push_option(proxy, "grpc-service-name", Some(service_name));
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Convert proxies to Shadowrocket format
+///
+/// Shadowrocket reads Surge style configuration, so this follows the Surge
+/// generator and adds Shadowrocket's own syntax for VLESS, Hysteria2 and TUIC.
+///
+/// # Arguments
+/// * `nodes` - List of proxy nodes to convert
+/// * `base_conf` - Base Shadowrocket configuration as a string
+/// * `ruleset_content_array` - Array of ruleset contents to apply
+/// * `extra_proxy_group` - Extra proxy group configurations
+/// * `ext` - Extra settings for conversion
+///
+/// # Returns
+/// * Converted configuration as a string
+pub async fn proxy_to_shadowrocket(
+    nodes: &mut Vec<Proxy>,
+    base_conf: &str,
+    ruleset_content_array: &mut Vec<RulesetContent>,
+    extra_proxy_group: &ProxyGroupConfigs,
+    ext: &mut ExtraSettings,
+) -> String {
+    let mut ini = IniReader::new();
+    let mut output_nodelist = String::new();
+    let mut nodelist = Vec::new();
+    let mut remarks_list = Vec::new();
+
+    // Configure INI reader
+    ini.store_any_line = true;
+
+    // Add direct save sections
+    let direct_save_sections = vec![
+        "General",
+        "Rule",
+        "Host",
+        "URL Rewrite",
+        "Header Rewrite",
+        "Script",
+        "MITM",
+    ];
+    for section in direct_save_sections {
+        ini.add_direct_save_section(section);
+    }
+
+    // Parse base configuration
+    if ini.parse(base_conf).is_err() && !ext.nodelist {
+        error!(
+            "Shadowrocket base loader failed with error: {}",
+            ini.get_last_error()
+        );
+        return String::new();
+    }
+
+    // Prepare Proxy section
+    ini.set_current_section("Proxy");
+    ini.erase_section();
+
+    // Process each proxy node
+    for node in nodes.iter_mut() {
+        // Add proxy type prefix if enabled
+        if ext.append_proxy_type {
+            let proxy_type = node.proxy_type.to_string();
+            node.remark = format!("[{}] {}", proxy_type, node.remark);
+        }
+
+        // Process remark
+        let mut remark = node.remark.clone();
+        process_remark(&mut remark, &remarks_list, false);
+
+        // Extract node properties for easier access
+        let hostname = &node.hostname;
+        let port = node.port;
+        let username = node.username.as_deref().unwrap_or("");
+        let password = node.password.as_deref().unwrap_or("");
+        let method = node.encrypt_method.as_deref().unwrap_or("");
+        let id = node.user_id.as_deref().unwrap_or("");
+        let transproto = node.transfer_protocol.as_deref().unwrap_or("");
+        let host = node.host.as_deref().unwrap_or("");
+        let path = node.path.as_deref().unwrap_or("");
+        let plugin = node.plugin.as_deref().unwrap_or("");
+        let pluginopts = node.plugin_option.as_deref().unwrap_or("");
+        let tls_secure = node.tls_secure;
+
+        // Define tribool values with defaults from ext and override with node-specific values
+        let udp = node.udp.define(ext.udp);
+        let tfo = node.tcp_fast_open.define(ext.tfo);
+        let scv = node.allow_insecure.define(ext.skip_cert_verify);
+
+        let mut proxy;
+
+        // Build proxy string based on type
+        match node.proxy_type {
+            ProxyType::Shadowsocks => {
+                proxy = format!(
+                    "ss, {}, {}, encrypt-method={}, password={}",
+                    hostname, port, method, password
+                );
+
+                if !plugin.is_empty() {
+                    match plugin {
+                        "simple-obfs" | "obfs-local" => {
+                            if !pluginopts.is_empty() {
+                                proxy.push_str(&format!(", {}", pluginopts.replace(';', ", ")));
+                            }
+                        }
+                        _ => continue,
+                    }
+                }
+            }
+            ProxyType::ShadowsocksR => {
+                proxy = format!(
+                    "ssr, {}, {}, encrypt-method={}, password={}, protocol={}, obfs={}",
+                    hostname,
+                    port,
+                    method,
+                    password,
+                    node.protocol.as_deref().unwrap_or(""),
+                    node.obfs.as_deref().unwrap_or("")
+                );
+                push_option(&mut proxy, "protocol-param", node.protocol_param.as_deref());
+                push_option(&mut proxy, "obfs-param", node.obfs_param.as_deref());
+            }
+            ProxyType::VMess => {
+                proxy = format!(
+                    "vmess, {}, {}, username={}, alterId={}, tls={}",
+                    hostname, port, id, node.alter_id, tls_secure
+                );
+
+                if !push_transport(&mut proxy, transproto, host, path, path) {
+                    continue;
+                }
+
+                if tls_secure {
+                    push_option(
+                        &mut proxy,
+                        "sni",
+                        node.server_name.as_deref().or(Some(host)),
+                    );
+                }
+                push_bool_option(&mut proxy, "skip-cert-verify", scv);
+            }
+            ProxyType::Vless => {
+                let vless = match &node.combined_proxy {
+                    Some(CombinedProxy::Vless(vless)) => vless,
+                    _ => continue,
+                };
+                let network = vless.network.as_deref().unwrap_or("tcp");
+                let ws_host = vless
+                    .ws_headers
+                    .as_ref()
+                    .and_then(|headers| headers.get("Host"))
+                    .map(|host| host.as_str())
+                    .unwrap_or(host);
+
+                proxy = format!(
+                    "vless, {}, {}, username={}, tls={}",
+                    hostname, port, vless.uuid, vless.tls
+                );
+
+                if !push_transport(
+                    &mut proxy,
+                    network,
+                    ws_host,
+                    vless.ws_path.as_deref().unwrap_or(path),
+                    vless.grpc_service_name.as_deref().unwrap_or(""),
+                ) {
+                    continue;
+                }
+
+                push_option(&mut proxy, "flow", vless.flow.as_deref());
+                push_option(&mut proxy, "sni", vless.servername.as_deref());
+                push_option(
+                    &mut proxy,
+                    "public-key",
+                    vless.reality_public_key.as_deref(),
+                );
+                push_option(&mut proxy, "short-id", vless.reality_short_id.as_deref());
+                push_option(
+                    &mut proxy,
+                    "client-fingerprint",
+                    vless.client_fingerprint.as_deref(),
+                );
+                push_bool_option(
+                    &mut proxy,
+                    "skip-cert-verify",
+                    vless.skip_cert_verify.define(scv),
+                );
+            }
+            ProxyType::Trojan => {
+                proxy = format!("trojan, {}, {}, password={}", hostname, port, password);
+
+                if !push_transport(&mut proxy, transproto, host, path, path) {
+                    continue;
+                }
+
+                push_option(
+                    &mut proxy,
+                    "sni",
+                    node.server_name.as_deref().or(Some(host)),
+                );
+                push_bool_option(&mut proxy, "skip-cert-verify", scv);
+            }
+            ProxyType::HTTP | ProxyType::HTTPS => {
+                let scheme = if node.proxy_type == ProxyType::HTTPS {
+                    "https"
+                } else {
+                    "http"
+                };
+                proxy = format!("{}, {}, {}", scheme, hostname, port);
+                push_option(&mut proxy, "username", Some(username));
+                push_option(&mut proxy, "password", Some(password));
+                push_bool_option(&mut proxy, "skip-cert-verify", scv);
+            }
+            ProxyType::Socks5 => {
+                proxy = format!("socks5, {}, {}", hostname, port);
+                push_option(&mut proxy, "username", Some(username));
+                push_option(&mut proxy, "password", Some(password));
+            }
+            ProxyType::Snell => {
+                proxy = format!("snell, {}, {}, psk={}", hostname, port, password);
+                push_option(&mut proxy, "obfs", node.obfs.as_deref());
+                push_option(&mut proxy, "obfs-host", Some(host));
+                if node.snell_version != 0 {
+                    proxy.push_str(&format!(", version={}", node.snell_version));
+                }
+            }
+            ProxyType::Hysteria2 => {
+                proxy = format!("hysteria2, {}, {}, password={}", hostname, port, password);
+
+                push_option(&mut proxy, "ports", node.ports.as_deref());
+                push_option(&mut proxy, "obfs", node.obfs.as_deref());
+                push_option(&mut proxy, "obfs-password", node.obfs_param.as_deref());
+                if node.up_speed > 0 {
+                    proxy.push_str(&format!(", upload-bandwidth={}", node.up_speed));
+                }
+                if node.down_speed > 0 {
+                    proxy.push_str(&format!(", download-bandwidth={}", node.down_speed));
+                }
+                push_option(&mut proxy, "sni", node.sni.as_deref());
+                push_bool_option(&mut proxy, "skip-cert-verify", scv);
+            }
+            ProxyType::Tuic => {
+                let tuic = match &node.combined_proxy {
+                    Some(CombinedProxy::Tuic(tuic)) => tuic,
+                    _ => continue,
+                };
+
+                proxy = format!(
+                    "tuic, {}, {}, uuid={}, password={}, version=5",
+                    hostname, port, tuic.uuid, tuic.password
+                );
+
+                if let Some(alpn) = &tuic.alpn {
+                    let mut alpn: Vec<String> = alpn.iter().cloned().collect();
+                    alpn.sort();
+                    push_option(&mut proxy, "alpn", Some(&join(&alpn, ",")));
+                }
+                push_option(
+                    &mut proxy,
+                    "sni",
+                    tuic.sni.as_deref().or(node.sni.as_deref()),
+                );
+                push_option(
+                    &mut proxy,
+                    "congestion-controller",
+                    tuic.congestion_control.as_deref(),
+                );
+                push_option(&mut proxy, "udp-relay-mode", tuic.udp_relay_mode.as_deref());
+                push_bool_option(
+                    &mut proxy,
+                    "skip-cert-verify",
+                    tuic.skip_cert_verify.define(scv),
+                );
+            }
+            _ => continue,
+        }
+
+        // Add common options
+        push_bool_option(&mut proxy, "tfo", tfo);
+        push_bool_option(&mut proxy, "udp-relay", udp);
+
+        // Add to nodelist or INI
+        if ext.nodelist {
+            output_nodelist.push_str(&format!("{} = {}\n", remark, proxy));
+        } else {
+            ini.set("{NONAME}", &format!("{} = {}", remark, proxy), "")
+                .unwrap_or(());
+            nodelist.push(node.clone());
+        }
+
+        remarks_list.push(remark);
+    }
+
+    if ext.nodelist {
+        return output_nodelist;
+    }
+
+    // Process proxy groups
+    ini.set_current_section("Proxy Group");
+    ini.erase_section();
+
+    for group in extra_proxy_group {
+        let mut filtered_nodelist = Vec::new();
+
+        match group.group_type {
+            ProxyGroupType::Select
+            | ProxyGroupType::URLTest
+            | ProxyGroupType::Fallback
+            | ProxyGroupType::LoadBalance => {}
+            _ => continue,
+        }
+
+        // Generate filtered proxy list
+        for proxy_name in &group.proxies {
+            group_generate(proxy_name, &nodelist, &mut filtered_nodelist, true, ext);
+        }
+
+        if filtered_nodelist.is_empty() {
+            filtered_nodelist.push("DIRECT".to_string());
+        }
+
+        if filtered_nodelist.len() == 1 {
+            let proxy_name = to_lower(&filtered_nodelist[0]);
+            if proxy_name == "direct" || proxy_name == "reject" {
+                ini.set(
+                    "Proxy",
+                    "{NONAME}",
+                    &format!("{} = {}", group.name, proxy_name),
+                )
+                .unwrap_or(());
+                continue;
+            }
+        }
+
+        // Build group string
+        let mut group_str = format!("{},", group.type_str());
+        group_str.push_str(&join(&filtered_nodelist, ","));
+
+        if group.group_type != ProxyGroupType::Select {
+            group_str.push_str(&format!(",url={},interval={}", group.url, group.interval));
+
+            if group.tolerance > 0 {
+                group_str.push_str(&format!(",tolerance={}", group.tolerance));
+            }
+
+            if group.timeout > 0 {
+                group_str.push_str(&format!(",timeout={}", group.timeout));
+            }
+        }
+
+        ini.set("{NONAME}", &format!("{} = {}", group.name, group_str), "")
+            .unwrap_or(());
+    }
+
+    // Generate rules if enabled, Shadowrocket reads Surge 4 style rules
+    if ext.enable_rule_generator {
+        ruleset_to_surge(
+            &mut ini,
+            ruleset_content_array,
+            4,
+            ext.overwrite_original_rules,
+            &ext.managed_config_prefix,
+            ext.surge_domain_set,
+        )
+        .await;
+    }
+
+    ini.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::test_utils::{
+        block_on, inline_rulesets, nodes, rule_generator_settings, select_group,
+    };
+    use crate::models::RulesetType;
+
+    const NODES: &str = r#"
+proxies:
+  - { name: ss, type: ss, server: ss.example.com, port: 8388, cipher: aes-256-gcm, password: pw }
+  - { name: vless, type: vless, server: vless.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, servername: sni.example.com, flow: xtls-rprx-vision, client-fingerprint: chrome, reality-opts: { public-key: pbk, short-id: sid } }
+  - { name: hy2, type: hysteria2, server: hy2.example.com, port: 443, password: pw, obfs: salamander, obfs-password: secret, down: 200Mbps, sni: hy2.example.com }
+  - { name: tuic, type: tuic, server: tuic.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000003, password: pw, alpn: [h3], congestion-controller: bbr }
+"#;
+
+    fn generate(rulesets: &mut Vec<RulesetContent>) -> String {
+        generate_with_domain_set(rulesets, false)
+    }
+
+    fn generate_with_domain_set(rulesets: &mut Vec<RulesetContent>, domain_set: bool) -> String {
+        let mut ext = rule_generator_settings();
+        ext.surge_domain_set = domain_set;
+        block_on(proxy_to_shadowrocket(
+            &mut nodes(NODES),
+            "[General]\nbypass-system = true\n",
+            rulesets,
+            &vec![select_group()],
+            &mut ext,
+        ))
+    }
+
+    #[test]
+    fn test_shadowrocket_proxies() {
+        let output = generate(&mut Vec::new());
+
+        assert!(output.contains("[General]\nbypass-system = true"));
+        assert!(output
+            .contains("ss = ss, ss.example.com, 8388, encrypt-method=aes-256-gcm, password=pw"));
+        assert!(output.contains(
+            "vless = vless, vless.example.com, 443, username=00000000-0000-0000-0000-000000000002, tls=true, flow=xtls-rprx-vision, sni=sni.example.com, public-key=pbk, short-id=sid, client-fingerprint=chrome"
+        ));
+        assert!(output.contains(
+            "hy2 = hysteria2, hy2.example.com, 443, password=pw, obfs=salamander, obfs-password=secret, download-bandwidth=200, sni=hy2.example.com"
+        ));
+        assert!(output.contains(
+            "tuic = tuic, tuic.example.com, 443, uuid=00000000-0000-0000-0000-000000000003, password=pw, version=5, alpn=h3, congestion-controller=bbr"
+        ));
+        assert!(output.contains("[Proxy Group]\nProxy = select,ss,vless,hy2,tuic"));
+    }
+
+    #[test]
+    fn test_shadowrocket_rules() {
+        let mut rulesets = inline_rulesets(&["DOMAIN-SUFFIX,google.com", "MATCH"], "Proxy");
+
+        let output = generate(&mut rulesets);
+        assert!(output.contains("[Rule]\nDOMAIN-SUFFIX,google.com,Proxy\nFINAL,Proxy"));
+    }
+
+    #[test]
+    fn test_shadowrocket_domain_set() {
+        let mut ruleset = RulesetContent::new("https://example.com/reject.txt", "REJECT");
+        ruleset.rule_type = RulesetType::DomainSet;
+        ruleset.update_interval = 86400;
+        ruleset.set_rule_content(".ads.example.com\ntracker.example.org\n");
+        let mut rulesets = vec![ruleset];
+
+        // Without an endpoint to convert it the domain set is expanded
+        let output = generate(&mut rulesets);
+        assert!(output.contains(
+            "[Rule]\nDOMAIN-SUFFIX,ads.example.com,REJECT\nDOMAIN,tracker.example.org,REJECT"
+        ));
+
+        let output = generate_with_domain_set(&mut rulesets, true);
+        assert!(output.contains(
+            "[Rule]\nDOMAIN-SET,https://example.com/reject.txt,REJECT,update-interval=86400"
+        ));
+        assert!(!output.contains("DOMAIN-SUFFIX,ads.example.com"));
+    }
+}
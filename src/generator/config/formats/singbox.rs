@@ -1,13 +1,19 @@
 use crate::generator::config::group::group_generate;
 use crate::generator::config::remark::process_remark;
-use crate::generator::ruleconvert::ruleset_to_sing_box::ruleset_to_sing_box;
+use crate::generator::ruleconvert::ruleset_to_sing_box::{
+    migrate_to_rule_actions, ruleset_to_sing_box,
+};
+use crate::models::proxy_node::anytls::AnyTlsProxy;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::vless::VlessProxy;
 use crate::models::{
     ExtraSettings, Proxy, ProxyGroupConfigs, ProxyGroupType, ProxyType, RulesetContent,
 };
 use crate::utils::base64::base64_encode;
 use crate::Settings;
-use log::error;
+use log::{error, warn};
 use serde_json::{json, Map, Value as JsonValue};
+use std::collections::{HashMap, HashSet};
 
 /// Format SingBox interval from seconds
 ///
@@ -103,6 +109,82 @@ fn build_singbox_transport(proxy: &Proxy) -> JsonValue {
     JsonValue::Object(transport)
 }
 
+/// Build SingBox transport configuration for a VLESS node
+///
+/// # Arguments
+/// * `vless` - The VLESS specific settings
+///
+/// # Returns
+/// * Transport configuration as JSON, or `None` if the transport is not
+///   supported by SingBox
+fn build_singbox_vless_transport(vless: &VlessProxy) -> Option<JsonValue> {
+    let mut transport = Map::new();
+
+    match vless.network.as_deref().unwrap_or("tcp") {
+        "tcp" => {}
+        "ws" | "httpupgrade" => {
+            let network = vless.network.as_deref().unwrap_or("ws");
+            transport.insert("type".to_string(), JsonValue::String(network.to_string()));
+            transport.insert(
+                "path".to_string(),
+                JsonValue::String(vless.ws_path.clone().unwrap_or_else(|| "/".to_string())),
+            );
+
+            let mut headers = Map::new();
+            for (name, value) in vless.ws_headers.iter().flatten() {
+                // HTTPUpgrade carries the host in a dedicated field
+                if network == "httpupgrade" && name.eq_ignore_ascii_case("host") {
+                    transport.insert("host".to_string(), JsonValue::String(value.clone()));
+                } else {
+                    headers.insert(name.clone(), JsonValue::String(value.clone()));
+                }
+            }
+            if !headers.is_empty() {
+                transport.insert("headers".to_string(), JsonValue::Object(headers));
+            }
+        }
+        "http" => {
+            transport.insert("type".to_string(), JsonValue::String("http".to_string()));
+            if let Some(hosts) = vless
+                .http_headers
+                .as_ref()
+                .and_then(|headers| headers.get("Host"))
+            {
+                transport.insert("host".to_string(), json!(hosts));
+            }
+            if let Some(path) = &vless.http_path {
+                transport.insert("path".to_string(), JsonValue::String(path.clone()));
+            }
+            if let Some(method) = &vless.http_method {
+                transport.insert("method".to_string(), JsonValue::String(method.clone()));
+            }
+        }
+        "h2" => {
+            transport.insert("type".to_string(), JsonValue::String("http".to_string()));
+            if let Some(hosts) = &vless.h2_host {
+                if !hosts.is_empty() {
+                    transport.insert("host".to_string(), json!(hosts));
+                }
+            }
+            if let Some(path) = &vless.h2_path {
+                transport.insert("path".to_string(), JsonValue::String(path.clone()));
+            }
+        }
+        "grpc" => {
+            transport.insert("type".to_string(), JsonValue::String("grpc".to_string()));
+            if let Some(service_name) = &vless.grpc_service_name {
+                transport.insert(
+                    "service_name".to_string(),
+                    JsonValue::String(service_name.clone()),
+                );
+            }
+        }
+        _ => return None,
+    }
+
+    Some(JsonValue::Object(transport))
+}
+
 /// Add common members to a SingBox proxy configuration
 ///
 /// # Arguments
@@ -132,6 +214,123 @@ fn add_singbox_common_members(
     );
 }
 
+/// Build SingBox TLS configuration
+///
+/// Every TLS-capable outbound goes through this builder so that SNI, ALPN
+/// and certificate verification are emitted the same way.
+///
+/// # Arguments
+/// * `server_name` - SNI of the server, omitted when empty
+/// * `alpn` - ALPN protocols, emitted in a stable order
+/// * `insecure` - Whether to skip certificate verification
+///
+/// # Returns
+/// * TLS configuration object
+fn build_singbox_tls(
+    server_name: Option<&str>,
+    alpn: Option<&HashSet<String>>,
+    insecure: Option<bool>,
+) -> Map<String, JsonValue> {
+    let mut tls = Map::new();
+    tls.insert("enabled".to_string(), JsonValue::Bool(true));
+
+    if let Some(server_name) = server_name.filter(|sni| !sni.is_empty()) {
+        tls.insert(
+            "server_name".to_string(),
+            JsonValue::String(server_name.to_string()),
+        );
+    }
+
+    if let Some(insecure) = insecure {
+        tls.insert("insecure".to_string(), JsonValue::Bool(insecure));
+    }
+
+    if let Some(alpn) = alpn.filter(|alpn| !alpn.is_empty()) {
+        let mut alpn: Vec<&String> = alpn.iter().collect();
+        alpn.sort();
+        tls.insert("alpn".to_string(), json!(alpn));
+    }
+
+    tls
+}
+
+/// Add a uTLS fingerprint to a SingBox TLS configuration
+fn add_singbox_utls(tls: &mut Map<String, JsonValue>, fingerprint: Option<&str>) {
+    if let Some(fingerprint) = fingerprint.filter(|fp| !fp.is_empty()) {
+        tls.insert(
+            "utls".to_string(),
+            json!({ "enabled": true, "fingerprint": fingerprint }),
+        );
+    }
+}
+
+/// Add the server certificate of a Hysteria node to a SingBox TLS configuration
+fn add_singbox_certificate(tls: &mut Map<String, JsonValue>, proxy: &Proxy) {
+    let certificate = [&proxy.ca_str, &proxy.ca]
+        .into_iter()
+        .flatten()
+        .find(|ca| !ca.is_empty());
+    if let Some(certificate) = certificate {
+        tls.insert(
+            "certificate".to_string(),
+            JsonValue::String(certificate.clone()),
+        );
+    }
+}
+
+/// Build the TLS configuration of a VLESS outbound, including REALITY
+fn build_singbox_vless_tls(
+    proxy: &Proxy,
+    vless: &VlessProxy,
+    scv: Option<bool>,
+) -> Map<String, JsonValue> {
+    let mut tls = build_singbox_tls(
+        vless.servername.as_deref().or(proxy.sni.as_deref()),
+        Some(&vless.alpn),
+        vless.skip_cert_verify.or(scv),
+    );
+
+    let fingerprint = vless
+        .client_fingerprint
+        .as_deref()
+        .or(vless.fingerprint.as_deref());
+
+    if let Some(public_key) = &vless.reality_public_key {
+        let mut reality = Map::new();
+        reality.insert("enabled".to_string(), JsonValue::Bool(true));
+        reality.insert(
+            "public_key".to_string(),
+            JsonValue::String(public_key.clone()),
+        );
+        if let Some(short_id) = &vless.reality_short_id {
+            reality.insert("short_id".to_string(), JsonValue::String(short_id.clone()));
+        }
+        tls.insert("reality".to_string(), JsonValue::Object(reality));
+
+        // REALITY requires uTLS
+        add_singbox_utls(&mut tls, Some(fingerprint.unwrap_or("chrome")));
+    } else {
+        add_singbox_utls(&mut tls, fingerprint);
+    }
+
+    tls
+}
+
+/// Build the TLS configuration of an AnyTLS outbound
+fn build_singbox_anytls_tls(
+    proxy: &Proxy,
+    anytls: &AnyTlsProxy,
+    scv: Option<bool>,
+) -> Map<String, JsonValue> {
+    let mut tls = build_singbox_tls(
+        anytls.sni.as_deref().or(proxy.sni.as_deref()),
+        anytls.alpn.as_ref(),
+        anytls.skip_cert_verify.or(scv),
+    );
+    add_singbox_utls(&mut tls, anytls.client_fingerprint.as_deref());
+    tls
+}
+
 /// Convert string array to JSON array
 ///
 /// # Arguments
@@ -149,6 +348,206 @@ fn string_array_to_json_array(array: &str, delimiter: &str) -> JsonValue {
     JsonValue::Array(values)
 }
 
+/// The newest sing-box configuration schema known to the generator
+const SINGBOX_LATEST_SCHEMA: (u32, u32) = (1, 12);
+
+/// The schema generated when no version is targeted, understood by every
+/// sing-box core still in use
+const SINGBOX_LEGACY_SCHEMA: (u32, u32) = (1, 10);
+
+/// Parse the targeted sing-box version into `(major, minor)`
+///
+/// `latest` selects the newest known schema, empty or unparsable versions
+/// keep the legacy schema.
+fn singbox_schema_version(version: &str) -> (u32, u32) {
+    let version = version.trim();
+    if version.eq_ignore_ascii_case("latest") {
+        return SINGBOX_LATEST_SCHEMA;
+    }
+
+    let mut parts = version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse::<u32>().ok());
+
+    match (parts.next().flatten(), parts.next().flatten()) {
+        (Some(major), Some(minor)) => (major, minor),
+        (Some(major), None) => (major, 0),
+        _ => SINGBOX_LEGACY_SCHEMA,
+    }
+}
+
+/// Map a legacy `rcode://` name to the response code of a `predefined` DNS action
+fn singbox_dns_rcode(name: &str) -> &'static str {
+    match name {
+        "success" => "NOERROR",
+        "format_error" => "FORMERR",
+        "server_failure" => "SERVFAIL",
+        "name_error" => "NXDOMAIN",
+        "not_implemented" => "NOTIMP",
+        _ => "REFUSED",
+    }
+}
+
+/// Split a DNS server authority into host and optional port
+fn split_dns_host_port(authority: &str) -> (&str, Option<u16>) {
+    if let Some(rest) = authority.strip_prefix('[') {
+        return match rest.split_once(']') {
+            Some((host, port)) => (host, port.strip_prefix(':').and_then(|p| p.parse().ok())),
+            None => (rest, None),
+        };
+    }
+
+    match authority.split_once(':') {
+        Some((host, port)) if !port.contains(':') => (host, port.parse().ok()),
+        _ => (authority, None),
+    }
+}
+
+/// Fill the typed server fields of sing-box 1.12 from a legacy DNS `address`
+fn set_singbox_dns_server_type(server: &mut Map<String, JsonValue>, address: &str) {
+    let (scheme, rest) = address.split_once("://").unwrap_or(("udp", address));
+
+    match (scheme, rest) {
+        (_, "local") | (_, "fakeip") => {
+            server.insert("type".to_string(), JsonValue::String(rest.to_string()));
+        }
+        ("dhcp", interface) => {
+            server.insert("type".to_string(), JsonValue::String("dhcp".to_string()));
+            if !interface.is_empty() && interface != "auto" {
+                server.insert(
+                    "interface".to_string(),
+                    JsonValue::String(interface.to_string()),
+                );
+            }
+        }
+        _ => {
+            let (authority, path) = match rest.find('/') {
+                Some(pos) => rest.split_at(pos),
+                None => (rest, ""),
+            };
+            let (host, port) = split_dns_host_port(authority);
+
+            server.insert("type".to_string(), JsonValue::String(scheme.to_string()));
+            server.insert("server".to_string(), JsonValue::String(host.to_string()));
+            if let Some(port) = port {
+                server.insert("server_port".to_string(), JsonValue::Number(port.into()));
+            }
+            if !path.is_empty() && path != "/dns-query" {
+                server.insert("path".to_string(), JsonValue::String(path.to_string()));
+            }
+        }
+    }
+}
+
+/// Rewrite a legacy `dns` section into the typed server format of sing-box 1.12+
+///
+/// `rcode://` servers become `predefined` rule actions, `fakeip` settings move
+/// onto the fakeip server and an `outbound: any` resolver rule becomes the
+/// route's `default_domain_resolver`. Servers already carrying a `type` are left
+/// untouched.
+fn migrate_singbox_dns(config: &mut JsonValue) {
+    let mut default_resolver = None;
+
+    if let Some(dns) = config.get_mut("dns").and_then(JsonValue::as_object_mut) {
+        let fakeip = dns.remove("fakeip");
+        dns.remove("independent_cache");
+
+        let mut rcode_servers = HashMap::new();
+        if let Some(servers) = dns.get_mut("servers").and_then(JsonValue::as_array_mut) {
+            servers.retain_mut(|server| {
+                let Some(server) = server.as_object_mut() else {
+                    return true;
+                };
+                let Some(address) = server.remove("address") else {
+                    return true;
+                };
+                let address = address.as_str().unwrap_or_default();
+
+                if let Some(rcode) = address.strip_prefix("rcode://") {
+                    if let Some(tag) = server.get("tag").and_then(JsonValue::as_str) {
+                        rcode_servers.insert(tag.to_string(), singbox_dns_rcode(rcode));
+                    }
+                    return false;
+                }
+
+                set_singbox_dns_server_type(server, address);
+
+                if address == "fakeip" {
+                    if let Some(fakeip) = &fakeip {
+                        for key in ["inet4_range", "inet6_range"] {
+                            if let Some(range) = fakeip.get(key) {
+                                server.insert(key.to_string(), range.clone());
+                            }
+                        }
+                    }
+                }
+
+                if let Some(resolver) = server.remove("address_resolver") {
+                    let resolver = match server.remove("address_strategy") {
+                        Some(strategy) => json!({ "server": resolver, "strategy": strategy }),
+                        None => resolver,
+                    };
+                    server.insert("domain_resolver".to_string(), resolver);
+                }
+
+                // An empty direct detour is the default and deprecated when explicit
+                if server.get("detour").and_then(JsonValue::as_str) == Some("DIRECT") {
+                    server.remove("detour");
+                }
+
+                true
+            });
+        }
+
+        if let Some(rules) = dns.get_mut("rules").and_then(JsonValue::as_array_mut) {
+            rules.retain_mut(|rule| {
+                let Some(rule) = rule.as_object_mut() else {
+                    return true;
+                };
+                let Some(server) = rule.get("server").and_then(JsonValue::as_str) else {
+                    return true;
+                };
+
+                if let Some(rcode) = rcode_servers.get(server) {
+                    for key in ["server", "disable_cache", "rewrite_ttl", "client_subnet"] {
+                        rule.remove(key);
+                    }
+                    rule.insert(
+                        "action".to_string(),
+                        JsonValue::String("predefined".to_string()),
+                    );
+                    rule.insert("rcode".to_string(), JsonValue::String(rcode.to_string()));
+                    return true;
+                }
+
+                let any_outbound = match rule.get("outbound") {
+                    Some(JsonValue::String(outbound)) => outbound == "any",
+                    Some(JsonValue::Array(outbounds)) => outbounds == &[json!("any")],
+                    _ => false,
+                };
+                if any_outbound && rule.len() == 2 {
+                    default_resolver = Some(server.to_string());
+                    return false;
+                }
+
+                true
+            });
+        }
+    }
+
+    if let Some(resolver) = default_resolver {
+        if config.get("route").is_none() {
+            config["route"] = json!({});
+        }
+        if let Some(route) = config["route"].as_object_mut() {
+            route
+                .entry("default_domain_resolver")
+                .or_insert(JsonValue::String(resolver));
+        }
+    }
+}
+
 /// Convert proxies to SingBox format
 ///
 /// # Arguments
@@ -183,6 +582,18 @@ pub fn proxy_to_singbox(
         }
     };
 
+    // Pick the configuration schema of the targeted sing-box version
+    let schema = singbox_schema_version(&ext.singbox_version);
+    let rule_actions = schema >= (1, 11);
+    if !ext.nodelist && schema >= (1, 12) {
+        migrate_singbox_dns(&mut json);
+    }
+    // Base rules must not keep routing to the removed dns-out and block
+    // outbounds, whether or not rules are generated
+    if !ext.nodelist && rule_actions {
+        migrate_to_rule_actions(&mut json);
+    }
+
     // Convert nodes to outbounds
     let mut outbounds = Vec::new();
     let mut nodelist = Vec::new();
@@ -196,17 +607,18 @@ pub fn proxy_to_singbox(
             "tag": "DIRECT"
         }));
 
-        // Reject outbound
-        outbounds.push(json!({
-            "type": "block",
-            "tag": "REJECT"
-        }));
-
-        // DNS outbound
-        outbounds.push(json!({
-            "type": "dns",
-            "tag": "dns-out"
-        }));
+        // Reject and DNS outbounds are replaced by rule actions since 1.11
+        if !rule_actions {
+            outbounds.push(json!({
+                "type": "block",
+                "tag": "REJECT"
+            }));
+
+            outbounds.push(json!({
+                "type": "dns",
+                "tag": "dns-out"
+            }));
+        }
     }
 
     // Process each proxy node
@@ -487,30 +899,8 @@ pub fn proxy_to_singbox(
                 }
 
                 // Add TLS settings
-                let mut tls = Map::new();
-                tls.insert("enabled".to_string(), JsonValue::Bool(true));
-
-                if let Some(allow_insecure) = scv {
-                    tls.insert("insecure".to_string(), JsonValue::Bool(allow_insecure));
-                }
-
-                if !node.alpn.is_empty() && node.alpn.len() > 0 {
-                    let alpn = vec![JsonValue::String(node.alpn.iter().next().unwrap().clone())];
-                    tls.insert("alpn".to_string(), JsonValue::Array(alpn));
-                }
-
-                if let Some(ca) = &node.ca {
-                    if !ca.is_empty() {
-                        tls.insert("certificate".to_string(), JsonValue::String(ca.to_string()));
-                    }
-                }
-
-                if let Some(ca_str) = &node.ca_str {
-                    if !ca_str.is_empty() {
-                        tls.insert("certificate".to_string(), JsonValue::String(ca_str.clone()));
-                    }
-                }
-
+                let mut tls = build_singbox_tls(node.sni.as_deref(), Some(&node.alpn), scv);
+                add_singbox_certificate(&mut tls, node);
                 obj.insert("tls".to_string(), JsonValue::Object(tls));
                 obj
             }
@@ -558,30 +948,8 @@ pub fn proxy_to_singbox(
                 }
 
                 // Add TLS settings
-                let mut tls = Map::new();
-                tls.insert("enabled".to_string(), JsonValue::Bool(true));
-
-                if let Some(allow_insecure) = scv {
-                    tls.insert("insecure".to_string(), JsonValue::Bool(allow_insecure));
-                }
-
-                if !node.alpn.is_empty() && node.alpn.len() > 0 {
-                    let alpn = vec![JsonValue::String(node.alpn.iter().next().unwrap().clone())];
-                    tls.insert("alpn".to_string(), JsonValue::Array(alpn));
-                }
-
-                if let Some(ca) = &node.ca {
-                    if !ca.is_empty() {
-                        tls.insert("certificate".to_string(), JsonValue::String(ca.to_string()));
-                    }
-                }
-
-                if let Some(ca_str) = &node.ca_str {
-                    if !ca_str.is_empty() {
-                        tls.insert("certificate".to_string(), JsonValue::String(ca_str.clone()));
-                    }
-                }
-
+                let mut tls = build_singbox_tls(node.sni.as_deref(), Some(&node.alpn), scv);
+                add_singbox_certificate(&mut tls, node);
                 obj.insert("tls".to_string(), JsonValue::Object(tls));
                 obj
             }
@@ -617,33 +985,164 @@ pub fn proxy_to_singbox(
 
                 obj
             }
-            _ => continue, // Skip unsupported types
-        };
+            ProxyType::Vless => {
+                let vless = match &node.combined_proxy {
+                    Some(CombinedProxy::Vless(vless)) => vless,
+                    _ => continue,
+                };
+                let transport = match build_singbox_vless_transport(vless) {
+                    Some(transport) => transport,
+                    None => {
+                        warn!(
+                            "Skipping VLESS node '{}': transport '{}' is not supported by SingBox",
+                            node.remark,
+                            vless.network.as_deref().unwrap_or_default()
+                        );
+                        continue;
+                    }
+                };
+
+                let mut obj = Map::new();
+                add_singbox_common_members(&mut obj, node, "vless");
 
-        // Add TLS settings for protocols that need it
-        if node.tls_secure {
-            let mut tls = Map::new();
-            tls.insert("enabled".to_string(), JsonValue::Bool(true));
-
-            // Set server_name from ServerName or Host
-            if let Some(server_name) = &node.server_name {
-                if !server_name.is_empty() {
-                    tls.insert(
-                        "server_name".to_string(),
-                        JsonValue::String(server_name.clone()),
+                // Add VLESS specific fields
+                obj.insert("uuid".to_string(), JsonValue::String(vless.uuid.clone()));
+
+                if let Some(flow) = &vless.flow {
+                    if !flow.is_empty() {
+                        obj.insert("flow".to_string(), JsonValue::String(flow.clone()));
+                    }
+                }
+
+                if let Some(packet_encoding) = &vless.packet_encoding {
+                    obj.insert(
+                        "packet_encoding".to_string(),
+                        JsonValue::String(packet_encoding.clone()),
                     );
                 }
-            } else if let Some(host) = &node.host {
-                if !host.is_empty() {
-                    tls.insert("server_name".to_string(), JsonValue::String(host.clone()));
+
+                // Add transport settings if any
+                if !transport.as_object().unwrap().is_empty() {
+                    obj.insert("transport".to_string(), transport);
                 }
+
+                // Add TLS settings
+                if vless.tls || vless.reality_public_key.is_some() {
+                    let tls = build_singbox_vless_tls(node, vless, scv);
+                    obj.insert("tls".to_string(), JsonValue::Object(tls));
+                }
+
+                obj
             }
+            ProxyType::AnyTls => {
+                let anytls = match &node.combined_proxy {
+                    Some(CombinedProxy::AnyTls(anytls)) => anytls,
+                    _ => continue,
+                };
+
+                let mut obj = Map::new();
+                add_singbox_common_members(&mut obj, node, "anytls");
+
+                // Add AnyTLS specific fields
+                obj.insert(
+                    "password".to_string(),
+                    JsonValue::String(anytls.password.clone()),
+                );
+
+                if let Some(interval) = anytls.idle_session_check_interval {
+                    obj.insert(
+                        "idle_session_check_interval".to_string(),
+                        JsonValue::String(format!("{}s", interval)),
+                    );
+                }
 
-            // Add insecure option
-            if let Some(allow_insecure) = scv {
-                tls.insert("insecure".to_string(), JsonValue::Bool(allow_insecure));
+                if let Some(timeout) = anytls.idle_session_timeout {
+                    obj.insert(
+                        "idle_session_timeout".to_string(),
+                        JsonValue::String(format!("{}s", timeout)),
+                    );
+                }
+
+                if let Some(min_idle_session) = anytls.min_idle_session {
+                    obj.insert(
+                        "min_idle_session".to_string(),
+                        JsonValue::Number(min_idle_session.into()),
+                    );
+                }
+
+                // AnyTLS always runs over TLS
+                let tls = build_singbox_anytls_tls(node, anytls, scv);
+                obj.insert("tls".to_string(), JsonValue::Object(tls));
+
+                obj
             }
+            ProxyType::Tuic => {
+                let tuic = match &node.combined_proxy {
+                    Some(CombinedProxy::Tuic(tuic)) => tuic,
+                    _ => continue,
+                };
+
+                let mut obj = Map::new();
+                add_singbox_common_members(&mut obj, node, "tuic");
 
+                // Add TUIC specific fields
+                obj.insert("uuid".to_string(), JsonValue::String(tuic.uuid.clone()));
+                obj.insert(
+                    "password".to_string(),
+                    JsonValue::String(tuic.password.clone()),
+                );
+
+                if let Some(cc) = &tuic.congestion_control {
+                    obj.insert(
+                        "congestion_control".to_string(),
+                        JsonValue::String(cc.clone()),
+                    );
+                }
+
+                if let Some(mode) = &tuic.udp_relay_mode {
+                    obj.insert(
+                        "udp_relay_mode".to_string(),
+                        JsonValue::String(mode.clone()),
+                    );
+                }
+
+                if tuic.reduce_rtt.unwrap_or(false) {
+                    obj.insert("zero_rtt_handshake".to_string(), JsonValue::Bool(true));
+                }
+
+                if let Some(heartbeat) = tuic.heartbeat_interval {
+                    obj.insert(
+                        "heartbeat".to_string(),
+                        JsonValue::String(format!("{}ms", heartbeat)),
+                    );
+                }
+
+                // Add TLS settings
+                let mut tls = build_singbox_tls(
+                    tuic.sni.as_deref().or(node.sni.as_deref()),
+                    tuic.alpn.as_ref(),
+                    tuic.skip_cert_verify.or(scv),
+                );
+
+                if tuic.disable_sni.unwrap_or(false) {
+                    tls.insert("disable_sni".to_string(), JsonValue::Bool(true));
+                }
+
+                obj.insert("tls".to_string(), JsonValue::Object(tls));
+                obj
+            }
+            _ => continue, // Skip unsupported types
+        };
+
+        // Add TLS settings for protocols that carry it on the node itself
+        let has_tls = node.tls_secure || node.proxy_type == ProxyType::HTTPS;
+        if has_tls && !proxy_obj.contains_key("tls") {
+            // Set server_name from ServerName, SNI or Host
+            let server_name = [&node.server_name, &node.sni, &node.host]
+                .into_iter()
+                .flatten()
+                .find(|name| !name.is_empty());
+            let tls = build_singbox_tls(server_name.map(|s| s.as_str()), Some(&node.alpn), scv);
             proxy_obj.insert("tls".to_string(), JsonValue::Object(tls));
         }
 
@@ -691,6 +1190,11 @@ pub fn proxy_to_singbox(
             group_generate(proxy_name, &nodelist, &mut filtered_nodelist, true, ext);
         }
 
+        // There is no REJECT outbound to select when rule actions are used
+        if rule_actions {
+            filtered_nodelist.retain(|name| name != "REJECT");
+        }
+
         // Add DIRECT if empty
         if filtered_nodelist.is_empty() {
             filtered_nodelist.push("DIRECT".to_string());
@@ -768,6 +1272,8 @@ pub fn proxy_to_singbox(
         &mut json,
         ruleset_content_array,
         ext.overwrite_original_rules,
+        rule_actions,
+        &ext.managed_config_prefix,
     );
 
     serde_json::to_string_pretty(&json).unwrap_or_default()
@@ -783,3 +1289,259 @@ pub fn generate_singbox(
 ) -> String {
     String::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::test_utils::{
+        inline_rulesets, nodes, rule_generator_settings, select_group,
+    };
+
+    const NODES: &str = r#"
+proxies:
+  - { name: reality, type: vless, server: reality.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000001, tls: true, flow: xtls-rprx-vision, servername: www.microsoft.com, client-fingerprint: safari, reality-opts: { public-key: pbk, short-id: sid } }
+  - { name: vless-ws, type: vless, server: ws.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, servername: ws.example.com, alpn: [http/1.1, h2], skip-cert-verify: true, network: ws, ws-opts: { path: /ws, headers: { Host: cdn.example.com } } }
+  - { name: anytls, type: anytls, server: anytls.example.com, port: 443, password: pw, sni: sni.example.com, alpn: [h2], client-fingerprint: chrome, idle-session-check-interval: 30, min-idle-session: 2 }
+  - { name: hy2, type: hysteria2, server: hy2.example.com, port: 443, password: pw, sni: hy2.example.com, alpn: [h3, h2] }
+  - { name: trojan, type: trojan, server: trojan.example.com, port: 443, password: pw, sni: trojan.example.com }
+"#;
+
+    fn generate() -> JsonValue {
+        let mut ext = ExtraSettings::default();
+        ext.nodelist = true;
+        let output = proxy_to_singbox(
+            &mut nodes(NODES),
+            "",
+            &mut Vec::new(),
+            &Vec::new(),
+            &mut ext,
+        );
+        serde_json::from_str(&output).unwrap()
+    }
+
+    const BASE: &str = r#"{
+        "dns": {
+            "servers": [
+                { "tag": "dns_proxy", "address": "tls://1.1.1.1", "address_resolver": "dns_resolver" },
+                { "tag": "dns_direct", "address": "h3://dns.alidns.com/dns-query", "detour": "DIRECT" },
+                { "tag": "dns_fakeip", "address": "fakeip" },
+                { "tag": "dns_resolver", "address": "223.5.5.5:53", "detour": "DIRECT" },
+                { "tag": "block", "address": "rcode://success" }
+            ],
+            "rules": [
+                { "outbound": ["any"], "server": "dns_resolver" },
+                { "rule_set": ["geosite-category-ads-all"], "server": "block", "disable_cache": true }
+            ],
+            "final": "dns_direct",
+            "independent_cache": true,
+            "fakeip": { "enabled": true, "inet4_range": "198.18.0.0/15" }
+        },
+        "inbounds": [{ "type": "tun", "tag": "tun-in", "stack": "mixed", "sniff": true }],
+        "route": { "rules": [] }
+    }"#;
+
+    fn generate_config(version: &str) -> JsonValue {
+        let mut select = select_group();
+        select.proxies.push("[]REJECT".to_string());
+
+        let mut rulesets = [
+            inline_rulesets(&["DOMAIN-SUFFIX,ads.example.com"], "REJECT"),
+            inline_rulesets(&["DOMAIN,example.com", "FINAL"], "Proxy"),
+        ]
+        .concat();
+
+        let mut ext = rule_generator_settings();
+        ext.overwrite_original_rules = false;
+        ext.singbox_version = version.to_string();
+        let output = proxy_to_singbox(
+            &mut nodes(NODES),
+            BASE,
+            &mut rulesets,
+            &vec![select],
+            &mut ext,
+        );
+        serde_json::from_str(&output).unwrap()
+    }
+
+    fn find_outbound<'a>(config: &'a JsonValue, tag: &str) -> &'a JsonValue {
+        config["outbounds"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|o| o["tag"] == tag)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_singbox_vless_reality() {
+        let config = generate();
+        let reality = find_outbound(&config, "reality");
+        assert_eq!(reality["type"], "vless");
+        assert_eq!(reality["flow"], "xtls-rprx-vision");
+        assert_eq!(
+            reality["tls"],
+            json!({
+                "enabled": true,
+                "server_name": "www.microsoft.com",
+                "reality": { "enabled": true, "public_key": "pbk", "short_id": "sid" },
+                "utls": { "enabled": true, "fingerprint": "safari" },
+            })
+        );
+
+        let ws = find_outbound(&config, "vless-ws");
+        assert_eq!(ws["transport"]["type"], "ws");
+        assert_eq!(ws["tls"]["alpn"], json!(["h2", "http/1.1"]));
+        assert_eq!(ws["tls"]["insecure"], true);
+    }
+
+    #[test]
+    fn test_singbox_anytls() {
+        let config = generate();
+        let anytls = find_outbound(&config, "anytls");
+        assert_eq!(anytls["type"], "anytls");
+        assert_eq!(anytls["password"], "pw");
+        assert_eq!(anytls["idle_session_check_interval"], "30s");
+        assert_eq!(anytls["min_idle_session"], 2);
+        assert_eq!(
+            anytls["tls"],
+            json!({
+                "enabled": true,
+                "server_name": "sni.example.com",
+                "alpn": ["h2"],
+                "utls": { "enabled": true, "fingerprint": "chrome" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_singbox_tls_fields() {
+        let config = generate();
+
+        let hy2 = &find_outbound(&config, "hy2")["tls"];
+        assert_eq!(hy2["server_name"], "hy2.example.com");
+        assert_eq!(hy2["alpn"], json!(["h2", "h3"]));
+
+        let trojan = &find_outbound(&config, "trojan")["tls"];
+        assert_eq!(trojan["enabled"], true);
+        assert_eq!(trojan["server_name"], "trojan.example.com");
+    }
+
+    #[test]
+    fn test_singbox_schema_version() {
+        assert_eq!(singbox_schema_version(""), SINGBOX_LEGACY_SCHEMA);
+        assert_eq!(singbox_schema_version("bogus"), SINGBOX_LEGACY_SCHEMA);
+        assert_eq!(singbox_schema_version("1.10.7"), (1, 10));
+        assert_eq!(singbox_schema_version("v1.11"), (1, 11));
+        assert_eq!(singbox_schema_version("latest"), SINGBOX_LATEST_SCHEMA);
+    }
+
+    #[test]
+    fn test_singbox_legacy_schema() {
+        // No version keeps the legacy schema older cores understand
+        assert_eq!(generate_config(""), generate_config("1.10"));
+
+        let config = generate_config("1.10");
+        assert_eq!(find_outbound(&config, "REJECT")["type"], "block");
+        assert_eq!(find_outbound(&config, "dns-out")["type"], "dns");
+        assert_eq!(config["inbounds"][0]["sniff"], true);
+        assert_eq!(config["dns"]["servers"][0]["address"], "tls://1.1.1.1");
+
+        let rules = config["route"]["rules"].as_array().unwrap();
+        assert_eq!(
+            rules[0],
+            json!({ "protocol": "dns", "outbound": "dns-out" })
+        );
+        assert!(
+            rules.contains(&json!({ "domain_suffix": "ads.example.com", "outbound": "REJECT" }))
+        );
+        assert_eq!(config["route"]["final"], "Proxy");
+    }
+
+    #[test]
+    fn test_singbox_rule_actions() {
+        let config = generate_config("1.11");
+        let outbounds = config["outbounds"].as_array().unwrap();
+        assert!(!outbounds
+            .iter()
+            .any(|o| o["type"] == "block" || o["type"] == "dns"));
+        assert!(!find_outbound(&config, "Proxy")["outbounds"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("REJECT")));
+        assert_eq!(config["inbounds"][0].get("sniff"), None);
+        // DNS servers keep the legacy format until 1.12
+        assert_eq!(config["dns"]["servers"][0]["address"], "tls://1.1.1.1");
+
+        let rules = config["route"]["rules"].as_array().unwrap();
+        assert_eq!(rules[0], json!({ "action": "sniff" }));
+        assert_eq!(
+            rules[1],
+            json!({ "protocol": "dns", "action": "hijack-dns" })
+        );
+        assert!(rules.contains(&json!({ "domain_suffix": "ads.example.com", "action": "reject" })));
+        assert!(rules.contains(&json!({ "domain": "example.com", "outbound": "Proxy" })));
+    }
+
+    #[test]
+    fn test_singbox_rule_actions_without_rule_generator() {
+        let base = r#"{
+            "inbounds": [{ "type": "tun", "tag": "tun-in", "sniff": true }],
+            "route": {
+                "rules": [
+                    { "protocol": "dns", "outbound": "dns-out" },
+                    { "domain_suffix": "ads.example.com", "outbound": "REJECT" },
+                    { "domain": "example.com", "outbound": "DIRECT" }
+                ]
+            }
+        }"#;
+        let mut ext = ExtraSettings::default();
+        ext.enable_rule_generator = false;
+        ext.singbox_version = "1.11".to_string();
+        let output = proxy_to_singbox(
+            &mut nodes(NODES),
+            base,
+            &mut Vec::new(),
+            &Vec::new(),
+            &mut ext,
+        );
+        let config: JsonValue = serde_json::from_str(&output).unwrap();
+
+        assert!(!config["outbounds"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|o| o["tag"] == "REJECT" || o["tag"] == "dns-out"));
+        assert_eq!(config["inbounds"][0].get("sniff"), None);
+        assert_eq!(
+            config["route"]["rules"],
+            json!([
+                { "action": "sniff" },
+                { "protocol": "dns", "action": "hijack-dns" },
+                { "domain_suffix": "ads.example.com", "action": "reject" },
+                { "domain": "example.com", "outbound": "DIRECT" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_singbox_dns_servers() {
+        let config = generate_config("latest");
+        let dns = &config["dns"];
+        assert_eq!(
+            dns["servers"],
+            json!([
+                { "tag": "dns_proxy", "type": "tls", "server": "1.1.1.1", "domain_resolver": "dns_resolver" },
+                { "tag": "dns_direct", "type": "h3", "server": "dns.alidns.com" },
+                { "tag": "dns_fakeip", "type": "fakeip", "inet4_range": "198.18.0.0/15" },
+                { "tag": "dns_resolver", "type": "udp", "server": "223.5.5.5", "server_port": 53 },
+            ])
+        );
+        assert_eq!(
+            dns["rules"],
+            json!([{ "rule_set": ["geosite-category-ads-all"], "action": "predefined", "rcode": "NOERROR" }])
+        );
+        assert_eq!(dns.get("fakeip"), None);
+        assert_eq!(dns.get("independent_cache"), None);
+        assert_eq!(config["route"]["default_domain_resolver"], "dns_resolver");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::vless::VlessProxy;
 use crate::models::{ExtraSettings, Proxy, ProxyType, SSR_CIPHERS, SS_CIPHERS};
 use crate::utils::base64::{base64_encode, url_safe_base64_encode};
 use crate::utils::url::url_encode;
@@ -12,7 +14,13 @@ bitflags! {
         const SSR = 0b0010;
         const VMESS = 0b0100;
         const TROJAN = 0b1000;
-        const MIXED = Self::SS.bits() | Self::SSR.bits() | Self::VMESS.bits() | Self::TROJAN.bits();
+        const TUIC = 0b10000;
+        const VLESS = 0b100000;
+        const HYSTERIA = 0b1000000;
+        const HYSTERIA2 = 0b10000000;
+        const ANYTLS = 0b100000000;
+        const MIXED = Self::SS.bits() | Self::SSR.bits() | Self::VMESS.bits() | Self::TROJAN.bits() | Self::TUIC.bits()
+            | Self::VLESS.bits() | Self::HYSTERIA.bits() | Self::HYSTERIA2.bits() | Self::ANYTLS.bits();
     }
 }
 
@@ -70,13 +78,193 @@ fn vmess_link_construct(
     }
 }
 
+/// Format a hostname for the authority part of a link, bracketing IPv6 addresses
+fn link_host(hostname: &str) -> String {
+    if hostname.contains(':') && !hostname.starts_with('[') {
+        format!("[{}]", hostname)
+    } else {
+        hostname.to_string()
+    }
+}
+
+/// Append a URL-encoded query parameter, skipping empty values
+fn push_param(params: &mut Vec<String>, key: &str, value: &str) {
+    if !value.is_empty() {
+        params.push(format!("{}={}", key, url_encode(value)));
+    }
+}
+
+/// Join a set of ALPN values into a stable, comma separated list
+fn join_alpn<'a>(alpn: impl IntoIterator<Item = &'a String>) -> String {
+    let mut alpn: Vec<&str> = alpn.into_iter().map(|s| s.as_str()).collect();
+    alpn.sort();
+    alpn.join(",")
+}
+
+/// Finish a link with its query string and remark
+fn finish_link(link: &mut String, params: &[String], remark: &str) {
+    if !params.is_empty() {
+        link.push('?');
+        link.push_str(&params.join("&"));
+    }
+    link.push_str(&format!("#{}", url_encode(remark)));
+}
+
+/// Generate a VLESS link using the v2rayN query parameters
+///
+/// # Arguments
+/// * `node` - The proxy node
+/// * `vless` - The VLESS specific settings
+///
+/// # Returns
+/// * VLESS link
+fn vless_link_construct(node: &Proxy, vless: &VlessProxy) -> String {
+    let mut params = vec!["encryption=none".to_string()];
+
+    let security = if vless.reality_public_key.is_some() {
+        "reality"
+    } else if vless.tls {
+        "tls"
+    } else {
+        "none"
+    };
+    params.push(format!("security={}", security));
+    if security != "none" {
+        push_param(
+            &mut params,
+            "sni",
+            vless
+                .servername
+                .as_deref()
+                .or(node.sni.as_deref())
+                .unwrap_or_default(),
+        );
+        push_param(
+            &mut params,
+            "fp",
+            vless
+                .client_fingerprint
+                .as_deref()
+                .or(vless.fingerprint.as_deref())
+                .unwrap_or_default(),
+        );
+        push_param(&mut params, "alpn", &join_alpn(&vless.alpn));
+        if vless
+            .skip_cert_verify
+            .or(node.allow_insecure)
+            .unwrap_or(false)
+        {
+            params.push("allowInsecure=1".to_string());
+        }
+    }
+    if security == "reality" {
+        push_param(
+            &mut params,
+            "pbk",
+            vless.reality_public_key.as_deref().unwrap_or_default(),
+        );
+        push_param(
+            &mut params,
+            "sid",
+            vless.reality_short_id.as_deref().unwrap_or_default(),
+        );
+    }
+    push_param(
+        &mut params,
+        "flow",
+        vless.flow.as_deref().unwrap_or_default(),
+    );
+
+    let network = vless.network.as_deref().unwrap_or("tcp");
+    match network {
+        "ws" | "httpupgrade" => {
+            params.push(format!("type={}", network));
+            if let Some(host) = vless
+                .ws_headers
+                .iter()
+                .flatten()
+                .find_map(|(name, value)| name.eq_ignore_ascii_case("host").then_some(value))
+            {
+                push_param(&mut params, "host", host);
+            }
+            push_param(&mut params, "path", vless.ws_path.as_deref().unwrap_or("/"));
+        }
+        "http" | "h2" => {
+            params.push("type=h2".to_string());
+            if let Some(hosts) = &vless.h2_host {
+                push_param(&mut params, "host", &hosts.join(","));
+            }
+            push_param(&mut params, "path", vless.h2_path.as_deref().unwrap_or("/"));
+        }
+        "grpc" => {
+            params.push("type=grpc".to_string());
+            push_param(
+                &mut params,
+                "serviceName",
+                vless.grpc_service_name.as_deref().unwrap_or_default(),
+            );
+        }
+        "xhttp" => {
+            params.push("type=xhttp".to_string());
+            push_param(
+                &mut params,
+                "host",
+                vless.xhttp_host.as_deref().unwrap_or_default(),
+            );
+            push_param(
+                &mut params,
+                "path",
+                vless.xhttp_path.as_deref().unwrap_or("/"),
+            );
+            push_param(
+                &mut params,
+                "mode",
+                vless.xhttp_mode.as_deref().unwrap_or_default(),
+            );
+            push_param(
+                &mut params,
+                "extra",
+                vless.xhttp_extra.as_deref().unwrap_or_default(),
+            );
+        }
+        _ => {
+            params.push("type=tcp".to_string());
+            match &vless.http_path {
+                Some(path) => {
+                    params.push("headerType=http".to_string());
+                    if let Some(host) = vless
+                        .http_headers
+                        .as_ref()
+                        .and_then(|headers| headers.get("Host"))
+                        .and_then(|hosts| hosts.first())
+                    {
+                        push_param(&mut params, "host", host);
+                    }
+                    push_param(&mut params, "path", path);
+                }
+                None => params.push("headerType=none".to_string()),
+            }
+        }
+    }
+
+    let mut link = format!(
+        "vless://{}@{}:{}",
+        url_encode(&vless.uuid),
+        link_host(&node.hostname),
+        node.port
+    );
+    finish_link(&mut link, &params, &node.remark);
+    link
+}
+
 /// Convert proxies to single links
 ///
 /// This function converts a list of proxies to single URL format links.
 ///
 /// # Arguments
 /// * `nodes` - List of proxy nodes to convert
-/// * `types` - Bitflags indicating which proxy types to include (SS, SSR, VMess, Trojan)
+/// * `types` - Bitflags indicating which proxy types to include (SS, SSR, VMess, Trojan, TUIC,
+///   VLESS, Hysteria, Hysteria2, AnyTLS)
 /// * `ext` - Extra settings for conversion
 ///
 /// # Returns
@@ -242,6 +430,183 @@ pub fn proxy_to_single(
 
                 _proxy_str.push_str(&format!("#{}", url_encode(remark)));
             }
+            ProxyType::Tuic => {
+                if !types.contains(ProxyUriTypes::TUIC) {
+                    continue;
+                }
+
+                let tuic = match node.combined_proxy {
+                    Some(CombinedProxy::Tuic(ref tuic)) => tuic,
+                    _ => continue,
+                };
+
+                // TUIC v5 format
+                let mut params = Vec::new();
+                if let Some(cc) = &tuic.congestion_control {
+                    params.push(format!("congestion_control={}", url_encode(cc)));
+                }
+                if let Some(mode) = &tuic.udp_relay_mode {
+                    params.push(format!("udp_relay_mode={}", url_encode(mode)));
+                }
+                if let Some(alpn) = &tuic.alpn {
+                    if !alpn.is_empty() {
+                        let mut alpn: Vec<&str> = alpn.iter().map(|s| s.as_str()).collect();
+                        alpn.sort();
+                        params.push(format!("alpn={}", url_encode(&alpn.join(","))));
+                    }
+                }
+                if let Some(sni) = tuic.sni.as_ref().or(node.sni.as_ref()) {
+                    params.push(format!("sni={}", url_encode(sni)));
+                }
+                if tuic
+                    .skip_cert_verify
+                    .or(node.allow_insecure)
+                    .unwrap_or(false)
+                {
+                    params.push("allow_insecure=1".to_string());
+                }
+                if tuic.disable_sni.unwrap_or(false) {
+                    params.push("disable_sni=1".to_string());
+                }
+                if tuic.reduce_rtt.unwrap_or(false) {
+                    params.push("reduce_rtt=1".to_string());
+                }
+
+                _proxy_str = format!(
+                    "tuic://{}:{}@{}:{}",
+                    url_encode(&tuic.uuid),
+                    url_encode(&tuic.password),
+                    hostname,
+                    port
+                );
+                if !params.is_empty() {
+                    _proxy_str.push('?');
+                    _proxy_str.push_str(&params.join("&"));
+                }
+                _proxy_str.push_str(&format!("#{}", url_encode(remark)));
+            }
+            ProxyType::Vless => {
+                if !types.contains(ProxyUriTypes::VLESS) {
+                    continue;
+                }
+
+                let vless = match node.combined_proxy {
+                    Some(CombinedProxy::Vless(ref vless)) => vless,
+                    _ => continue,
+                };
+
+                _proxy_str = vless_link_construct(node, vless);
+            }
+            ProxyType::Hysteria => {
+                if !types.contains(ProxyUriTypes::HYSTERIA) {
+                    continue;
+                }
+
+                // Hysteria v1 format
+                let mut params = Vec::new();
+                push_param(&mut params, "protocol", protocol);
+                push_param(
+                    &mut params,
+                    "auth",
+                    node.auth_str
+                        .as_deref()
+                        .or(node.auth.as_deref())
+                        .unwrap_or_default(),
+                );
+                push_param(&mut params, "peer", node.sni.as_deref().unwrap_or_default());
+                if node.allow_insecure.unwrap_or(false) {
+                    params.push("insecure=1".to_string());
+                }
+                if node.up_speed > 0 {
+                    params.push(format!("upmbps={}", node.up_speed));
+                }
+                if node.down_speed > 0 {
+                    params.push(format!("downmbps={}", node.down_speed));
+                }
+                push_param(&mut params, "alpn", &join_alpn(&node.alpn));
+                push_param(&mut params, "obfs", obfs);
+                push_param(&mut params, "obfsParam", obfs_param);
+
+                _proxy_str = format!("hysteria://{}:{}", link_host(hostname), port);
+                finish_link(&mut _proxy_str, &params, remark);
+            }
+            ProxyType::Hysteria2 => {
+                if !types.contains(ProxyUriTypes::HYSTERIA2) {
+                    continue;
+                }
+
+                // Hysteria2 format
+                let mut params = Vec::new();
+                push_param(&mut params, "sni", node.sni.as_deref().unwrap_or_default());
+                if node.allow_insecure.unwrap_or(false) {
+                    params.push("insecure=1".to_string());
+                }
+                push_param(&mut params, "obfs", obfs);
+                push_param(&mut params, "obfs-password", obfs_param);
+                push_param(
+                    &mut params,
+                    "mport",
+                    node.ports.as_deref().unwrap_or_default(),
+                );
+                push_param(&mut params, "alpn", &join_alpn(&node.alpn));
+                push_param(
+                    &mut params,
+                    "pinSHA256",
+                    node.fingerprint.as_deref().unwrap_or_default(),
+                );
+
+                _proxy_str = format!(
+                    "hysteria2://{}@{}:{}",
+                    url_encode(password),
+                    link_host(hostname),
+                    port
+                );
+                finish_link(&mut _proxy_str, &params, remark);
+            }
+            ProxyType::AnyTls => {
+                if !types.contains(ProxyUriTypes::ANYTLS) {
+                    continue;
+                }
+
+                let anytls = match node.combined_proxy {
+                    Some(CombinedProxy::AnyTls(ref anytls)) => anytls,
+                    _ => continue,
+                };
+
+                let mut params = Vec::new();
+                push_param(
+                    &mut params,
+                    "sni",
+                    anytls
+                        .sni
+                        .as_deref()
+                        .or(node.sni.as_deref())
+                        .unwrap_or_default(),
+                );
+                if anytls
+                    .skip_cert_verify
+                    .or(node.allow_insecure)
+                    .unwrap_or(false)
+                {
+                    params.push("insecure=1".to_string());
+                }
+                if let Some(alpn) = &anytls.alpn {
+                    push_param(&mut params, "alpn", &join_alpn(alpn));
+                }
+                push_param(
+                    &mut params,
+                    "fp",
+                    anytls.client_fingerprint.as_deref().unwrap_or_default(),
+                );
+
+                _proxy_str = format!(
+                    "anytls://{}@{}:{}",
+                    url_encode(&anytls.password),
+                    link_host(hostname),
+                    port
+                );
+                finish_link(&mut _proxy_str, &params, remark);
+            }
             _ => continue,
         }
 
@@ -1,6 +1,7 @@
 use crate::generator::config::group::group_generate;
 use crate::generator::config::remark::process_remark;
 use crate::generator::ruleconvert::ruleset_to_surge::ruleset_to_surge;
+use crate::models::proxy_node::combined::CombinedProxy;
 use crate::models::{
     ExtraSettings, Proxy, ProxyGroupConfigs, ProxyGroupType, ProxyType, RulesetContent,
 };
@@ -527,6 +528,45 @@ pub async fn proxy_to_surge(
                     }
                 }
             }
+            ProxyType::Tuic => {
+                if surge_ver < 5 {
+                    continue;
+                }
+
+                let tuic = match &node.combined_proxy {
+                    Some(CombinedProxy::Tuic(tuic)) => tuic,
+                    _ => continue,
+                };
+
+                _proxy = format!(
+                    "tuic-v5, {}, {}, password={}, uuid={}",
+                    hostname, port, tuic.password, tuic.uuid
+                );
+
+                if let Some(alpn) = &tuic.alpn {
+                    if let Some(first) = alpn.iter().min() {
+                        _proxy.push_str(&format!(", alpn={}", first));
+                    }
+                }
+
+                if let Some(sni) = tuic.sni.as_ref().or(node.sni.as_ref()) {
+                    if !sni.is_empty() {
+                        _proxy.push_str(&format!(", sni={}", sni));
+                    }
+                }
+
+                let scv = tuic.skip_cert_verify.define(scv);
+                if scv.is_some() {
+                    _proxy.push_str(&format!(
+                        ", skip-cert-verify={}",
+                        if scv.unwrap_or(false) {
+                            "true"
+                        } else {
+                            "false"
+                        }
+                    ));
+                }
+            }
             _ => continue,
         }
 
@@ -669,6 +709,7 @@ pub async fn proxy_to_surge(
             surge_ver,
             ext.overwrite_original_rules,
             &ext.managed_config_prefix,
+            ext.surge_domain_set,
         )
         .await;
     }
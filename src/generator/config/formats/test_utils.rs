@@ -0,0 +1,59 @@
+//! Shared scaffolding for the tests of the configuration targets
+//!
+//! Every target test converts a small Clash fixture with a single `Proxy`
+//! select group holding all nodes and the rule generator enabled.
+
+use crate::models::{
+    ExtraSettings, Proxy, ProxyGroupConfig, ProxyGroupType, RulesetContent, RulesetType,
+};
+use crate::parser::yaml::clash::parse_clash_yaml;
+use std::future::Future;
+
+/// Parses the `proxies` of a Clash fixture
+pub fn nodes(clash_yaml: &str) -> Vec<Proxy> {
+    parse_clash_yaml(clash_yaml).unwrap()
+}
+
+/// A `Proxy` select group holding every node
+pub fn select_group() -> ProxyGroupConfig {
+    let mut group = ProxyGroupConfig::new("Proxy".to_string(), ProxyGroupType::Select);
+    group.proxies = vec![".*".to_string()];
+    group
+}
+
+/// Default settings with the rule generator enabled
+pub fn rule_generator_settings() -> ExtraSettings {
+    ExtraSettings {
+        enable_rule_generator: true,
+        ..Default::default()
+    }
+}
+
+/// Runs an async generator to completion on a current-thread runtime
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+/// A ruleset of the given type routed to `Proxy`
+pub fn ruleset(rule_path: &str, rule_type: RulesetType, content: &str) -> RulesetContent {
+    let mut ruleset = RulesetContent::new(rule_path, "Proxy");
+    ruleset.rule_type = rule_type;
+    ruleset.set_rule_content(content);
+    ruleset
+}
+
+/// One inline `[]` ruleset per rule, routed to `group`
+pub fn inline_rulesets(rules: &[&str], group: &str) -> Vec<RulesetContent> {
+    rules
+        .iter()
+        .map(|rule| {
+            let mut ruleset = RulesetContent::new("", group);
+            ruleset.set_rule_content(&format!("[]{}", rule));
+            ruleset
+        })
+        .collect()
+}
@@ -0,0 +1,936 @@
+use crate::generator::config::group::group_generate;
+use crate::generator::config::remark::process_remark;
+use crate::generator::ruleconvert::ruleset_to_xray::{ruleset_to_xray, XrayRouteTarget};
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::vless::VlessProxy;
+use crate::models::{
+    BalanceStrategy, ExtraSettings, Proxy, ProxyGroupConfigs, ProxyGroupType, ProxyType,
+    RulesetContent,
+};
+use log::{error, warn};
+use serde_json::{json, Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Transport and security options of a node, in Xray terms
+#[derive(Debug, Default)]
+struct XrayStream<'a> {
+    network: &'a str,
+    host: Option<&'a str>,
+    path: Option<&'a str>,
+    /// HTTP header obfuscation on a TCP transport
+    http_header: bool,
+    service_name: Option<&'a str>,
+    xhttp_mode: Option<&'a str>,
+    xhttp_extra: Option<&'a str>,
+    tls: bool,
+    server_name: Option<&'a str>,
+    allow_insecure: Option<bool>,
+    alpn: Vec<String>,
+    fingerprint: Option<&'a str>,
+    reality_public_key: Option<&'a str>,
+    reality_short_id: Option<&'a str>,
+}
+
+/// Returns the value if it is present and not empty
+fn non_empty(value: Option<&String>) -> Option<&str> {
+    value.map(|v| v.as_str()).filter(|v| !v.is_empty())
+}
+
+/// Collects the transport options of a VMess or Trojan node
+///
+/// # Returns
+/// * The stream options, or `None` if the transport is not supported by Xray
+fn stream_from_node(node: &Proxy, scv: Option<bool>) -> Option<XrayStream<'_>> {
+    let mut stream = XrayStream {
+        tls: node.tls_secure,
+        server_name: non_empty(node.server_name.as_ref())
+            .or(non_empty(node.sni.as_ref()))
+            .or(non_empty(node.host.as_ref())),
+        allow_insecure: scv,
+        alpn: node.alpn.iter().cloned().collect(),
+        fingerprint: non_empty(node.fingerprint.as_ref()),
+        ..Default::default()
+    };
+    stream.alpn.sort();
+
+    let host = non_empty(node.host.as_ref());
+    let path = non_empty(node.path.as_ref());
+    match node.transfer_protocol.as_deref().unwrap_or("tcp") {
+        "" | "tcp" => {
+            stream.network = "tcp";
+            if node.fake_type.as_deref() == Some("http") {
+                stream.http_header = true;
+                stream.host = host;
+                stream.path = path;
+            }
+        }
+        "http" => {
+            stream.network = "tcp";
+            stream.http_header = true;
+            stream.host = host;
+            stream.path = path;
+        }
+        network @ ("ws" | "httpupgrade" | "h2") => {
+            stream.network = network;
+            stream.host = host;
+            stream.path = path;
+        }
+        "grpc" => {
+            stream.network = "grpc";
+            stream.service_name = path;
+        }
+        _ => return None,
+    }
+    Some(stream)
+}
+
+/// Collects the transport options of a VLESS node
+///
+/// # Returns
+/// * The stream options, or `None` if the transport is not supported by Xray
+fn stream_from_vless<'a>(
+    node: &'a Proxy,
+    vless: &'a VlessProxy,
+    scv: Option<bool>,
+) -> Option<XrayStream<'a>> {
+    let mut stream = XrayStream {
+        tls: vless.tls,
+        server_name: non_empty(vless.servername.as_ref()).or(non_empty(node.sni.as_ref())),
+        allow_insecure: vless.skip_cert_verify.or(scv),
+        alpn: vless.alpn.iter().cloned().collect(),
+        fingerprint: non_empty(vless.client_fingerprint.as_ref())
+            .or(non_empty(vless.fingerprint.as_ref())),
+        reality_public_key: non_empty(vless.reality_public_key.as_ref()),
+        reality_short_id: vless.reality_short_id.as_deref(),
+        ..Default::default()
+    };
+    stream.alpn.sort();
+
+    match vless.network.as_deref().unwrap_or("tcp") {
+        "" | "tcp" => {
+            stream.network = "tcp";
+            if let Some(path) = &vless.http_path {
+                stream.http_header = true;
+                stream.path = Some(path);
+                stream.host = vless
+                    .http_headers
+                    .as_ref()
+                    .and_then(|headers| headers.get("Host"))
+                    .and_then(|hosts| hosts.first())
+                    .map(|host| host.as_str());
+            }
+        }
+        network @ ("ws" | "httpupgrade") => {
+            stream.network = network;
+            stream.path = vless.ws_path.as_deref();
+            stream.host = vless.ws_headers.iter().flatten().find_map(|(name, value)| {
+                name.eq_ignore_ascii_case("host").then_some(value.as_str())
+            });
+        }
+        "h2" => {
+            stream.network = "h2";
+            stream.path = vless.h2_path.as_deref();
+            stream.host = vless
+                .h2_host
+                .as_ref()
+                .and_then(|hosts| hosts.first())
+                .map(|host| host.as_str());
+        }
+        "grpc" => {
+            stream.network = "grpc";
+            stream.service_name = vless.grpc_service_name.as_deref();
+        }
+        "xhttp" => {
+            stream.network = "xhttp";
+            stream.path = vless.xhttp_path.as_deref();
+            stream.host = vless.xhttp_host.as_deref();
+            stream.xhttp_mode = vless.xhttp_mode.as_deref();
+            stream.xhttp_extra = vless.xhttp_extra.as_deref();
+        }
+        _ => return None,
+    }
+    Some(stream)
+}
+
+/// Build the Xray `streamSettings` object
+///
+/// # Arguments
+/// * `stream` - Transport and security options of the node
+/// * `tfo` - Whether TCP Fast Open is enabled
+///
+/// # Returns
+/// * Stream settings as JSON
+fn build_xray_stream_settings(stream: &XrayStream, tfo: Option<bool>) -> JsonValue {
+    let mut settings = Map::new();
+    settings.insert(
+        "network".to_string(),
+        JsonValue::String(stream.network.to_string()),
+    );
+
+    match stream.network {
+        "tcp" if stream.http_header => {
+            let mut request = Map::new();
+            request.insert("path".to_string(), json!([stream.path.unwrap_or("/")]));
+            if let Some(host) = stream.host {
+                request.insert("headers".to_string(), json!({ "Host": [host] }));
+            }
+            settings.insert(
+                "tcpSettings".to_string(),
+                json!({ "header": { "type": "http", "request": request } }),
+            );
+        }
+        "ws" | "httpupgrade" => {
+            let mut transport = Map::new();
+            transport.insert(
+                "path".to_string(),
+                JsonValue::String(stream.path.unwrap_or("/").to_string()),
+            );
+            if let Some(host) = stream.host {
+                transport.insert("host".to_string(), JsonValue::String(host.to_string()));
+            }
+            let key = if stream.network == "ws" {
+                "wsSettings"
+            } else {
+                "httpupgradeSettings"
+            };
+            settings.insert(key.to_string(), JsonValue::Object(transport));
+        }
+        "h2" => {
+            let mut transport = Map::new();
+            if let Some(host) = stream.host {
+                transport.insert("host".to_string(), json!([host]));
+            }
+            transport.insert(
+                "path".to_string(),
+                JsonValue::String(stream.path.unwrap_or("/").to_string()),
+            );
+            settings.insert("httpSettings".to_string(), JsonValue::Object(transport));
+        }
+        "grpc" => {
+            settings.insert(
+                "grpcSettings".to_string(),
+                json!({ "serviceName": stream.service_name.unwrap_or_default() }),
+            );
+        }
+        "xhttp" => {
+            let mut transport = Map::new();
+            transport.insert(
+                "path".to_string(),
+                JsonValue::String(stream.path.unwrap_or("/").to_string()),
+            );
+            if let Some(host) = stream.host {
+                transport.insert("host".to_string(), JsonValue::String(host.to_string()));
+            }
+            if let Some(mode) = stream.xhttp_mode {
+                transport.insert("mode".to_string(), JsonValue::String(mode.to_string()));
+            }
+            // The extra options are kept as a raw JSON object
+            if let Some(extra) = stream
+                .xhttp_extra
+                .and_then(|extra| serde_json::from_str::<JsonValue>(extra).ok())
+                .filter(|extra| extra.is_object())
+            {
+                transport.insert("extra".to_string(), extra);
+            }
+            settings.insert("xhttpSettings".to_string(), JsonValue::Object(transport));
+        }
+        _ => {}
+    }
+
+    if let Some(public_key) = stream.reality_public_key {
+        let mut reality = Map::new();
+        if let Some(server_name) = stream.server_name {
+            reality.insert(
+                "serverName".to_string(),
+                JsonValue::String(server_name.to_string()),
+            );
+        }
+        reality.insert(
+            "fingerprint".to_string(),
+            JsonValue::String(stream.fingerprint.unwrap_or("chrome").to_string()),
+        );
+        reality.insert(
+            "publicKey".to_string(),
+            JsonValue::String(public_key.to_string()),
+        );
+        reality.insert(
+            "shortId".to_string(),
+            JsonValue::String(stream.reality_short_id.unwrap_or_default().to_string()),
+        );
+        settings.insert(
+            "security".to_string(),
+            JsonValue::String("reality".to_string()),
+        );
+        settings.insert("realitySettings".to_string(), JsonValue::Object(reality));
+    } else if stream.tls {
+        let mut tls = Map::new();
+        if let Some(server_name) = stream.server_name {
+            tls.insert(
+                "serverName".to_string(),
+                JsonValue::String(server_name.to_string()),
+            );
+        }
+        if let Some(allow_insecure) = stream.allow_insecure {
+            tls.insert("allowInsecure".to_string(), JsonValue::Bool(allow_insecure));
+        }
+        if !stream.alpn.is_empty() {
+            tls.insert("alpn".to_string(), json!(stream.alpn));
+        }
+        if let Some(fingerprint) = stream.fingerprint {
+            tls.insert(
+                "fingerprint".to_string(),
+                JsonValue::String(fingerprint.to_string()),
+            );
+        }
+        settings.insert("security".to_string(), JsonValue::String("tls".to_string()));
+        settings.insert("tlsSettings".to_string(), JsonValue::Object(tls));
+    }
+
+    if tfo == Some(true) {
+        settings.insert("sockopt".to_string(), json!({ "tcpFastOpen": true }));
+    }
+
+    JsonValue::Object(settings)
+}
+
+/// Convert a single node to an Xray outbound
+///
+/// # Returns
+/// * The outbound as JSON, or `None` if the node is not supported by Xray
+fn node_to_xray_outbound(node: &Proxy, scv: Option<bool>, tfo: Option<bool>) -> Option<JsonValue> {
+    let (protocol, settings, stream) = match node.proxy_type {
+        ProxyType::Shadowsocks => {
+            if non_empty(node.plugin.as_ref()).is_some() {
+                return None;
+            }
+            let server = json!({
+                "address": node.hostname,
+                "port": node.port,
+                "method": node.encrypt_method.clone().unwrap_or_default(),
+                "password": node.password.clone().unwrap_or_default(),
+            });
+            ("shadowsocks", json!({ "servers": [server] }), None)
+        }
+        ProxyType::VMess => {
+            let user = json!({
+                "id": node.user_id.clone().unwrap_or_default(),
+                "alterId": node.alter_id,
+                "security": non_empty(node.encrypt_method.as_ref()).unwrap_or("auto"),
+            });
+            let server = json!({ "address": node.hostname, "port": node.port, "users": [user] });
+            (
+                "vmess",
+                json!({ "vnext": [server] }),
+                Some(stream_from_node(node, scv)?),
+            )
+        }
+        ProxyType::Vless => {
+            let vless = match &node.combined_proxy {
+                Some(CombinedProxy::Vless(vless)) => vless,
+                _ => return None,
+            };
+            let mut user = Map::new();
+            user.insert("id".to_string(), JsonValue::String(vless.uuid.clone()));
+            user.insert(
+                "encryption".to_string(),
+                JsonValue::String("none".to_string()),
+            );
+            if let Some(flow) = non_empty(vless.flow.as_ref()) {
+                user.insert("flow".to_string(), JsonValue::String(flow.to_string()));
+            }
+            let server = json!({ "address": node.hostname, "port": node.port, "users": [user] });
+            (
+                "vless",
+                json!({ "vnext": [server] }),
+                Some(stream_from_vless(node, vless, scv)?),
+            )
+        }
+        ProxyType::Trojan => {
+            let server = json!({
+                "address": node.hostname,
+                "port": node.port,
+                "password": node.password.clone().unwrap_or_default(),
+            });
+            (
+                "trojan",
+                json!({ "servers": [server] }),
+                Some(stream_from_node(node, scv)?),
+            )
+        }
+        ProxyType::Socks5 | ProxyType::HTTP | ProxyType::HTTPS => {
+            let mut server = Map::new();
+            server.insert(
+                "address".to_string(),
+                JsonValue::String(node.hostname.clone()),
+            );
+            server.insert("port".to_string(), JsonValue::Number(node.port.into()));
+            if let Some(username) = non_empty(node.username.as_ref()) {
+                server.insert(
+                    "users".to_string(),
+                    json!([{
+                        "user": username,
+                        "pass": node.password.clone().unwrap_or_default(),
+                    }]),
+                );
+            }
+            let protocol = if node.proxy_type == ProxyType::Socks5 {
+                "socks"
+            } else {
+                "http"
+            };
+            let stream = (node.proxy_type == ProxyType::HTTPS).then(|| XrayStream {
+                network: "tcp",
+                tls: true,
+                server_name: non_empty(node.server_name.as_ref()),
+                allow_insecure: scv,
+                ..Default::default()
+            });
+            (protocol, json!({ "servers": [server] }), stream)
+        }
+        ProxyType::WireGuard => {
+            let mut address = Vec::new();
+            if let Some(self_ip) = non_empty(node.self_ip.as_ref()) {
+                address.push(self_ip.to_string());
+            }
+            if let Some(self_ipv6) = non_empty(node.self_ipv6.as_ref()) {
+                address.push(self_ipv6.to_string());
+            }
+
+            let mut peer = Map::new();
+            peer.insert(
+                "publicKey".to_string(),
+                JsonValue::String(node.public_key.clone().unwrap_or_default()),
+            );
+            if let Some(pre_shared_key) = non_empty(node.pre_shared_key.as_ref()) {
+                peer.insert(
+                    "preSharedKey".to_string(),
+                    JsonValue::String(pre_shared_key.to_string()),
+                );
+            }
+            let endpoint = if node.hostname.contains(':') {
+                format!("[{}]:{}", node.hostname, node.port)
+            } else {
+                format!("{}:{}", node.hostname, node.port)
+            };
+            peer.insert("endpoint".to_string(), JsonValue::String(endpoint));
+
+            let mut settings = Map::new();
+            settings.insert(
+                "secretKey".to_string(),
+                JsonValue::String(node.private_key.clone().unwrap_or_default()),
+            );
+            settings.insert("address".to_string(), json!(address));
+            settings.insert("peers".to_string(), json!([peer]));
+            if node.mtu > 0 {
+                settings.insert("mtu".to_string(), JsonValue::Number(node.mtu.into()));
+            }
+            ("wireguard", JsonValue::Object(settings), None)
+        }
+        _ => return None,
+    };
+
+    let mut outbound = Map::new();
+    outbound.insert("tag".to_string(), JsonValue::String(node.remark.clone()));
+    outbound.insert(
+        "protocol".to_string(),
+        JsonValue::String(protocol.to_string()),
+    );
+    outbound.insert("settings".to_string(), settings);
+    match stream {
+        Some(stream) => {
+            outbound.insert(
+                "streamSettings".to_string(),
+                build_xray_stream_settings(&stream, tfo),
+            );
+        }
+        None if tfo == Some(true) => {
+            outbound.insert(
+                "streamSettings".to_string(),
+                json!({ "sockopt": { "tcpFastOpen": true } }),
+            );
+        }
+        None => {}
+    }
+
+    Some(JsonValue::Object(outbound))
+}
+
+/// Returns outbound tags for the given remarks where no tag is a prefix of
+/// another one
+///
+/// Xray matches balancer and observatory selectors as tag prefixes, so a
+/// selector for `HK 1` would also pick up `HK 10`. Remarks that are a prefix
+/// of another remark get a numbered suffix until they no longer collide.
+fn prefix_unique_tags(remarks: &[String]) -> Vec<String> {
+    let mut tags = remarks.to_vec();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in 0..tags.len() {
+            let mut cnt = 1;
+            while tags
+                .iter()
+                .enumerate()
+                .any(|(j, tag)| j != i && tag.starts_with(tags[i].as_str()))
+            {
+                tags[i] = format!("{} ({})", remarks[i], cnt);
+                cnt += 1;
+                changed = true;
+            }
+        }
+    }
+    tags
+}
+
+/// Convert proxies to Xray client configuration
+///
+/// Every supported node becomes an outbound tagged with its remark. Xray has
+/// no selector outbounds, so proxy groups are mapped onto routing targets:
+/// select groups route to their first member, url-test and fallback groups
+/// become `leastPing` balancers probed by the observatory, and load-balance
+/// groups become `roundRobin` (or `random`) balancers. A select group whose
+/// first member is a balancer routes to that balancer. Balancer selectors are
+/// matched by Xray as tag prefixes, so outbound tags are made prefix-unique.
+///
+/// # Arguments
+/// * `nodes` - List of proxy nodes to convert
+/// * `base_conf` - Base Xray configuration as a string
+/// * `ruleset_content_array` - Array of ruleset contents to apply
+/// * `extra_proxy_group` - Extra proxy group configurations
+/// * `ext` - Extra settings for conversion
+///
+/// # Returns
+/// * Converted configuration as a string
+pub fn proxy_to_xray(
+    nodes: &mut Vec<Proxy>,
+    base_conf: &str,
+    ruleset_content_array: &mut Vec<RulesetContent>,
+    extra_proxy_group: &ProxyGroupConfigs,
+    ext: &mut ExtraSettings,
+) -> String {
+    // Parse the base configuration
+    let mut json: JsonValue = if ext.nodelist {
+        json!({})
+    } else {
+        match serde_json::from_str(base_conf) {
+            Ok(json) => json,
+            Err(e) => {
+                error!(
+                    "Xray base loader failed with error: {}, base_conf: {}",
+                    e, base_conf
+                );
+                return String::new();
+            }
+        }
+    };
+    if !json.is_object() {
+        json = json!({});
+    }
+
+    let mut outbounds = Vec::new();
+    let mut nodelist = Vec::new();
+    let mut remarks_list = Vec::new();
+
+    // Process each proxy node
+    for node in nodes.iter_mut() {
+        // Add proxy type prefix if enabled
+        if ext.append_proxy_type {
+            let proxy_type = node.proxy_type.to_string();
+            node.remark = format!("[{}] {}", proxy_type, node.remark);
+        }
+
+        // Process remark
+        let mut remark = node.remark.clone();
+        process_remark(&mut remark, &remarks_list, false);
+        node.remark = remark;
+
+        let tfo = node.tcp_fast_open.or(ext.tfo);
+        let scv = node.allow_insecure.or(ext.skip_cert_verify);
+
+        let outbound = match node_to_xray_outbound(node, scv, tfo) {
+            Some(outbound) => outbound,
+            None => {
+                warn!(
+                    "Skipping node '{}': {} is not supported by Xray",
+                    node.remark,
+                    node.proxy_type.to_string()
+                );
+                continue;
+            }
+        };
+
+        nodelist.push(node.clone());
+        remarks_list.push(node.remark.clone());
+        outbounds.push(outbound);
+    }
+
+    // If nodelist mode, just return outbounds
+    if ext.nodelist {
+        json["outbounds"] = JsonValue::Array(outbounds);
+        return serde_json::to_string_pretty(&json).unwrap_or_default();
+    }
+
+    // Make sure no selector picks up outbounds it does not name
+    let tags = prefix_unique_tags(&remarks_list);
+    let mut node_tags = HashMap::new();
+    for ((outbound, remark), tag) in outbounds.iter_mut().zip(&remarks_list).zip(tags) {
+        if *remark != tag {
+            warn!(
+                "Renaming Xray outbound '{}' to '{}' as its tag is a prefix of another one",
+                remark, tag
+            );
+            outbound["tag"] = JsonValue::String(tag.clone());
+        }
+        node_tags.insert(remark.clone(), tag);
+    }
+
+    outbounds.push(json!({ "tag": "DIRECT", "protocol": "freedom" }));
+    outbounds.push(json!({ "tag": "REJECT", "protocol": "blackhole" }));
+
+    // Collect the groups that become balancers first, so select groups can
+    // route to balancers defined after them
+    let balancer_names: Vec<&str> = extra_proxy_group
+        .iter()
+        .filter(|group| {
+            matches!(
+                group.group_type,
+                ProxyGroupType::URLTest | ProxyGroupType::Fallback | ProxyGroupType::LoadBalance
+            )
+        })
+        .map(|group| group.name.as_str())
+        .collect();
+
+    // Process proxy groups
+    let mut route_targets: HashMap<String, XrayRouteTarget> = HashMap::new();
+    let mut group_members: HashMap<String, Vec<String>> = HashMap::new();
+    let mut balancers = Vec::new();
+    let mut observed = Vec::new();
+    let mut probe: Option<(&str, u32)> = None;
+
+    for group in extra_proxy_group {
+        let mut filtered_nodelist = Vec::new();
+        for proxy_name in &group.proxies {
+            group_generate(proxy_name, &nodelist, &mut filtered_nodelist, true, ext);
+        }
+        let first = filtered_nodelist.first().cloned();
+
+        // Groups referencing earlier groups use their members instead
+        let mut members: Vec<String> = Vec::new();
+        for name in filtered_nodelist {
+            let names = match group_members.get(&name) {
+                Some(nested) => nested.clone(),
+                None => vec![node_tags.get(&name).cloned().unwrap_or(name)],
+            };
+            for name in names {
+                if !members.contains(&name) {
+                    members.push(name);
+                }
+            }
+        }
+
+        // Add DIRECT if empty
+        if members.is_empty() {
+            members.push("DIRECT".to_string());
+        }
+
+        let strategy = match group.group_type {
+            ProxyGroupType::Select => {
+                let target = match first {
+                    Some(name) if balancer_names.contains(&name.as_str()) => {
+                        XrayRouteTarget::Balancer(name)
+                    }
+                    Some(name) if route_targets.contains_key(&name) => route_targets[&name].clone(),
+                    _ => XrayRouteTarget::Outbound(members[0].clone()),
+                };
+                route_targets.insert(group.name.clone(), target);
+                group_members.insert(group.name.clone(), members);
+                continue;
+            }
+            ProxyGroupType::URLTest | ProxyGroupType::Fallback => {
+                for member in &members {
+                    if !observed.contains(member) {
+                        observed.push(member.clone());
+                    }
+                }
+                probe.get_or_insert((group.url.as_str(), group.interval));
+                "leastPing"
+            }
+            ProxyGroupType::LoadBalance => match group.strategy {
+                BalanceStrategy::RoundRobin => "roundRobin",
+                BalanceStrategy::ConsistentHashing => "random",
+            },
+            _ => continue, // Skip unsupported types
+        };
+
+        balancers.push(json!({
+            "tag": group.name,
+            "selector": members,
+            "strategy": { "type": strategy },
+        }));
+        route_targets.insert(
+            group.name.clone(),
+            XrayRouteTarget::Balancer(group.name.clone()),
+        );
+        group_members.insert(group.name.clone(), members);
+    }
+
+    json["outbounds"] = JsonValue::Array(outbounds);
+
+    if !balancers.is_empty() {
+        if !json["routing"].is_object() {
+            json["routing"] = json!({});
+        }
+        json["routing"]["balancers"] = JsonValue::Array(balancers);
+    }
+
+    if let Some((url, interval)) = probe {
+        let mut observatory = Map::new();
+        observatory.insert("subjectSelector".to_string(), json!(observed));
+        if !url.is_empty() {
+            observatory.insert("probeUrl".to_string(), JsonValue::String(url.to_string()));
+        }
+        if interval > 0 {
+            observatory.insert(
+                "probeInterval".to_string(),
+                JsonValue::String(format!("{}s", interval)),
+            );
+        }
+        observatory.insert("enableConcurrency".to_string(), JsonValue::Bool(true));
+        json["observatory"] = JsonValue::Object(observatory);
+    }
+
+    // Handle rule generation
+    if !ext.enable_rule_generator {
+        return serde_json::to_string_pretty(&json).unwrap_or_default();
+    }
+
+    ruleset_to_xray(
+        &mut json,
+        ruleset_content_array,
+        ext.overwrite_original_rules,
+        &route_targets,
+    );
+
+    serde_json::to_string_pretty(&json).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::test_utils::{
+        inline_rulesets, nodes, rule_generator_settings,
+    };
+    use crate::models::ProxyGroupConfig;
+    use crate::parser::explodes::explode_conf_content;
+
+    const NODES: &str = r#"
+proxies:
+  - { name: reality, type: vless, server: reality.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000001, tls: true, flow: xtls-rprx-vision, servername: www.microsoft.com, network: grpc, grpc-opts: { grpc-service-name: svc }, reality-opts: { public-key: pbk, short-id: sid } }
+  - { name: xhttp, type: vless, server: xhttp.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, network: xhttp, xhttp-opts: { path: /xh, host: cdn.example.com, mode: stream-one } }
+  - { name: vmess, type: vmess, server: vmess.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000003, alterId: 0, cipher: auto, tls: true, network: ws, ws-opts: { path: /ws, headers: { Host: cdn.example.com } } }
+  - { name: trojan, type: trojan, server: trojan.example.com, port: 443, password: pw, sni: trojan.example.com }
+  - { name: ss, type: ss, server: 1.2.3.4, port: 8388, cipher: aes-256-gcm, password: sspass }
+  - { name: hy2, type: hysteria2, server: hy2.example.com, port: 443, password: pw }
+"#;
+
+    /// Returns the Auto, Balance and Proxy groups, in that order
+    fn groups() -> Vec<ProxyGroupConfig> {
+        let mut select = ProxyGroupConfig::new("Proxy".to_string(), ProxyGroupType::Select);
+        select.proxies = vec!["[]Auto".to_string(), ".*".to_string()];
+        let mut auto = ProxyGroupConfig::new("Auto".to_string(), ProxyGroupType::URLTest);
+        auto.proxies = vec!["vmess|trojan".to_string()];
+        auto.url = "https://www.gstatic.com/generate_204".to_string();
+        auto.interval = 300;
+        let mut balance = ProxyGroupConfig::new("Balance".to_string(), ProxyGroupType::LoadBalance);
+        balance.proxies = vec!["[]Auto".to_string(), "ss".to_string()];
+        balance.strategy = BalanceStrategy::RoundRobin;
+        vec![auto, balance, select]
+    }
+
+    fn generate_with(
+        clash_yaml: &str,
+        rulesets: &mut Vec<RulesetContent>,
+        groups: &ProxyGroupConfigs,
+        nodelist: bool,
+    ) -> JsonValue {
+        let mut ext = rule_generator_settings();
+        ext.nodelist = nodelist;
+        let output = proxy_to_xray(
+            &mut nodes(clash_yaml),
+            r#"{ "log": { "loglevel": "warning" }, "routing": { "domainStrategy": "IPIfNonMatch" } }"#,
+            rulesets,
+            groups,
+            &mut ext,
+        );
+        serde_json::from_str(&output).unwrap()
+    }
+
+    fn generate(rulesets: &mut Vec<RulesetContent>, nodelist: bool) -> JsonValue {
+        generate_with(NODES, rulesets, &groups(), nodelist)
+    }
+
+    fn find_outbound<'a>(config: &'a JsonValue, tag: &str) -> &'a JsonValue {
+        config["outbounds"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|o| o["tag"] == tag)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_xray_stream_settings() {
+        let xray = generate(&mut Vec::new(), false);
+        let tags: Vec<&str> = xray["outbounds"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|o| o["tag"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            tags,
+            vec!["reality", "xhttp", "vmess", "trojan", "ss", "DIRECT", "REJECT"]
+        );
+
+        let reality = find_outbound(&xray, "reality");
+        assert_eq!(
+            reality["settings"]["vnext"][0]["users"][0]["flow"],
+            "xtls-rprx-vision"
+        );
+        let stream = &reality["streamSettings"];
+        assert_eq!(stream["network"], "grpc");
+        assert_eq!(stream["grpcSettings"]["serviceName"], "svc");
+        assert_eq!(stream["security"], "reality");
+        assert_eq!(
+            stream["realitySettings"],
+            json!({ "serverName": "www.microsoft.com", "fingerprint": "chrome", "publicKey": "pbk", "shortId": "sid" })
+        );
+
+        let stream = &find_outbound(&xray, "xhttp")["streamSettings"];
+        assert_eq!(
+            stream["xhttpSettings"],
+            json!({ "path": "/xh", "host": "cdn.example.com", "mode": "stream-one" })
+        );
+        assert_eq!(stream["security"], "tls");
+
+        let stream = &find_outbound(&xray, "vmess")["streamSettings"];
+        assert_eq!(
+            stream["wsSettings"],
+            json!({ "path": "/ws", "host": "cdn.example.com" })
+        );
+
+        let stream = &find_outbound(&xray, "trojan")["streamSettings"];
+        assert_eq!(stream["network"], "tcp");
+        assert_eq!(stream["tlsSettings"]["serverName"], "trojan.example.com");
+    }
+
+    #[test]
+    fn test_xray_balancers_and_routing() {
+        let mut rulesets = Vec::new();
+        for (rule, group) in [
+            ("DOMAIN-SUFFIX,google.com", "Proxy"),
+            ("GEOIP,CN", "DIRECT"),
+            ("DOMAIN-KEYWORD,netflix", "Balance"),
+            ("MATCH", "Auto"),
+        ] {
+            rulesets.extend(inline_rulesets(&[rule], group));
+        }
+        let xray = generate(&mut rulesets, false);
+
+        assert_eq!(xray["routing"]["domainStrategy"], "IPIfNonMatch");
+        assert_eq!(
+            xray["routing"]["balancers"],
+            json!([
+                { "tag": "Auto", "selector": ["vmess", "trojan"], "strategy": { "type": "leastPing" } },
+                { "tag": "Balance", "selector": ["vmess", "trojan", "ss"], "strategy": { "type": "roundRobin" } },
+            ])
+        );
+        assert_eq!(
+            xray["observatory"],
+            json!({
+                "subjectSelector": ["vmess", "trojan"],
+                "probeUrl": "https://www.gstatic.com/generate_204",
+                "probeInterval": "300s",
+                "enableConcurrency": true,
+            })
+        );
+        assert_eq!(
+            xray["routing"]["rules"],
+            json!([
+                { "type": "field", "domain": ["domain:google.com"], "balancerTag": "Auto" },
+                { "type": "field", "ip": ["geoip:cn"], "outboundTag": "DIRECT" },
+                { "type": "field", "domain": ["netflix"], "balancerTag": "Balance" },
+                { "type": "field", "network": "tcp,udp", "balancerTag": "Auto" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_xray_select_group_before_balancer() {
+        let mut groups = groups();
+        groups.rotate_right(1);
+        assert_eq!(groups[0].name, "Proxy");
+
+        let mut rulesets = inline_rulesets(&["MATCH"], "Proxy");
+        let xray = generate_with(NODES, &mut rulesets, &groups, false);
+        assert_eq!(
+            xray["routing"]["rules"],
+            json!([{ "type": "field", "network": "tcp,udp", "balancerTag": "Auto" }])
+        );
+    }
+
+    #[test]
+    fn test_xray_prefix_unique_tags() {
+        let nodes = r#"
+proxies:
+  - { name: HK 1, type: ss, server: 1.1.1.1, port: 8388, cipher: aes-256-gcm, password: pw }
+  - { name: HK 10, type: ss, server: 1.1.1.10, port: 8388, cipher: aes-256-gcm, password: pw }
+  - { name: HK 1 (1), type: ss, server: 1.1.1.11, port: 8388, cipher: aes-256-gcm, password: pw }
+"#;
+        let mut auto = ProxyGroupConfig::new("Auto".to_string(), ProxyGroupType::URLTest);
+        auto.proxies = vec!["^HK 1$".to_string()];
+        let xray = generate_with(nodes, &mut Vec::new(), &vec![auto], false);
+
+        let tags: Vec<&str> = xray["outbounds"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|o| o["tag"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            tags,
+            vec!["HK 1 (2)", "HK 10", "HK 1 (1)", "DIRECT", "REJECT"]
+        );
+        assert_eq!(
+            xray["routing"]["balancers"][0]["selector"],
+            json!(["HK 1 (2)"])
+        );
+        assert_eq!(xray["observatory"]["subjectSelector"], json!(["HK 1 (2)"]));
+    }
+
+    #[test]
+    fn test_xray_round_trip() {
+        let xray = generate(&mut Vec::new(), true);
+        assert!(xray.get("routing").is_none());
+
+        let mut nodes = Vec::new();
+        let count = explode_conf_content(&xray.to_string(), &mut nodes);
+        assert_eq!(count, 5);
+
+        let vless = match &nodes[0].combined_proxy {
+            Some(CombinedProxy::Vless(vless)) => vless,
+            _ => panic!("expected a VLESS combined proxy"),
+        };
+        assert_eq!(nodes[0].remark, "reality");
+        assert_eq!(vless.network.as_deref(), Some("grpc"));
+        assert_eq!(vless.grpc_service_name.as_deref(), Some("svc"));
+        assert_eq!(vless.reality_public_key.as_deref(), Some("pbk"));
+        assert_eq!(vless.reality_short_id.as_deref(), Some("sid"));
+
+        let vmess = &nodes[2];
+        assert_eq!(vmess.transfer_protocol.as_deref(), Some("ws"));
+        assert_eq!(vmess.path.as_deref(), Some("/ws"));
+        assert_eq!(vmess.host.as_deref(), Some("cdn.example.com"));
+        assert_eq!(nodes[4].password.as_deref(), Some("sspass"));
+    }
+}
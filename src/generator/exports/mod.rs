@@ -1 +1,2 @@
 pub mod proxy_to_clash;
+pub mod proxy_to_stash;
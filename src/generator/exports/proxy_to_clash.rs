@@ -3,8 +3,11 @@ use crate::generator::config::remark::process_remark;
 use crate::generator::ruleconvert::ruleset_to_clash_str;
 use crate::generator::yaml::clash::clash_output::ClashProxyOutput;
 use crate::generator::yaml::proxy_group_output::convert_proxy_groups;
-use crate::models::{ExtraSettings, Proxy, ProxyGroupConfigs, ProxyType, RulesetContent};
-use log::error;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::{
+    ExtraSettings, Proxy, ProxyGroupConfigs, ProxyType, RulesetContent, SubconverterTarget,
+};
+use log::{error, warn};
 use serde_yaml::{self, Mapping, Sequence, Value as YamlValue};
 use std::collections::{HashMap, HashSet};
 
@@ -58,6 +61,30 @@ lazy_static::lazy_static! {
         obfs.insert("tls1.2_ticket_fastauth");
         obfs
     };
+
+    // VLESS transports that can be expressed in mihomo configuration
+    static ref CLASH_VLESS_NETWORKS: HashSet<&'static str> = {
+        let mut networks = HashSet::new();
+        networks.insert("tcp");
+        networks.insert("ws");
+        networks.insert("httpupgrade");
+        networks.insert("http");
+        networks.insert("h2");
+        networks.insert("grpc");
+        networks.insert("xhttp");
+        networks
+    };
+
+    // VLESS transports that can be expressed in Stash configuration
+    static ref STASH_VLESS_NETWORKS: HashSet<&'static str> = {
+        let mut networks = HashSet::new();
+        networks.insert("tcp");
+        networks.insert("ws");
+        networks.insert("http");
+        networks.insert("h2");
+        networks.insert("grpc");
+        networks
+    };
 }
 
 /// Convert proxies to Clash format
@@ -70,14 +97,14 @@ lazy_static::lazy_static! {
 /// * `base_conf` - Base Clash configuration as a string
 /// * `ruleset_content_array` - Array of ruleset contents to apply
 /// * `extra_proxy_group` - Extra proxy group configurations
-/// * `clash_r` - Whether to use ClashR format
+/// * `target` - Clash flavour to generate (Clash, ClashR or mihomo)
 /// * `ext` - Extra settings for conversion
 pub fn proxy_to_clash(
     nodes: &mut Vec<Proxy>,
     base_conf: &str,
     ruleset_content_array: &mut Vec<RulesetContent>,
     extra_proxy_group: &ProxyGroupConfigs,
-    clash_r: bool,
+    target: &SubconverterTarget,
     ext: &mut ExtraSettings,
 ) -> String {
     // Parse the base configuration
@@ -99,7 +126,7 @@ pub fn proxy_to_clash(
         &mut yaml_node,
         ruleset_content_array,
         extra_proxy_group,
-        clash_r,
+        target,
         ext,
     );
 
@@ -119,26 +146,18 @@ pub fn proxy_to_clash(
         };
     }
 
-    // Handle managed config and clash script
-    if !ext.managed_config_prefix.is_empty() || ext.clash_script {
+    // Handle clash script
+    if ext.clash_script {
         // Set mode if it exists
         if yaml_node.get("mode").is_some() {
             if let Some(ref mut map) = yaml_node.as_mapping_mut() {
                 map.insert(
                     YamlValue::String("mode".to_string()),
                     YamlValue::String(
-                        if ext.clash_script {
-                            if ext.clash_new_field_name {
-                                "script"
-                            } else {
-                                "Script"
-                            }
+                        if ext.clash_new_field_name {
+                            "script"
                         } else {
-                            if ext.clash_new_field_name {
-                                "rule"
-                            } else {
-                                "Rule"
-                            }
+                            "Script"
                         }
                         .to_string(),
                     ),
@@ -156,10 +175,12 @@ pub fn proxy_to_clash(
 
     // Generate rules and return combined output
     let rules_str = ruleset_to_clash_str(
-        &yaml_node,
+        &mut yaml_node,
         ruleset_content_array,
         ext.overwrite_original_rules,
         ext.clash_new_field_name,
+        target,
+        &ext.managed_config_prefix,
     );
 
     let yaml_output = match serde_yaml::to_string(&yaml_node) {
@@ -180,16 +201,20 @@ pub fn proxy_to_clash(
 /// * `yaml_node` - YAML node to modify
 /// * `ruleset_content_array` - Array of ruleset contents to apply
 /// * `extra_proxy_group` - Extra proxy group configurations
-/// * `clash_r` - Whether to use ClashR format
+/// * `target` - Clash flavour to generate (Clash, ClashR, mihomo or Stash)
 /// * `ext` - Extra settings for conversion
 pub fn proxy_to_clash_yaml(
     nodes: &mut Vec<Proxy>,
     yaml_node: &mut serde_yaml::Value,
     _ruleset_content_array: &Vec<RulesetContent>,
     extra_proxy_group: &ProxyGroupConfigs,
-    clash_r: bool,
+    target: &SubconverterTarget,
     ext: &mut ExtraSettings,
 ) {
+    let clash_r = *target == SubconverterTarget::ClashR;
+    let mihomo = *target == SubconverterTarget::Mihomo;
+    let stash = *target == SubconverterTarget::Stash;
+
     // Style settings - in C++ this is used to set serialization style but in Rust we have less control
     // over the serialization format. We keep them for compatibility but their actual effect may differ.
     let _proxy_block = ext.clash_proxies_style == "block";
@@ -240,6 +265,52 @@ pub fn proxy_to_clash_yaml(
                     || !CLASHR_OBFS.contains(obfs)
             }
 
+            // Skip proxy types only mihomo understands
+            ProxyType::AnyTls | ProxyType::Ssh if !mihomo => {
+                warn!(
+                    "Skipping {} node '{}': only supported by the mihomo target",
+                    node.proxy_type.to_string(),
+                    node.remark
+                );
+                true
+            }
+
+            // Skip proxy types only mihomo and Stash understand
+            ProxyType::Vless | ProxyType::Hysteria | ProxyType::Hysteria2 | ProxyType::Tuic
+                if !mihomo && !stash =>
+            {
+                warn!(
+                    "Skipping {} node '{}': only supported by the mihomo and stash targets",
+                    node.proxy_type.to_string(),
+                    node.remark
+                );
+                true
+            }
+
+            // Skip VLESS transports the target cannot express
+            ProxyType::Vless => match &node.combined_proxy {
+                Some(CombinedProxy::Vless(vless)) => {
+                    let network = vless.network.as_deref().unwrap_or("tcp");
+                    let networks = if stash {
+                        &*STASH_VLESS_NETWORKS
+                    } else {
+                        &*CLASH_VLESS_NETWORKS
+                    };
+                    if networks.contains(network) {
+                        false
+                    } else {
+                        warn!(
+                            "Skipping VLESS node '{}': transport '{}' is not supported by {}",
+                            node.remark,
+                            network,
+                            target.to_str()
+                        );
+                        true
+                    }
+                }
+                _ => false,
+            },
+
             // Skip unsupported proxy types
             ProxyType::Unknown | ProxyType::HTTPS => true,
 
@@ -317,7 +388,22 @@ pub fn proxy_to_clash_yaml(
         }
 
         // Convert proxy groups using the new serialization
-        let clash_proxy_groups = convert_proxy_groups(extra_proxy_group, Some(&filtered_nodes_map));
+        let mut clash_proxy_groups =
+            convert_proxy_groups(extra_proxy_group, Some(&filtered_nodes_map));
+
+        // Provider filters are a mihomo extension
+        if !mihomo {
+            for group in clash_proxy_groups.iter_mut() {
+                if !group.filter.is_empty() || !group.exclude_filter.is_empty() {
+                    warn!(
+                        "Dropping filter of group '{}': only supported by the mihomo target",
+                        group.name
+                    );
+                    group.filter.clear();
+                    group.exclude_filter.clear();
+                }
+            }
+        }
 
         // Merge with existing groups
         for group in clash_proxy_groups {
@@ -366,3 +452,283 @@ pub fn proxy_to_clash_yaml(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ProxyGroupConfig, ProxyGroupType, RulesetType};
+    use crate::parser::yaml::clash::parse_clash_yaml;
+
+    const NODES: &str = r#"
+proxies:
+  - { name: ss, type: ss, server: ss.example.com, port: 8388, cipher: aes-256-gcm, password: pw }
+  - { name: vless, type: vless, server: vless.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, network: ws }
+  - { name: tuic, type: tuic, server: tuic.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000003, password: pw }
+  - { name: ssh, type: ssh, server: ssh.example.com, port: 22, username: root, password: pw }
+"#;
+
+    fn generate(target: &SubconverterTarget, rulesets: &mut Vec<RulesetContent>) -> YamlValue {
+        generate_with_prefix(target, rulesets, "")
+    }
+
+    fn generate_with_prefix(
+        target: &SubconverterTarget,
+        rulesets: &mut Vec<RulesetContent>,
+        remote_path_prefix: &str,
+    ) -> YamlValue {
+        let mut nodes = parse_clash_yaml(NODES).unwrap();
+        let mut group = ProxyGroupConfig::new("Proxy".to_string(), ProxyGroupType::Select);
+        group.proxies = vec![".*".to_string()];
+        group.using_provider = vec!["provider".to_string()];
+        group.filter = "(?i)hk".to_string();
+        group.exclude_filter = "expire".to_string();
+
+        let mut ext = ExtraSettings::default();
+        ext.enable_rule_generator = true;
+        ext.overwrite_original_rules = false;
+        ext.managed_config_prefix = remote_path_prefix.to_string();
+        let output = proxy_to_clash(
+            &mut nodes,
+            "rules:\n  - DOMAIN,base.example.com,DIRECT\n",
+            rulesets,
+            &vec![group],
+            target,
+            &mut ext,
+        );
+        serde_yaml::from_str(&output).unwrap()
+    }
+
+    fn proxy_names(config: &YamlValue) -> Vec<&str> {
+        config["proxies"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect()
+    }
+
+    fn rules(config: &YamlValue) -> Vec<&str> {
+        config["rules"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|r| r.as_str().unwrap())
+            .collect()
+    }
+
+    fn inline_rules(rules: &[&str]) -> Vec<RulesetContent> {
+        rules
+            .iter()
+            .map(|rule| {
+                let mut ruleset = RulesetContent::new("", "Proxy");
+                ruleset.set_rule_content(&format!("[]{}", rule));
+                ruleset
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mihomo_only_proxy_types() {
+        let mihomo = generate(&SubconverterTarget::Mihomo, &mut Vec::new());
+        assert_eq!(proxy_names(&mihomo), vec!["ss", "vless", "tuic", "ssh"]);
+        assert_eq!(mihomo["proxies"][3]["type"].as_str(), Some("ssh"));
+        assert_eq!(mihomo["proxies"][3]["username"].as_str(), Some("root"));
+
+        let clash = generate(&SubconverterTarget::Clash, &mut Vec::new());
+        assert_eq!(proxy_names(&clash), vec!["ss"]);
+    }
+
+    #[test]
+    fn test_group_filters() {
+        let mihomo = generate(&SubconverterTarget::Mihomo, &mut Vec::new());
+        let group = &mihomo["proxy-groups"][0];
+        assert_eq!(group["filter"].as_str(), Some("(?i)hk"));
+        assert_eq!(group["exclude-filter"].as_str(), Some("expire"));
+
+        let clash = generate(&SubconverterTarget::Clash, &mut Vec::new());
+        let group = &clash["proxy-groups"][0];
+        assert!(group.get("filter").is_none());
+        assert!(group.get("exclude-filter").is_none());
+    }
+
+    #[test]
+    fn test_mihomo_rule_types() {
+        let source = [
+            "GEOSITE,google",
+            "DOMAIN-REGEX,^ads\\.",
+            "AND,((DOMAIN-SUFFIX,example.com),(NETWORK,UDP))",
+            "DOMAIN-SUFFIX,google.com",
+            "MATCH",
+        ];
+
+        let mihomo = generate(&SubconverterTarget::Mihomo, &mut inline_rules(&source));
+        assert_eq!(
+            rules(&mihomo),
+            vec![
+                "DOMAIN,base.example.com,DIRECT",
+                "GEOSITE,google,Proxy",
+                "DOMAIN-REGEX,^ads\\.,Proxy",
+                "AND,((DOMAIN-SUFFIX,example.com),(NETWORK,UDP)),Proxy",
+                "DOMAIN-SUFFIX,google.com,Proxy",
+                "MATCH,Proxy",
+            ]
+        );
+
+        let clash = generate(&SubconverterTarget::Clash, &mut inline_rules(&source));
+        assert_eq!(
+            rules(&clash),
+            vec![
+                "DOMAIN,base.example.com,DIRECT",
+                "DOMAIN-SUFFIX,google.com,Proxy",
+                "MATCH,Proxy",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_logical_rules() {
+        let mut ruleset = RulesetContent::new("rules/logical.yaml", "Proxy");
+        ruleset.rule_type = RulesetType::ClashClassical;
+        ruleset.set_rule_content(
+            "payload:\n  - 'AND,((DOMAIN-SUFFIX,example.com), (OR,((NETWORK,UDP),(NOT,((DST-PORT,443))))))'\n  - 'NOT,((DST-PORT,80))'\n",
+        );
+        let mut rulesets = inline_rules(&["OR,((GEOSITE,google),(DOMAIN,a.com))"]);
+        rulesets.push(ruleset);
+
+        let mihomo = generate(&SubconverterTarget::Mihomo, &mut rulesets);
+        assert_eq!(
+            rules(&mihomo),
+            vec![
+                "DOMAIN,base.example.com,DIRECT",
+                "OR,((GEOSITE,google),(DOMAIN,a.com)),Proxy",
+                "AND,((DOMAIN-SUFFIX,example.com),(OR,((NETWORK,UDP),(NOT,((DST-PORT,443)))))),Proxy",
+                "NOT,((DST-PORT,80)),Proxy",
+            ]
+        );
+
+        // Stash has logical rules and GEOSITE but no NETWORK
+        let stash = generate(&SubconverterTarget::Stash, &mut rulesets);
+        assert_eq!(
+            rules(&stash),
+            vec![
+                "DOMAIN,base.example.com,DIRECT",
+                "OR,((GEOSITE,google),(DOMAIN,a.com)),Proxy",
+                "NOT,((DST-PORT,80)),Proxy",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mihomo_rule_providers() {
+        let mut ruleset = RulesetContent::new("https://example.com/rules/reject.yaml", "REJECT");
+        ruleset.rule_type = RulesetType::ClashDomain;
+        ruleset.update_interval = 3600;
+        ruleset.set_rule_content("payload:\n  - '+.ads.example.com'\n");
+
+        let mihomo = generate(&SubconverterTarget::Mihomo, &mut vec![ruleset.clone()]);
+        assert_eq!(
+            rules(&mihomo),
+            vec!["DOMAIN,base.example.com,DIRECT", "RULE-SET,reject,REJECT"]
+        );
+        let provider = &mihomo["rule-providers"]["reject"];
+        assert_eq!(provider["behavior"].as_str(), Some("domain"));
+        assert_eq!(
+            provider["url"].as_str(),
+            Some("https://example.com/rules/reject.yaml")
+        );
+        assert_eq!(provider["interval"].as_u64(), Some(3600));
+
+        let clash = generate(&SubconverterTarget::Clash, &mut vec![ruleset]);
+        assert!(clash.get("rule-providers").is_none());
+        assert_eq!(
+            rules(&clash),
+            vec![
+                "DOMAIN,base.example.com,DIRECT",
+                "DOMAIN-SUFFIX,ads.example.com,REJECT"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clash_rule_providers_from_getruleset() {
+        let mut surge = RulesetContent::new("https://example.com/rules/Netflix.list", "Proxy");
+        surge.set_rule_content("DOMAIN-SUFFIX,netflix.com\n");
+        let mut reject = RulesetContent::new("https://example.com/rules/reject.yaml", "REJECT");
+        reject.rule_type = RulesetType::ClashDomain;
+        reject.set_rule_content("payload:\n  - '+.ads.example.com'\n");
+        let mut rulesets = vec![surge.clone(), reject];
+        rulesets.extend(inline_rules(&["MATCH"]));
+
+        let clash = generate_with_prefix(
+            &SubconverterTarget::Clash,
+            &mut rulesets,
+            "http://127.0.0.1:25500",
+        );
+        assert_eq!(
+            rules(&clash),
+            vec![
+                "DOMAIN,base.example.com,DIRECT",
+                "RULE-SET,Netflix,Proxy",
+                "RULE-SET,reject,REJECT",
+                "MATCH,Proxy",
+            ]
+        );
+
+        let provider = &clash["rule-providers"]["Netflix"];
+        assert_eq!(provider["type"].as_str(), Some("http"));
+        assert_eq!(provider["behavior"].as_str(), Some("classical"));
+        assert_eq!(
+            provider["url"].as_str(),
+            Some(
+                format!(
+                    "http://127.0.0.1:25500/getruleset?type=6&url={}",
+                    crate::utils::base64::url_safe_base64_encode(&surge.rule_path_typed)
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(
+            provider["path"].as_str(),
+            Some("./providers/rule-provider_Netflix.yaml")
+        );
+        assert_eq!(provider["interval"].as_u64(), Some(86400));
+
+        let provider = &clash["rule-providers"]["reject"];
+        assert_eq!(provider["behavior"].as_str(), Some("domain"));
+        assert_eq!(
+            provider["url"].as_str(),
+            Some("https://example.com/rules/reject.yaml")
+        );
+    }
+
+    #[test]
+    fn test_mihomo_rule_providers_from_getruleset() {
+        let mut surge = RulesetContent::new("rules/Ads.list", "REJECT");
+        surge.set_rule_content("DOMAIN-SUFFIX,ads.example.com\nGEOSITE,category-ads\n");
+        let mut rulesets = vec![surge.clone()];
+
+        let mihomo = generate_with_prefix(
+            &SubconverterTarget::Mihomo,
+            &mut rulesets,
+            "http://127.0.0.1:25500",
+        );
+        assert_eq!(
+            rules(&mihomo),
+            vec!["DOMAIN,base.example.com,DIRECT", "RULE-SET,Ads,REJECT"]
+        );
+        let provider = &mihomo["rule-providers"]["Ads"];
+        assert_eq!(provider["format"].as_str(), Some("yaml"));
+        // The payload is served with the mihomo rule types, GEOSITE included
+        assert_eq!(
+            provider["url"].as_str(),
+            Some(
+                format!(
+                    "http://127.0.0.1:25500/getruleset?type=6&url={}&target=mihomo",
+                    crate::utils::base64::url_safe_base64_encode(&surge.rule_path_typed)
+                )
+                .as_str()
+            )
+        );
+    }
+
+}
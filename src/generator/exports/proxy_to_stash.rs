@@ -0,0 +1,224 @@
+use crate::generator::exports::proxy_to_clash::proxy_to_clash_yaml;
+use crate::generator::ruleconvert::ruleset_to_clash_str;
+use crate::models::{ExtraSettings, Proxy, ProxyGroupConfigs, RulesetContent, SubconverterTarget};
+use log::error;
+use serde_yaml::{self, Mapping, Value as YamlValue};
+
+/// Convert proxies to Stash format
+///
+/// Stash reads Clash configuration, so the proxies and groups are generated by
+/// `proxy_to_clash_yaml` and then adjusted to the options Stash names
+/// differently. Remote Clash rulesets are referenced as `rule-providers`.
+///
+/// # Arguments
+/// * `nodes` - List of proxy nodes to convert
+/// * `base_conf` - Base Clash configuration as a string
+/// * `ruleset_content_array` - Array of ruleset contents to apply
+/// * `extra_proxy_group` - Extra proxy group configurations
+/// * `ext` - Extra settings for conversion
+pub fn proxy_to_stash(
+    nodes: &mut Vec<Proxy>,
+    base_conf: &str,
+    ruleset_content_array: &mut Vec<RulesetContent>,
+    extra_proxy_group: &ProxyGroupConfigs,
+    ext: &mut ExtraSettings,
+) -> String {
+    // Parse the base configuration
+    let mut yaml_node: YamlValue = match serde_yaml::from_str(base_conf) {
+        Ok(node) => node,
+        Err(e) => {
+            error!("Stash base loader failed with error: {}", e);
+            return String::new();
+        }
+    };
+
+    if yaml_node.is_null() {
+        yaml_node = YamlValue::Mapping(Mapping::new());
+    }
+
+    // Stash only understands the new field names
+    ext.clash_new_field_name = true;
+
+    proxy_to_clash_yaml(
+        nodes,
+        &mut yaml_node,
+        ruleset_content_array,
+        extra_proxy_group,
+        &SubconverterTarget::Stash,
+        ext,
+    );
+
+    if let Some(proxies) = yaml_node
+        .get_mut("proxies")
+        .and_then(|proxies| proxies.as_sequence_mut())
+    {
+        for proxy in proxies
+            .iter_mut()
+            .filter_map(|proxy| proxy.as_mapping_mut())
+        {
+            adapt_stash_proxy(proxy);
+        }
+    }
+
+    if ext.nodelist || !ext.enable_rule_generator {
+        return serde_yaml::to_string(&yaml_node).unwrap_or_default();
+    }
+
+    // Generate rules and return combined output
+    let rules_str = ruleset_to_clash_str(
+        &mut yaml_node,
+        ruleset_content_array,
+        ext.overwrite_original_rules,
+        true,
+        &SubconverterTarget::Stash,
+        &ext.managed_config_prefix,
+    );
+
+    let yaml_output = serde_yaml::to_string(&yaml_node).unwrap_or_default();
+
+    format!("{}{}", yaml_output, rules_str)
+}
+
+/// Rewrites the options of a generated proxy that Stash names differently
+fn adapt_stash_proxy(proxy: &mut Mapping) {
+    match proxy.get("type").and_then(|t| t.as_str()) {
+        Some("hysteria2") => {
+            if let Some(password) = proxy.remove("password") {
+                proxy.insert("auth".into(), password);
+            }
+            for (from, to) in [("up", "up-speed"), ("down", "down-speed")] {
+                let speed = proxy
+                    .get(from)
+                    .and_then(|value| value.as_str())
+                    .and_then(parse_mbps);
+                if let Some(speed) = speed {
+                    proxy.remove(from);
+                    proxy.insert(to.into(), speed.into());
+                }
+            }
+        }
+        Some("tuic") if !proxy.contains_key("version") => {
+            proxy.insert("version".into(), 5.into());
+        }
+        _ => {}
+    }
+}
+
+/// Parses a bandwidth like `100`, `100 Mbps` or `1Gbps` into Mbps
+fn parse_mbps(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let number: u32 = value[..digits].parse().ok()?;
+    match value[digits..].trim().to_ascii_lowercase().as_str() {
+        "" | "m" | "mbps" => Some(number),
+        "g" | "gbps" => Some(number * 1000),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::test_utils::{
+        inline_rulesets, nodes, rule_generator_settings, select_group,
+    };
+    use crate::models::RulesetType;
+
+    const NODES: &str = r#"
+proxies:
+  - { name: vless, type: vless, server: vless.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, network: ws }
+  - { name: xhttp, type: vless, server: vless.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, network: xhttp }
+  - { name: hy2, type: hysteria2, server: hy2.example.com, port: 443, password: pw, up: 50Mbps, down: 200Mbps }
+  - { name: tuic, type: tuic, server: tuic.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000003, password: pw }
+  - { name: wg, type: wireguard, server: wg.example.com, port: 51820, ip: 10.0.0.2, private-key: cHJpdmF0ZQ==, public-key: cHVibGlj }
+  - { name: anytls, type: anytls, server: anytls.example.com, port: 443, password: pw }
+  - { name: ssh, type: ssh, server: ssh.example.com, port: 22, username: root, password: pw }
+"#;
+
+    fn generate(rulesets: &mut Vec<RulesetContent>) -> YamlValue {
+        let output = proxy_to_stash(
+            &mut nodes(NODES),
+            "rules:\n  - DOMAIN,base.example.com,DIRECT\n",
+            rulesets,
+            &vec![select_group()],
+            &mut rule_generator_settings(),
+        );
+        serde_yaml::from_str(&output).unwrap()
+    }
+
+    fn find_proxy<'a>(config: &'a YamlValue, name: &str) -> &'a YamlValue {
+        config["proxies"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"].as_str() == Some(name))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_stash_proxy_types() {
+        let stash = generate(&mut Vec::new());
+        let names: Vec<&str> = stash["proxies"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["vless", "hy2", "tuic", "wg"]);
+
+        let hy2 = find_proxy(&stash, "hy2");
+        assert_eq!(hy2["auth"].as_str(), Some("pw"));
+        assert!(hy2.get("password").is_none());
+        assert_eq!(hy2["up-speed"].as_u64(), Some(50));
+        assert_eq!(hy2["down-speed"].as_u64(), Some(200));
+
+        assert_eq!(find_proxy(&stash, "tuic")["version"].as_u64(), Some(5));
+        assert_eq!(find_proxy(&stash, "wg")["type"].as_str(), Some("wireguard"));
+    }
+
+    #[test]
+    fn test_stash_rules() {
+        let mut provider = RulesetContent::new("https://example.com/rules/reject.yaml", "REJECT");
+        provider.rule_type = RulesetType::ClashDomain;
+        provider.set_rule_content("payload:\n  - '+.ads.example.com'\n");
+        let mut rulesets = vec![provider];
+        rulesets.extend(inline_rulesets(
+            &["SSID,home", "GEOSITE,google", "MATCH"],
+            "Proxy",
+        ));
+
+        let stash = generate(&mut rulesets);
+        let rules: Vec<&str> = stash["rules"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|r| r.as_str().unwrap())
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                "DOMAIN,base.example.com,DIRECT",
+                "RULE-SET,reject,REJECT",
+                "SSID,home,Proxy",
+                "GEOSITE,google,Proxy",
+                "MATCH,Proxy",
+            ]
+        );
+
+        let provider = &stash["rule-providers"]["reject"];
+        assert_eq!(provider["behavior"].as_str(), Some("domain"));
+        assert_eq!(provider["interval"].as_u64(), Some(86400));
+        assert!(provider.get("type").is_none());
+        assert!(provider.get("format").is_none());
+    }
+
+    #[test]
+    fn test_parse_mbps() {
+        assert_eq!(parse_mbps("100"), Some(100));
+        assert_eq!(parse_mbps("100 Mbps"), Some(100));
+        assert_eq!(parse_mbps("1Gbps"), Some(1000));
+        assert_eq!(parse_mbps("10 KBps"), None);
+    }
+}
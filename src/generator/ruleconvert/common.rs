@@ -38,3 +38,45 @@ pub fn transform_rule_to_common(input: &str, group: &str, no_resolve_only: bool)
         result
     }
 }
+
+/// Derives a unique name for a ruleset from the file name of its URL
+///
+/// `is_taken` reports names already in use, a numeric suffix is appended until
+/// the name is free.
+pub fn ruleset_name_from_path(rule_path: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    let file_name = rule_path
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("")
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("");
+    let stem = file_name
+        .rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(file_name);
+    let base: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let base = if base.is_empty() {
+        "ruleset".to_string()
+    } else {
+        base
+    };
+
+    let mut name = base.clone();
+    let mut index = 2;
+    while is_taken(&name) {
+        name = format!("{}_{}", base, index);
+        index += 1;
+    }
+    name
+}
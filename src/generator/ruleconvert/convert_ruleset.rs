@@ -1,11 +1,366 @@
 //! Rule conversion implementation between different proxy configuration formats
 //!
-//! Converts proxy rule formats between Clash, Surge, and Quantumult X
+//! Converts proxy rule formats between Clash, Surge, Quantumult X and sing-box
 
 use crate::models::RulesetType;
 use crate::utils::network::is_ipv4;
 use crate::utils::string::ends_with;
+use log::warn;
 use regex::Regex;
+use serde_json::{Map, Value};
+
+use super::rule_ast::{LogicalOp, RuleNode};
+
+/// Magic bytes starting a compiled sing-box rule-set
+const SING_BOX_BINARY_MAGIC: &str = "SRS";
+
+/// Fields of a sing-box headless rule in the order they are matched, grouped
+/// the way sing-box combines them: fields of one group are OR-ed, groups are
+/// AND-ed
+const SING_BOX_RULE_GROUPS: &[&[&str]] = &[
+    &[
+        "domain",
+        "domain_suffix",
+        "domain_keyword",
+        "domain_regex",
+        "ip_cidr",
+    ],
+    &["source_ip_cidr"],
+    &["port", "port_range"],
+    &["source_port", "source_port_range"],
+    &["network"],
+    &["process_name"],
+    &["process_path"],
+];
+
+/// Maps a sing-box headless rule field value to a Surge-style rule
+///
+/// Port ranges are written `start-end` as mihomo expects, open ranges cannot
+/// be expressed and yield `None`.
+fn sing_box_field_to_rule(field: &str, value: &str) -> Option<(&'static str, String)> {
+    let rule = match field {
+        "domain" => ("DOMAIN", value.to_string()),
+        "domain_suffix" => ("DOMAIN-SUFFIX", value.trim_start_matches('.').to_string()),
+        "domain_keyword" => ("DOMAIN-KEYWORD", value.to_string()),
+        "domain_regex" => ("DOMAIN-REGEX", value.to_string()),
+        "ip_cidr" if value.contains(':') => ("IP-CIDR6", value.to_string()),
+        "ip_cidr" => ("IP-CIDR", value.to_string()),
+        "source_ip_cidr" => ("SRC-IP-CIDR", value.to_string()),
+        "port" => ("DST-PORT", value.to_string()),
+        "source_port" => ("SRC-PORT", value.to_string()),
+        "port_range" | "source_port_range" => {
+            let (start, end) = value.split_once(':')?;
+            if start.is_empty() || end.is_empty() {
+                return None;
+            }
+            let rule_type = if field == "port_range" {
+                "DST-PORT"
+            } else {
+                "SRC-PORT"
+            };
+            (rule_type, format!("{}-{}", start, end))
+        }
+        "network" => ("NETWORK", value.to_uppercase()),
+        "process_name" => ("PROCESS-NAME", value.to_string()),
+        "process_path" => ("PROCESS-PATH", value.to_string()),
+        _ => return None,
+    };
+    Some(rule)
+}
+
+/// Returns the values of a rule field, sing-box accepts a single value in
+/// place of a list
+fn sing_box_field_values(value: &Value) -> Vec<String> {
+    let values = match value {
+        Value::Array(values) => values.iter().collect(),
+        value => vec![value],
+    };
+    values
+        .into_iter()
+        .filter_map(|value| match value {
+            Value::String(value) => Some(value.clone()),
+            Value::Number(value) => Some(value.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Combines rules with a logical operator, a single rule is kept as it is
+fn combine_rule_nodes(op: LogicalOp, mut rules: Vec<RuleNode>) -> Option<RuleNode> {
+    match rules.len() {
+        0 => None,
+        1 => rules.pop(),
+        _ => Some(RuleNode::Logical { op, rules }),
+    }
+}
+
+/// Converts a sing-box headless rule into a rule tree
+///
+/// Rules using a field no other client can express are dropped as a whole,
+/// matching only part of them would widen what they match.
+fn sing_box_rule_to_node(rule: &Map<String, Value>) -> Result<RuleNode, String> {
+    let node = if rule.get("type").and_then(Value::as_str) == Some("logical") {
+        let op = match rule.get("mode").and_then(Value::as_str) {
+            Some("and") => LogicalOp::And,
+            Some("or") => LogicalOp::Or,
+            mode => return Err(format!("unknown logical mode {:?}", mode)),
+        };
+        let rules = rule
+            .get("rules")
+            .and_then(Value::as_array)
+            .map(|rules| {
+                rules
+                    .iter()
+                    .map(|rule| match rule.as_object() {
+                        Some(rule) => sing_box_rule_to_node(rule),
+                        None => Err("sub-rule is not an object".to_string()),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        combine_rule_nodes(op, rules).ok_or("logical rule without sub-rules")?
+    } else {
+        if let Some(field) = rule.keys().find(|field| {
+            *field != "invert"
+                && !SING_BOX_RULE_GROUPS
+                    .iter()
+                    .any(|group| group.contains(&field.as_str()))
+        }) {
+            return Err(format!("unsupported field '{}'", field));
+        }
+
+        let mut groups = Vec::new();
+        for group in SING_BOX_RULE_GROUPS {
+            let mut rules = Vec::new();
+            for field in group.iter() {
+                for value in rule
+                    .get(*field)
+                    .map(sing_box_field_values)
+                    .unwrap_or_default()
+                {
+                    let (rule_type, value) = sing_box_field_to_rule(field, &value)
+                        .ok_or_else(|| format!("unsupported {} '{}'", field, value))?;
+                    rules.push(RuleNode::Plain {
+                        rule_type: rule_type.to_string(),
+                        value,
+                        options: Vec::new(),
+                    });
+                }
+            }
+            groups.extend(combine_rule_nodes(LogicalOp::Or, rules));
+        }
+        combine_rule_nodes(LogicalOp::And, groups).ok_or("empty rule")?
+    };
+
+    if rule.get("invert").and_then(Value::as_bool) == Some(true) {
+        return Ok(RuleNode::Logical {
+            op: LogicalOp::Not,
+            rules: vec![node],
+        });
+    }
+    Ok(node)
+}
+
+/// Converts a domain set, one domain per line, into Surge-style rule lines
+///
+/// A leading `.` matches the domain and all of its subdomains, as in Surge
+/// `DOMAIN-SET` files.
+fn convert_domain_set(content: &str) -> String {
+    let mut output = String::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with(';')
+            || line.starts_with("//")
+        {
+            continue;
+        }
+
+        match line.strip_prefix('.') {
+            Some(domain) => output.push_str(&format!("DOMAIN-SUFFIX,{}\n", domain)),
+            None => output.push_str(&format!("DOMAIN,{}\n", line)),
+        }
+    }
+    output
+}
+
+/// Returns true if `domain` is a plain domain name, without wildcards, paths
+/// or ports
+fn is_plain_domain(domain: &str) -> bool {
+    !domain.is_empty()
+        && domain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+/// Converts an AdBlock/AdGuard filter list into Surge-style rule lines
+///
+/// Only domain rules `||example.com^` can be expressed, they match the domain
+/// and its subdomains. Exceptions (`@@`) cannot unblock anything once turned
+/// into rules and are skipped, as are cosmetic, regex, path and modifier rules;
+/// both are reported in the log.
+fn convert_adblock(content: &str) -> String {
+    let mut output = String::new();
+    let (mut exceptions, mut unsupported) = (0, 0);
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('!') || line.starts_with('[') {
+            continue;
+        }
+        if line.starts_with("@@") {
+            exceptions += 1;
+            continue;
+        }
+
+        let domain = line
+            .strip_prefix("||")
+            .and_then(|rule| rule.strip_suffix('^').or_else(|| rule.strip_suffix("^|")));
+        match domain {
+            Some(domain) if is_plain_domain(domain) => {
+                output.push_str(&format!("DOMAIN-SUFFIX,{}\n", domain.to_lowercase()));
+            }
+            _ => unsupported += 1,
+        }
+    }
+
+    if exceptions > 0 || unsupported > 0 {
+        warn!(
+            "Skipped {} exception and {} unsupported rules of an AdBlock filter list",
+            exceptions, unsupported
+        );
+    }
+    output
+}
+
+/// Host names mapped in every hosts file that must not become rules
+const HOSTS_LOCAL_NAMES: &[&str] = &[
+    "localhost",
+    "localhost.localdomain",
+    "local",
+    "broadcasthost",
+    "ip6-localhost",
+    "ip6-loopback",
+    "ip6-localnet",
+    "ip6-mcastprefix",
+    "ip6-allnodes",
+    "ip6-allrouters",
+    "ip6-allhosts",
+    "0.0.0.0",
+];
+
+/// Converts a hosts file into Surge-style rule lines
+///
+/// Every host name of an `address name [name...]` entry becomes a `DOMAIN`
+/// rule, the address itself is ignored.
+fn convert_hosts(content: &str) -> String {
+    let mut output = String::new();
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("");
+        let mut fields = line.split_whitespace();
+        if fields.next().is_none() {
+            continue;
+        }
+
+        for name in fields {
+            let name = name.to_lowercase();
+            if HOSTS_LOCAL_NAMES.contains(&name.as_str()) || !is_plain_domain(&name) {
+                continue;
+            }
+            output.push_str(&format!("DOMAIN,{}\n", name));
+        }
+    }
+    output
+}
+
+/// Converts a dnsmasq configuration into Surge-style rule lines
+///
+/// The domains of `server=/domain/...`, `address=/domain/...` and
+/// `local=/domain/` entries match themselves and their subdomains, so each
+/// becomes a `DOMAIN-SUFFIX` rule. Other options are ignored.
+fn convert_dnsmasq(content: &str) -> String {
+    let mut output = String::new();
+    for line in content.lines() {
+        let line = line.trim();
+        let Some((option, value)) = line.split_once('=') else {
+            continue;
+        };
+        if !matches!(option.trim(), "server" | "address" | "local") {
+            continue;
+        }
+        let Some(domains) = value.trim().strip_prefix('/') else {
+            continue;
+        };
+
+        // The last field is the upstream server or address, possibly empty
+        let mut domains: Vec<&str> = domains.split('/').collect();
+        domains.pop();
+        for domain in domains {
+            let domain = domain.trim_start_matches('.').to_lowercase();
+            if is_plain_domain(&domain) {
+                output.push_str(&format!("DOMAIN-SUFFIX,{}\n", domain));
+            }
+        }
+    }
+    output
+}
+
+/// Converts a sing-box source rule-set into Surge-style rule lines
+///
+/// A plain `OR` of rules, which is what most rule-sets consist of, is written
+/// as one line per rule, everything else as a single logical rule.
+fn convert_sing_box_source(content: &str) -> String {
+    if content.starts_with(SING_BOX_BINARY_MAGIC) {
+        warn!("Binary sing-box rule-sets are not supported, use the source format instead");
+        return String::new();
+    }
+
+    let source: Value = match serde_json::from_str(content) {
+        Ok(source) => source,
+        Err(e) => {
+            warn!("Failed to parse sing-box rule-set: {}", e);
+            return String::new();
+        }
+    };
+
+    let mut output = String::new();
+    let rules = source
+        .get("rules")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for rule in rules.iter().filter_map(Value::as_object) {
+        let node = match sing_box_rule_to_node(rule) {
+            Ok(node) => node,
+            Err(e) => {
+                warn!(
+                    "Skipping sing-box rule {}: {}",
+                    Value::Object(rule.clone()),
+                    e
+                );
+                continue;
+            }
+        };
+        let lines = match node {
+            RuleNode::Logical {
+                op: LogicalOp::Or,
+                rules,
+            } if rules
+                .iter()
+                .all(|rule| matches!(rule, RuleNode::Plain { .. })) =>
+            {
+                rules
+            }
+            node => vec![node],
+        };
+        for line in lines {
+            output.push_str(&line.to_string());
+            output.push('\n');
+        }
+    }
+    output
+}
 
 /// Converts a ruleset from one format to another
 ///
@@ -23,6 +378,18 @@ pub fn convert_ruleset(content: &str, ruleset_type: RulesetType) -> String {
         return content.to_string();
     }
 
+    if ruleset_type == RulesetType::SingBox {
+        return convert_sing_box_source(content);
+    }
+
+    match ruleset_type {
+        RulesetType::DomainSet => return convert_domain_set(content),
+        RulesetType::AdBlock => return convert_adblock(content),
+        RulesetType::Hosts => return convert_hosts(content),
+        RulesetType::Dnsmasq => return convert_dnsmasq(content),
+        _ => {}
+    }
+
     let mut output = String::new();
     let payload_regex = Regex::new(r"^payload:\r?\n").unwrap();
 
@@ -166,3 +533,85 @@ pub fn convert_ruleset(content: &str, ruleset_type: RulesetType) -> String {
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_sing_box_source() {
+        let source = r#"{
+            "version": 2,
+            "rules": [
+                {
+                    "domain": ["example.com"],
+                    "domain_suffix": [".example.org", "example.net"],
+                    "ip_cidr": ["1.1.1.0/24", "2001:db8::/32"]
+                },
+                { "domain_keyword": "ads", "port": [80, 443] },
+                { "network": "udp", "port_range": ["1000:2000"], "invert": true },
+                {
+                    "type": "logical",
+                    "mode": "or",
+                    "rules": [{ "process_name": "curl" }, { "source_port": 53 }]
+                },
+                { "wifi_ssid": ["home"] }
+            ]
+        }"#;
+
+        assert_eq!(
+            convert_ruleset(source, RulesetType::SingBox),
+            "DOMAIN,example.com\n\
+             DOMAIN-SUFFIX,example.org\n\
+             DOMAIN-SUFFIX,example.net\n\
+             IP-CIDR,1.1.1.0/24\n\
+             IP-CIDR6,2001:db8::/32\n\
+             AND,((DOMAIN-KEYWORD,ads),(OR,((DST-PORT,80),(DST-PORT,443))))\n\
+             NOT,((AND,((DST-PORT,1000-2000),(NETWORK,UDP))))\n\
+             PROCESS-NAME,curl\n\
+             SRC-PORT,53\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_domain_set() {
+        let domain_set = "# Ads\n.ads.example.com\ntracker.example.org\r\n\n; comment\n";
+        assert_eq!(
+            convert_ruleset(domain_set, RulesetType::DomainSet),
+            "DOMAIN-SUFFIX,ads.example.com\nDOMAIN,tracker.example.org\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_adblock() {
+        let filter = "[Adblock Plus 2.0]\n! Title: Example\n||ads.example.com^\n||Tracker.Example.org^|\n@@||cdn.example.com^\n||example.net^$third-party\nexample.com##.banner\n/banner\\d+/\n||*.example.io^\n";
+        assert_eq!(
+            convert_ruleset(filter, RulesetType::AdBlock),
+            "DOMAIN-SUFFIX,ads.example.com\nDOMAIN-SUFFIX,tracker.example.org\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_hosts() {
+        let hosts = "# hosts\n127.0.0.1 localhost\n::1 ip6-localhost ip6-loopback\n0.0.0.0 0.0.0.0\n0.0.0.0 ads.example.com tracker.example.org # trackers\n0.0.0.0\n";
+        assert_eq!(
+            convert_ruleset(hosts, RulesetType::Hosts),
+            "DOMAIN,ads.example.com\nDOMAIN,tracker.example.org\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_dnsmasq() {
+        let dnsmasq = "# China domains\nserver=/example.cn/114.114.114.114\naddress=/ads.example.com/.ads.example.org/0.0.0.0\nlocal=/lan.example.net/\nserver=/#/8.8.8.8\ncache-size=1000\n";
+        assert_eq!(
+            convert_ruleset(dnsmasq, RulesetType::Dnsmasq),
+            "DOMAIN-SUFFIX,example.cn\nDOMAIN-SUFFIX,ads.example.com\nDOMAIN-SUFFIX,ads.example.org\nDOMAIN-SUFFIX,lan.example.net\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_sing_box_binary() {
+        assert_eq!(convert_ruleset("SRS\u{1}x\u{9c}", RulesetType::SingBox), "");
+        assert_eq!(convert_ruleset("not json", RulesetType::SingBox), "");
+    }
+}
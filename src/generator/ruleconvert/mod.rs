@@ -5,14 +5,17 @@
 
 pub mod common;
 pub mod convert_ruleset;
+pub mod rule_ast;
 // Keep the ruleset module for now but don't use its RulesetType
 mod ruleset;
 // mod ruleset_to_clash; // @deprecated
 pub mod ruleset_to_clash_str;
 pub mod ruleset_to_sing_box;
 pub mod ruleset_to_surge;
+pub mod ruleset_to_xray;
 
 pub use convert_ruleset::convert_ruleset;
 pub use ruleset_to_clash_str::ruleset_to_clash_str;
 pub use ruleset_to_sing_box::ruleset_to_sing_box;
 pub use ruleset_to_surge::ruleset_to_surge;
+pub use ruleset_to_xray::ruleset_to_xray;
@@ -0,0 +1,280 @@
+//! Rule syntax tree
+//!
+//! Parses Surge-style rules, including nested logical `AND`/`OR`/`NOT` rules
+//! such as `AND,((DOMAIN-SUFFIX,example.com),(NOT,((DST-PORT,443))))`, so they
+//! can be checked against a target and written out without being split apart.
+
+use log::warn;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Operator of a logical rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicalOp {
+    And,
+    Or,
+    Not,
+}
+
+impl LogicalOp {
+    fn from_rule_type(rule_type: &str) -> Option<Self> {
+        match rule_type.to_uppercase().as_str() {
+            "AND" => Some(LogicalOp::And),
+            "OR" => Some(LogicalOp::Or),
+            "NOT" => Some(LogicalOp::Not),
+            _ => None,
+        }
+    }
+
+    /// Rule type of the operator, e.g. `AND`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogicalOp::And => "AND",
+            LogicalOp::Or => "OR",
+            LogicalOp::Not => "NOT",
+        }
+    }
+}
+
+/// A parsed rule without its policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleNode {
+    /// A single condition such as `IP-CIDR,10.0.0.0/8,no-resolve`
+    Plain {
+        rule_type: String,
+        value: String,
+        options: Vec<String>,
+    },
+    /// A logical combination of sub-rules
+    Logical { op: LogicalOp, rules: Vec<RuleNode> },
+}
+
+/// Returns true if the rule is a logical `AND`/`OR`/`NOT` rule
+pub fn is_logical_rule(rule: &str) -> bool {
+    let rule_type = rule.split(',').next().unwrap_or("").trim();
+    LogicalOp::from_rule_type(rule_type).is_some()
+}
+
+/// Returns the index of the parenthesis closing the one at `open`
+fn matching_paren(input: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in input[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+impl RuleNode {
+    /// Parses a rule, the policy must already be stripped off
+    pub fn parse(rule: &str) -> Result<RuleNode, String> {
+        let rule = rule.trim();
+        let (rule_type, rest) = match rule.split_once(',') {
+            Some((rule_type, rest)) => (rule_type.trim(), rest.trim()),
+            None => (rule, ""),
+        };
+        if rule_type.is_empty() {
+            return Err(format!("missing rule type in '{}'", rule));
+        }
+
+        let Some(op) = LogicalOp::from_rule_type(rule_type) else {
+            let mut fields = rest.split(',').map(|field| field.trim().to_string());
+            return Ok(RuleNode::Plain {
+                rule_type: rule_type.to_uppercase(),
+                value: fields.next().unwrap_or_default(),
+                options: fields.filter(|field| !field.is_empty()).collect(),
+            });
+        };
+
+        // The sub-rules are wrapped in one pair of parentheses as a whole and
+        // one pair each: ((RULE-A),(RULE-B))
+        if !rest.starts_with('(') || matching_paren(rest, 0) != Some(rest.len() - 1) {
+            return Err(format!("unbalanced parentheses in '{}'", rule));
+        }
+        let inner = &rest[1..rest.len() - 1];
+
+        let mut rules = Vec::new();
+        let mut pos = 0;
+        while pos < inner.len() {
+            let tail = &inner[pos..];
+            let skipped = tail.len() - tail.trim_start_matches([' ', ',']).len();
+            pos += skipped;
+            if pos >= inner.len() {
+                break;
+            }
+            if !inner[pos..].starts_with('(') {
+                return Err(format!("expected '(' in '{}'", rule));
+            }
+            let close = matching_paren(inner, pos)
+                .ok_or_else(|| format!("unbalanced parentheses in '{}'", rule))?;
+            rules.push(RuleNode::parse(&inner[pos + 1..close])?);
+            pos = close + 1;
+        }
+
+        match (op, rules.len()) {
+            (_, 0) => Err(format!("no sub-rules in '{}'", rule)),
+            (LogicalOp::Not, n) if n > 1 => {
+                Err(format!("NOT takes a single sub-rule in '{}'", rule))
+            }
+            _ => Ok(RuleNode::Logical { op, rules }),
+        }
+    }
+
+    /// Returns the first rule type, logical operators included, that is not
+    /// in `rule_types`
+    pub fn unsupported_type<'a>(&'a self, rule_types: &HashSet<&str>) -> Option<&'a str> {
+        match self {
+            RuleNode::Plain { rule_type, .. } => {
+                (!rule_types.contains(rule_type.as_str())).then_some(rule_type.as_str())
+            }
+            RuleNode::Logical { op, rules } => {
+                if !rule_types.contains(op.as_str()) {
+                    return Some(op.as_str());
+                }
+                rules
+                    .iter()
+                    .find_map(|rule| rule.unsupported_type(rule_types))
+            }
+        }
+    }
+}
+
+/// Writes the rule in the syntax Surge, Loon and mihomo share
+impl fmt::Display for RuleNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleNode::Plain {
+                rule_type,
+                value,
+                options,
+            } => {
+                write!(f, "{},{}", rule_type, value)?;
+                for option in options {
+                    write!(f, ",{}", option)?;
+                }
+                Ok(())
+            }
+            RuleNode::Logical { op, rules } => {
+                write!(f, "{},(", op.as_str())?;
+                for (i, rule) in rules.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "({})", rule)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Parses a logical rule and checks every rule type in it against the
+/// `rule_types` of a target
+///
+/// Returns the normalised rule without a policy, or logs a warning and returns
+/// `None` when the rule is malformed or cannot be expressed for `target`.
+pub fn convert_logical_rule(
+    rule: &str,
+    rule_types: &HashSet<&str>,
+    target: &str,
+) -> Option<RuleNode> {
+    match RuleNode::parse(rule) {
+        Ok(node) => match node.unsupported_type(rule_types) {
+            Some(rule_type) => {
+                warn!(
+                    "Skipping rule '{}': {} is not supported by {}",
+                    rule, rule_type, target
+                );
+                None
+            }
+            None => Some(node),
+        },
+        Err(e) => {
+            warn!("Skipping malformed rule '{}': {}", rule, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(rule_type: &str, value: &str) -> RuleNode {
+        RuleNode::Plain {
+            rule_type: rule_type.to_string(),
+            value: value.to_string(),
+            options: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_nested_logical_rule() {
+        let rule = "AND,((DOMAIN-SUFFIX,example.com), (OR,((DST-PORT,443),(NOT,((NETWORK,UDP))))))";
+        let node = RuleNode::parse(rule).unwrap();
+
+        assert_eq!(
+            node,
+            RuleNode::Logical {
+                op: LogicalOp::And,
+                rules: vec![
+                    plain("DOMAIN-SUFFIX", "example.com"),
+                    RuleNode::Logical {
+                        op: LogicalOp::Or,
+                        rules: vec![
+                            plain("DST-PORT", "443"),
+                            RuleNode::Logical {
+                                op: LogicalOp::Not,
+                                rules: vec![plain("NETWORK", "UDP")],
+                            },
+                        ],
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            node.to_string(),
+            "AND,((DOMAIN-SUFFIX,example.com),(OR,((DST-PORT,443),(NOT,((NETWORK,UDP))))))"
+        );
+    }
+
+    #[test]
+    fn test_parse_keeps_parentheses_in_values() {
+        let rule =
+            "OR,((URL-REGEX,^https?://(www\\.)?example\\.com),(IP-CIDR,10.0.0.0/8,no-resolve))";
+        let node = RuleNode::parse(rule).unwrap();
+        assert_eq!(node.to_string(), rule);
+    }
+
+    #[test]
+    fn test_parse_malformed_rules() {
+        assert!(RuleNode::parse("AND,((DOMAIN,example.com)").is_err());
+        assert!(RuleNode::parse("AND,()").is_err());
+        assert!(RuleNode::parse("NOT,((DOMAIN,a.com),(DOMAIN,b.com))").is_err());
+        assert!(RuleNode::parse("OR,(DOMAIN,a.com)").is_err());
+    }
+
+    #[test]
+    fn test_convert_logical_rule() {
+        let types: HashSet<&str> = ["AND", "NOT", "DOMAIN", "DST-PORT"].into_iter().collect();
+
+        assert!(
+            convert_logical_rule("AND,((DOMAIN,a.com),(NOT,((DST-PORT,80))))", &types, "test")
+                .is_some()
+        );
+        assert!(
+            convert_logical_rule("AND,((DOMAIN,a.com),(NETWORK,UDP))", &types, "test").is_none()
+        );
+        assert!(
+            convert_logical_rule("OR,((DOMAIN,a.com),(DOMAIN,b.com))", &types, "test").is_none()
+        );
+    }
+}
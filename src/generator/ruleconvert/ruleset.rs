@@ -107,6 +107,10 @@ lazy_static::lazy_static! {
         set.insert("SRC-PORT-RANGE");
         set.insert("USER");
         set.insert("USER-ID");
+        // Logical rules
+        set.insert("AND");
+        set.insert("OR");
+        set.insert("NOT");
         set
     };
 }
@@ -2,16 +2,19 @@
 //!
 //! This module provides functionality to convert rulesets to Clash YAML string format.
 
-use crate::models::RulesetContent;
+use crate::models::{RulesetContent, RulesetType, SubconverterTarget};
+use crate::utils::base64::url_safe_base64_encode;
+use crate::utils::network::is_link;
 use crate::utils::string::{find_str, starts_with, trim};
 use crate::Settings;
 use lazy_static::lazy_static;
 use log::warn;
-use serde_yaml::Value as YamlValue;
+use serde_yaml::{Mapping, Value as YamlValue};
 use std::collections::HashSet;
 
-use super::common::transform_rule_to_common;
+use super::common::{ruleset_name_from_path, transform_rule_to_common};
 use super::convert_ruleset::convert_ruleset;
+use super::rule_ast::{convert_logical_rule, is_logical_rule};
 
 lazy_static! {
     static ref CLASH_RULE_TYPES: HashSet<&'static str> = {
@@ -30,28 +33,272 @@ lazy_static! {
         types.insert("SRC-PORT");
         types.insert("DST-PORT");
         types.insert("PROCESS-NAME");
+        types.insert("RULE-SET");
         types
     };
+
+    static ref MIHOMO_RULE_TYPES: HashSet<&'static str> = {
+        let mut types = CLASH_RULE_TYPES.clone();
+        types.insert("DOMAIN-REGEX");
+        types.insert("DOMAIN-WILDCARD");
+        types.insert("GEOSITE");
+        types.insert("IP-SUFFIX");
+        types.insert("IP-ASN");
+        types.insert("SRC-GEOIP");
+        types.insert("SRC-IP-ASN");
+        types.insert("SRC-IP-SUFFIX");
+        types.insert("IN-PORT");
+        types.insert("IN-TYPE");
+        types.insert("IN-USER");
+        types.insert("IN-NAME");
+        types.insert("PROCESS-PATH");
+        types.insert("PROCESS-NAME-REGEX");
+        types.insert("PROCESS-PATH-REGEX");
+        types.insert("NETWORK");
+        types.insert("UID");
+        types.insert("DSCP");
+        // Logical rules
+        types.insert("AND");
+        types.insert("OR");
+        types.insert("NOT");
+        types
+    };
+
+    static ref STASH_RULE_TYPES: HashSet<&'static str> = {
+        let mut types = CLASH_RULE_TYPES.clone();
+        types.insert("GEOSITE");
+        types.insert("IP-ASN");
+        types.insert("PROCESS-PATH");
+        types.insert("SCRIPT");
+        types.insert("SSID");
+        types.insert("DEVICE-NAME");
+        types.insert("CELLULAR-CARRIER");
+        types.insert("CELLULAR-RADIO");
+        // Logical rules
+        types.insert("AND");
+        types.insert("OR");
+        types.insert("NOT");
+        types
+    };
+}
+
+/// Rule types a Clash flavour understands
+fn clash_rule_types(target: &SubconverterTarget) -> &'static HashSet<&'static str> {
+    match target {
+        SubconverterTarget::Mihomo => &MIHOMO_RULE_TYPES,
+        SubconverterTarget::Stash => &STASH_RULE_TYPES,
+        _ => &CLASH_RULE_TYPES,
+    }
+}
+
+/// Returns the rule type of a rule line, e.g. `DOMAIN-SUFFIX` for
+/// `DOMAIN-SUFFIX,google.com`
+fn rule_type_of(rule: &str) -> &str {
+    rule.split(',').next().unwrap_or("").trim()
+}
+
+/// Appends the target group to a rule the target supports
+///
+/// Logical rules are parsed as a whole, so every nested rule type is checked
+/// and a rule the target cannot express is reported instead of being emitted
+/// half-converted. Other unsupported rules yield `None` silently, the caller
+/// decides whether to warn.
+fn transform_clash_rule(
+    rule: &str,
+    group: &str,
+    rule_types: &HashSet<&str>,
+    target: &SubconverterTarget,
+) -> Option<String> {
+    if is_logical_rule(rule) {
+        return convert_logical_rule(rule, rule_types, &target.to_str())
+            .map(|node| format!("{},{}", node, group));
+    }
+    if !rule_types.contains(rule_type_of(rule)) {
+        return None;
+    }
+    Some(transform_rule_to_common(rule, group, false))
+}
+
+/// `/getruleset` types serving Clash rule provider payloads, by behavior
+const CLASH_DOMAIN_RULESET_TYPE: u32 = 3;
+const CLASH_IPCIDR_RULESET_TYPE: u32 = 4;
+const CLASH_CLASSICAL_RULESET_TYPE: u32 = 6;
+
+/// Builds a rule provider so a ruleset can be referenced instead of being
+/// expanded inline
+///
+/// Remote rulesets already in Clash payload format are linked directly, by
+/// default only in mihomo and Stash. With a `remote_path_prefix`, every other
+/// ruleset is served converted by `/getruleset`, its behavior inferred from the
+/// ruleset type, and carry the target so the payload keeps its rule types.
+/// Stash infers the provider type and format, so only mihomo and Clash get them.
+fn clash_rule_provider(
+    ruleset: &RulesetContent,
+    target: &SubconverterTarget,
+    remote_path_prefix: &str,
+) -> Option<Mapping> {
+    let (behavior, ruleset_type) = match ruleset.rule_type {
+        RulesetType::ClashDomain => ("domain", CLASH_DOMAIN_RULESET_TYPE),
+        rule_type if rule_type.is_domain_list() => ("domain", CLASH_DOMAIN_RULESET_TYPE),
+        RulesetType::ClashIpcidr => ("ipcidr", CLASH_IPCIDR_RULESET_TYPE),
+        _ => ("classical", CLASH_CLASSICAL_RULESET_TYPE),
+    };
+    let is_clash_payload = matches!(
+        ruleset.rule_type,
+        RulesetType::ClashDomain | RulesetType::ClashIpcidr | RulesetType::ClashClassical
+    );
+
+    let native_providers = matches!(
+        target,
+        SubconverterTarget::Mihomo | SubconverterTarget::Stash
+    );
+
+    let url = if is_clash_payload
+        && is_link(&ruleset.rule_path)
+        && (native_providers || !remote_path_prefix.is_empty())
+    {
+        ruleset.rule_path.clone()
+    } else if !remote_path_prefix.is_empty()
+        && !ruleset.rule_path.is_empty()
+        && !ruleset.get_rule_content().is_empty()
+    {
+        let mut url = format!(
+            "{}/getruleset?type={}&url={}",
+            remote_path_prefix,
+            ruleset_type,
+            url_safe_base64_encode(&ruleset.rule_path_typed)
+        );
+        if native_providers {
+            url.push_str(&format!("&target={}", target.to_str()));
+        }
+        url
+    } else {
+        return None;
+    };
+
+    let typed = *target != SubconverterTarget::Stash;
+    let mut provider = Mapping::new();
+    if typed {
+        provider.insert("type".into(), "http".into());
+    }
+    provider.insert("behavior".into(), behavior.into());
+    if *target == SubconverterTarget::Mihomo {
+        provider.insert("format".into(), "yaml".into());
+    }
+    provider.insert("url".into(), url.into());
+    let interval = if ruleset.update_interval > 0 {
+        ruleset.update_interval
+    } else {
+        86400
+    };
+    provider.insert("interval".into(), interval.into());
+    Some(provider)
+}
+
+/// Converts a single ruleset into the Clash rule provider payload served by
+/// `/getruleset` for the given `ruleset_type` (3 domain, 4 ipcidr, 6 classical)
+///
+/// Rules the provider behavior or the `target` flavour cannot express are
+/// dropped.
+pub fn ruleset_to_clash_payload(
+    content: &str,
+    rule_type: RulesetType,
+    ruleset_type: u32,
+    target: &SubconverterTarget,
+) -> Option<String> {
+    if !matches!(
+        ruleset_type,
+        CLASH_DOMAIN_RULESET_TYPE | CLASH_IPCIDR_RULESET_TYPE | CLASH_CLASSICAL_RULESET_TYPE
+    ) {
+        return None;
+    }
+
+    let rule_types = clash_rule_types(target);
+    let mut payload = String::from("payload:\n");
+    for line in convert_ruleset(content, rule_type).lines() {
+        let mut str_line = trim(line).to_string();
+
+        // Skip empty lines and comments
+        if str_line.is_empty()
+            || str_line.starts_with(';')
+            || str_line.starts_with('#')
+            || str_line.starts_with("//")
+        {
+            continue;
+        }
+
+        // Remove inline comments
+        if let Some(comment_pos) = find_str(&str_line, "//") {
+            str_line = trim(&str_line[..comment_pos]).to_string();
+        }
+
+        let mut parts = str_line.splitn(3, ',').map(str::trim);
+        let (rule, value) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        let item = match (ruleset_type, rule) {
+            (CLASH_DOMAIN_RULESET_TYPE, "DOMAIN") => value.to_string(),
+            (CLASH_DOMAIN_RULESET_TYPE, "DOMAIN-SUFFIX") => format!("+.{}", value),
+            (CLASH_IPCIDR_RULESET_TYPE, "IP-CIDR" | "IP-CIDR6") => value.to_string(),
+            (CLASH_CLASSICAL_RULESET_TYPE, _) if is_logical_rule(&str_line) => {
+                match convert_logical_rule(&str_line, rule_types, &target.to_str()) {
+                    Some(node) => node.to_string(),
+                    None => continue,
+                }
+            }
+            (CLASH_CLASSICAL_RULESET_TYPE, _)
+                if rule_types.contains(rule) && !matches!(rule, "MATCH" | "FINAL" | "RULE-SET") =>
+            {
+                str_line.clone()
+            }
+            _ => continue,
+        };
+        if value.is_empty() {
+            continue;
+        }
+
+        payload.push_str(&format!("  - '{}'\n", item.replace('\'', "''")));
+    }
+
+    Some(payload)
 }
 
 /// Converts rulesets to Clash YAML string format
 ///
+/// Rule types the target cannot express are dropped. For mihomo and Stash, remote
+/// rulesets already in Clash payload format are emitted as `rule-providers`
+/// in `base_rule` and referenced with `RULE-SET` rules. With a
+/// `remote_path_prefix`, every other ruleset becomes a rule provider served by
+/// `/getruleset` as well.
+///
 /// # Arguments
 ///
-/// * `base_rule` - YAML node containing base rules
+/// * `base_rule` - YAML node containing base rules, its rules are moved into the output
 /// * `ruleset_content_array` - Array of ruleset content
 /// * `overwrite_original_rules` - Whether to overwrite original rules
 /// * `new_field_name` - Whether to use "rules" or "Rule" as the field name
+/// * `target` - Clash flavour the rules are generated for
+/// * `remote_path_prefix` - Prefix of the `/getruleset` links rule providers
+///   are served from, empty to expand rulesets inline
 ///
 /// # Returns
 ///
 /// YAML string containing the converted rules
 pub fn ruleset_to_clash_str(
-    base_rule: &YamlValue,
+    base_rule: &mut YamlValue,
     ruleset_content_array: &[RulesetContent],
     overwrite_original_rules: bool,
     new_field_name: bool,
+    target: &SubconverterTarget,
+    remote_path_prefix: &str,
 ) -> String {
+    let rule_types = clash_rule_types(target);
+    // Start from the providers of the base so generated names do not clash
+    let mut rule_providers = base_rule
+        .get("rule-providers")
+        .and_then(|providers| providers.as_mapping())
+        .cloned()
+        .unwrap_or_default();
+    let base_provider_count = rule_providers.len();
+
     // Get global settings
     let settings = Settings::current();
 
@@ -60,14 +307,19 @@ pub fn ruleset_to_clash_str(
     let mut output_content = format!("\n{}:\n", field_name);
     let mut total_rules = 0;
 
-    // Include existing rules if not overwriting
+    // Include existing rules if not overwriting, the field itself is taken out
+    // of the base so it is not emitted twice
+    let original_rules = base_rule
+        .as_mapping_mut()
+        .and_then(|map| map.remove(field_name));
     if !overwrite_original_rules {
-        if let Some(rules) = base_rule.get(field_name) {
-            if let Some(rules_array) = rules.as_sequence() {
-                for rule in rules_array {
-                    if let Some(rule_str) = rule.as_str() {
-                        output_content.push_str(&format!("  - {}\n", rule_str));
-                    }
+        if let Some(rules_array) = original_rules
+            .as_ref()
+            .and_then(|rules| rules.as_sequence())
+        {
+            for rule in rules_array {
+                if let Some(rule_str) = rule.as_str() {
+                    output_content.push_str(&format!("  - {}\n", rule_str));
                 }
             }
         }
@@ -86,6 +338,27 @@ pub fn ruleset_to_clash_str(
         // Get group name from ruleset
         let rule_group = &ruleset.group;
 
+        // Reference the ruleset as a rule provider where possible
+        if let Some(mut provider) = clash_rule_provider(ruleset, target, remote_path_prefix) {
+            let name = ruleset_name_from_path(&ruleset.rule_path, |name| {
+                rule_providers.contains_key(name)
+            });
+            // Clash stores fetched providers in a file of its own
+            if matches!(
+                target,
+                SubconverterTarget::Clash | SubconverterTarget::ClashR
+            ) {
+                provider.insert(
+                    "path".into(),
+                    format!("./providers/rule-provider_{}.yaml", name).into(),
+                );
+            }
+            output_content.push_str(&format!("  - RULE-SET,{},{}\n", name, rule_group));
+            rule_providers.insert(name.into(), YamlValue::Mapping(provider));
+            total_rules += 1;
+            continue;
+        }
+
         // Get rule content from the shared future-like container
         let retrieved_rules = ruleset.get_rule_content();
 
@@ -108,9 +381,18 @@ pub fn ruleset_to_clash_str(
             }
 
             // Transform rule to common format
-            let transformed = transform_rule_to_common(&rule_line, rule_group, false);
-            output_content.push_str(&format!("  - {}\n", transformed));
-            total_rules += 1;
+            match transform_clash_rule(&rule_line, rule_group, rule_types, target) {
+                Some(transformed) => {
+                    output_content.push_str(&format!("  - {}\n", transformed));
+                    total_rules += 1;
+                }
+                None if !is_logical_rule(&rule_line) => warn!(
+                    "Skipping rule '{}': not supported by {}",
+                    rule_line,
+                    target.to_str()
+                ),
+                None => {}
+            }
             continue;
         }
 
@@ -143,26 +425,28 @@ pub fn ruleset_to_clash_str(
                 continue;
             }
 
-            // Check if the rule type is supported by Clash
-            if !CLASH_RULE_TYPES
-                .iter()
-                .any(|&rule_type| starts_with(&str_line, rule_type))
-            {
-                continue;
-            }
-
             // Remove inline comments
             if let Some(comment_pos) = find_str(&str_line, "//") {
                 str_line = str_line[..comment_pos].to_string();
                 str_line = trim(&str_line).to_string();
             }
 
-            // Transform rule to common format and add to output
-            let transformed = transform_rule_to_common(&str_line, rule_group, false);
+            // Transform rule to common format and add to output, skipping rule
+            // types the target does not support
+            let Some(transformed) = transform_clash_rule(&str_line, rule_group, rule_types, target)
+            else {
+                continue;
+            };
             output_content.push_str(&format!("  - {}\n", transformed));
             total_rules += 1;
         }
     }
 
+    if rule_providers.len() > base_provider_count {
+        if let Some(map) = base_rule.as_mapping_mut() {
+            map.insert("rule-providers".into(), YamlValue::Mapping(rule_providers));
+        }
+    }
+
     output_content
 }
@@ -2,16 +2,41 @@
 //!
 //! This module provides functionality to convert rulesets to Sing-Box format.
 
-use crate::models::RulesetContent;
+use crate::models::{RulesetContent, RulesetType};
+use crate::utils::base64::url_safe_base64_encode;
+use crate::utils::network::is_link;
 use crate::utils::string::{find_str, starts_with, to_lower};
 use crate::utils::trim;
 use crate::Settings;
 use log::warn;
 use serde_json::{json, Map, Value};
 
+use super::common::ruleset_name_from_path;
 use super::convert_ruleset::convert_ruleset;
+use super::rule_ast::{convert_logical_rule, is_logical_rule, LogicalOp, RuleNode};
 use super::ruleset::SINGBOX_RULE_TYPES;
 
+/// `/getruleset` type serving a ruleset as a sing-box source rule-set
+pub const SINGBOX_RULESET_TYPE: u32 = 7;
+
+/// Rule fields sing-box accepts in the headless rules of a rule-set
+const HEADLESS_RULE_FIELDS: &[&str] = &[
+    "network",
+    "domain",
+    "domain_suffix",
+    "domain_keyword",
+    "domain_regex",
+    "source_ip_cidr",
+    "ip_cidr",
+    "source_port",
+    "source_port_range",
+    "port",
+    "port_range",
+    "process_name",
+    "process_path",
+    "package_name",
+];
+
 /// Converts rulesets to Sing-Box format and updates the JSON configuration
 ///
 /// # Arguments
@@ -19,10 +44,17 @@ use super::ruleset::SINGBOX_RULE_TYPES;
 /// * `base_rule` - The base Sing-Box configuration as JSON
 /// * `ruleset_content_array` - Array of ruleset contents to process
 /// * `overwrite_original_rules` - Whether to overwrite original rules
+/// * `rule_actions` - Whether to emit rule actions (sing-box 1.11+) instead of
+///   routing to the legacy `dns-out` and `block` outbounds
+/// * `remote_path_prefix` - Prefix of the `/getruleset` endpoint, when set
+///   fetched rulesets are referenced as remote `route.rule_set` entries
+///   instead of being inlined
 pub fn ruleset_to_sing_box(
     base_rule: &mut Value,
     ruleset_content_array: &[RulesetContent],
     overwrite_original_rules: bool,
+    rule_actions: bool,
+    remote_path_prefix: &str,
 ) {
     // Get global settings
     let settings = Settings::current();
@@ -41,6 +73,15 @@ pub fn ruleset_to_sing_box(
         }
     }
 
+    if rule_actions {
+        // Sniffing moved from inbound fields to a route rule action
+        strip_inbound_sniff(base_rule);
+
+        if let Some(rules_array) = rules.as_array_mut() {
+            rules_to_actions(rules_array);
+        }
+    }
+
     // Add Clash modes if enabled
     if settings.singbox_add_clash_modes {
         let global_object = json!({
@@ -60,10 +101,17 @@ pub fn ruleset_to_sing_box(
     }
 
     // Add DNS rule
-    let dns_object = json!({
-        "protocol": "dns",
-        "outbound": "dns-out"
-    });
+    let dns_object = if rule_actions {
+        json!({
+            "protocol": "dns",
+            "action": "hijack-dns"
+        })
+    } else {
+        json!({
+            "protocol": "dns",
+            "outbound": "dns-out"
+        })
+    };
 
     if let Some(rules_array) = rules.as_array_mut() {
         rules_array.push(dns_object);
@@ -73,6 +121,15 @@ pub fn ruleset_to_sing_box(
     let mut total_rules = 0;
     let mut final_rule = String::new();
 
+    // Start from the rule-sets of the base so generated tags stay unique
+    let mut rule_sets = base_rule
+        .get("route")
+        .and_then(|route| route.get("rule_set"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let base_rule_set_count = rule_sets.len();
+
     for ruleset in ruleset_content_array {
         // Check if we've reached the maximum number of rules
         if settings.max_allowed_rules > 0 && total_rules >= settings.max_allowed_rules {
@@ -99,6 +156,17 @@ pub fn ruleset_to_sing_box(
                 continue;
             }
 
+            if is_logical_rule(str_line) {
+                if let Some(mut rule_obj) = sing_box_logical_rule(str_line) {
+                    insert_rule_target(&mut rule_obj, rule_group, rule_actions);
+                    if let Some(rules_array) = rules.as_array_mut() {
+                        rules_array.push(Value::Object(rule_obj));
+                        total_rules += 1;
+                    }
+                }
+                continue;
+            }
+
             // Transform rule to SingBox format
             let parts: Vec<&str> = str_line.split(',').collect();
             if parts.len() < 2 {
@@ -111,19 +179,13 @@ pub fn ruleset_to_sing_box(
             let mut rule_obj = Map::new();
 
             // Convert type names from Clash format to SingBox format
-            let rule_type = rule_type
-                .replace("-", "_")
-                .replace("ip_cidr6", "ip_cidr")
-                .replace("src_", "source_");
+            let rule_type = sing_box_rule_field(&rule_type);
 
             if rule_type == "match" || rule_type == "final" {
                 rule_obj.insert("outbound".to_string(), Value::String(rule_value));
             } else {
                 rule_obj.insert(rule_type, Value::String(rule_value));
-                rule_obj.insert(
-                    "outbound".to_string(),
-                    Value::String(rule_group.to_string()),
-                );
+                insert_rule_target(&mut rule_obj, rule_group, rule_actions);
             }
 
             if let Some(rules_array) = rules.as_array_mut() {
@@ -134,87 +196,572 @@ pub fn ruleset_to_sing_box(
             continue;
         }
 
-        // Convert ruleset based on its type
-        let converted_rules = convert_ruleset(&retrieved_rules, ruleset.rule_type);
+        // Reference the ruleset as a remote rule-set instead of inlining it
+        if !remote_path_prefix.is_empty() {
+            if let Some(rule_set) = sing_box_rule_set(ruleset, remote_path_prefix, &rule_sets) {
+                let mut rule_obj = Map::new();
+                rule_obj.insert("rule_set".to_string(), rule_set["tag"].clone());
+                insert_rule_target(&mut rule_obj, rule_group, rule_actions);
 
-        // Create a new rule object for this ruleset
-        let mut rule_obj = Map::new();
-
-        // Process each rule line
-        for line in converted_rules.lines() {
-            if settings.max_allowed_rules > 0 && total_rules >= settings.max_allowed_rules {
-                break;
+                if let Some(rules_array) = rules.as_array_mut() {
+                    rules_array.push(Value::Object(rule_obj));
+                    total_rules += 1;
+                }
+                rule_sets.push(rule_set);
+
+                // Rules a converted source rule-set cannot hold stay inline
+                if !is_direct_rule_set(ruleset) {
+                    let (rule_obj, logical_rules) = collect_sing_box_rule(
+                        &retrieved_rules,
+                        ruleset.rule_type,
+                        &mut total_rules,
+                        settings.max_allowed_rules,
+                    );
+                    let (_, inline_rules) = partition_headless(rule_obj, logical_rules);
+                    for mut rule_obj in inline_rules {
+                        insert_rule_target(&mut rule_obj, rule_group, rule_actions);
+
+                        if let Some(rules_array) = rules.as_array_mut() {
+                            rules_array.push(Value::Object(rule_obj));
+                        }
+                    }
+                }
+                continue;
             }
+        }
 
-            let mut str_line = trim(line).to_string();
-            let line_size = str_line.len();
+        // Convert ruleset based on its type and merge it into one rule object,
+        // logical rules stay separate rules of their own
+        let (rule_obj, logical_rules) = collect_sing_box_rule(
+            &retrieved_rules,
+            ruleset.rule_type,
+            &mut total_rules,
+            settings.max_allowed_rules,
+        );
 
-            // Skip empty lines and comments
-            if line_size == 0
-                || (line_size >= 1 && (str_line.starts_with(';') || str_line.starts_with('#')))
-                || (line_size >= 2 && str_line.starts_with("//"))
-            {
-                continue;
-            }
+        // Only add if rule object is not empty
+        let rule_objs = Some(rule_obj)
+            .filter(|rule_obj| !rule_obj.is_empty())
+            .into_iter()
+            .chain(logical_rules);
+        for mut rule_obj in rule_objs {
+            // Add outbound to the rule object
+            insert_rule_target(&mut rule_obj, rule_group, rule_actions);
 
-            // Remove inline comments
-            if let Some(comment_pos) = find_str(&str_line, "//") {
-                str_line = str_line[..comment_pos].to_string();
-                str_line = trim(&str_line).to_string();
+            if let Some(rules_array) = rules.as_array_mut() {
+                rules_array.push(Value::Object(rule_obj));
             }
+        }
+    }
 
-            // Process the rule
-            let rule_parts: Vec<&str> = str_line.split(',').collect();
-            if rule_parts.len() < 2 {
-                continue;
+    // Ensure "route" section exists in the base rule
+    if base_rule.get("route").is_none() {
+        base_rule["route"] = json!({});
+    }
+
+    // Update the rules array in the base rule
+    if let Some(route) = base_rule.get_mut("route") {
+        if let Some(route_obj) = route.as_object_mut() {
+            route_obj.insert("rules".to_string(), rules);
+            route_obj.insert("final".to_string(), Value::String(final_rule));
+            if rule_sets.len() > base_rule_set_count {
+                route_obj.insert("rule_set".to_string(), Value::Array(rule_sets));
             }
+        }
+    }
+}
 
-            let rule_type = rule_parts[0];
+/// Merge the rules of a ruleset into a single sing-box rule object
+///
+/// Logical rules cannot be merged and are returned as separate rule objects.
+/// Rules are counted into `total_rules` and collection stops once it reaches
+/// `max_rules`, a `max_rules` of 0 means no limit.
+fn collect_sing_box_rule(
+    content: &str,
+    ruleset_type: RulesetType,
+    total_rules: &mut usize,
+    max_rules: usize,
+) -> (Map<String, Value>, Vec<Map<String, Value>>) {
+    let converted_rules = convert_ruleset(content, ruleset_type);
+
+    // Create a new rule object for this ruleset
+    let mut rule_obj = Map::new();
+    let mut logical_rules = Vec::new();
+
+    // Process each rule line
+    for line in converted_rules.lines() {
+        if max_rules > 0 && *total_rules >= max_rules {
+            break;
+        }
 
-            // Skip if rule type is not supported
-            if !SINGBOX_RULE_TYPES.contains(rule_type) {
-                continue;
+        let mut str_line = trim(line).to_string();
+        let line_size = str_line.len();
+
+        // Skip empty lines and comments
+        if line_size == 0
+            || (line_size >= 1 && (str_line.starts_with(';') || str_line.starts_with('#')))
+            || (line_size >= 2 && str_line.starts_with("//"))
+        {
+            continue;
+        }
+
+        // Remove inline comments
+        if let Some(comment_pos) = find_str(&str_line, "//") {
+            str_line = str_line[..comment_pos].to_string();
+            str_line = trim(&str_line).to_string();
+        }
+
+        if is_logical_rule(&str_line) {
+            if let Some(logical_rule) = sing_box_logical_rule(&str_line) {
+                logical_rules.push(logical_rule);
+                *total_rules += 1;
             }
+            continue;
+        }
+
+        // Process the rule
+        let rule_parts: Vec<&str> = str_line.split(',').collect();
+        if rule_parts.len() < 2 {
+            continue;
+        }
 
-            let real_type = to_lower(rule_type)
-                .replace("-", "_")
-                .replace("ip_cidr6", "ip_cidr")
-                .replace("src_", "source_");
+        let rule_type = rule_parts[0];
 
-            let rule_value = to_lower(rule_parts[1]);
+        // Skip if rule type is not supported
+        if !SINGBOX_RULE_TYPES.contains(rule_type) {
+            continue;
+        }
 
-            // Add to rule object
-            let values = rule_obj
-                .entry(real_type)
-                .or_insert_with(|| Value::Array(Vec::new()));
+        let real_type = sing_box_rule_field(rule_type);
 
-            if let Value::Array(ref mut arr) = values {
-                arr.push(Value::String(rule_value));
-                total_rules += 1;
-            }
+        let rule_value = to_lower(rule_parts[1]);
+
+        // Add to rule object
+        let values = rule_obj
+            .entry(real_type)
+            .or_insert_with(|| Value::Array(Vec::new()));
+
+        if let Value::Array(ref mut arr) = values {
+            arr.push(Value::String(rule_value));
+            *total_rules += 1;
         }
+    }
 
-        // Only add if rule object is not empty
-        if !rule_obj.is_empty() {
-            // Add outbound to the rule object
-            rule_obj.insert("outbound".to_string(), Value::String(rule_group.clone()));
+    (rule_obj, logical_rules)
+}
 
-            if let Some(rules_array) = rules.as_array_mut() {
-                rules_array.push(Value::Object(rule_obj));
+/// Convert a rule type name from Clash format to a sing-box rule field
+fn sing_box_rule_field(rule_type: &str) -> String {
+    to_lower(rule_type)
+        .replace("-", "_")
+        .replace("ip_cidr6", "ip_cidr")
+        .replace("src_", "source_")
+}
+
+/// Convert a logical `AND`/`OR`/`NOT` rule into a sing-box logical rule
+///
+/// Rules nesting a type sing-box does not support are reported and dropped.
+fn sing_box_logical_rule(rule: &str) -> Option<Map<String, Value>> {
+    let node = convert_logical_rule(rule, &SINGBOX_RULE_TYPES, "sing-box")?;
+    Some(rule_node_to_sing_box(&node))
+}
+
+/// Convert a parsed rule into a sing-box rule object
+///
+/// `NOT` has no logical mode of its own, its only sub-rule is inverted instead.
+fn rule_node_to_sing_box(node: &RuleNode) -> Map<String, Value> {
+    let mut rule_obj = Map::new();
+    match node {
+        RuleNode::Plain {
+            rule_type, value, ..
+        } => {
+            rule_obj.insert(
+                sing_box_rule_field(rule_type),
+                Value::Array(vec![Value::String(to_lower(value))]),
+            );
+        }
+        RuleNode::Logical {
+            op: LogicalOp::Not,
+            rules,
+        } => {
+            rule_obj = rule_node_to_sing_box(&rules[0]);
+            let inverted = rule_obj.get("invert") == Some(&Value::Bool(true));
+            if inverted {
+                rule_obj.remove("invert");
+            } else {
+                rule_obj.insert("invert".to_string(), Value::Bool(true));
             }
         }
+        RuleNode::Logical { op, rules } => {
+            rule_obj.insert("type".to_string(), Value::String("logical".to_string()));
+            rule_obj.insert("mode".to_string(), Value::String(to_lower(op.as_str())));
+            rule_obj.insert(
+                "rules".to_string(),
+                Value::Array(
+                    rules
+                        .iter()
+                        .map(|rule| Value::Object(rule_node_to_sing_box(rule)))
+                        .collect(),
+                ),
+            );
+        }
     }
+    rule_obj
+}
 
-    // Ensure "route" section exists in the base rule
-    if base_rule.get("route").is_none() {
+/// Check that a rule, nested rules included, only uses headless rule fields
+fn is_headless_rule(rule_obj: &Map<String, Value>) -> bool {
+    match rule_obj.get("rules").and_then(Value::as_array) {
+        Some(rules) => rules
+            .iter()
+            .filter_map(Value::as_object)
+            .all(is_headless_rule),
+        None => rule_obj
+            .keys()
+            .all(|field| field == "invert" || HEADLESS_RULE_FIELDS.contains(&field.as_str())),
+    }
+}
+
+/// Whether a ruleset links to a compiled `.srs` rule-set
+fn is_binary_rule_set(rule_path: &str) -> bool {
+    is_link(rule_path)
+        && rule_path
+            .split(['?', '#'])
+            .next()
+            .is_some_and(|path| path.ends_with(".srs"))
+}
+
+/// Whether a ruleset links to a rule-set sing-box can load without conversion
+fn is_direct_rule_set(ruleset: &RulesetContent) -> bool {
+    is_binary_rule_set(&ruleset.rule_path)
+        || (is_link(&ruleset.rule_path) && ruleset.rule_type == RulesetType::SingBox)
+}
+
+/// Build a remote `route.rule_set` entry for a fetched ruleset
+///
+/// Links to compiled `.srs` rule-sets and to `singbox:` source rule-sets are
+/// referenced directly, other rulesets are served as source rule-sets by the
+/// `/getruleset` endpoint below `remote_path_prefix`. Inline rules, and local
+/// files when no prefix is set, are not eligible.
+fn sing_box_rule_set(
+    ruleset: &RulesetContent,
+    remote_path_prefix: &str,
+    rule_sets: &[Value],
+) -> Option<Value> {
+    let rule_path = &ruleset.rule_path;
+    if rule_path.is_empty() {
+        return None;
+    }
+
+    let binary = is_binary_rule_set(rule_path);
+    let url = if is_direct_rule_set(ruleset) {
+        rule_path.clone()
+    } else if !remote_path_prefix.is_empty() {
+        format!(
+            "{}/getruleset?type={}&url={}",
+            remote_path_prefix,
+            SINGBOX_RULESET_TYPE,
+            url_safe_base64_encode(&ruleset.rule_path_typed)
+        )
+    } else {
+        return None;
+    };
+
+    let tag = ruleset_name_from_path(rule_path, |name| {
+        rule_sets.iter().any(|rule_set| rule_set["tag"] == name)
+    });
+
+    let mut rule_set = Map::new();
+    rule_set.insert("tag".to_string(), Value::String(tag));
+    rule_set.insert("type".to_string(), Value::String("remote".to_string()));
+    rule_set.insert(
+        "format".to_string(),
+        Value::String(if binary { "binary" } else { "source" }.to_string()),
+    );
+    rule_set.insert("url".to_string(), Value::String(url));
+    rule_set.insert(
+        "download_detour".to_string(),
+        Value::String("DIRECT".to_string()),
+    );
+    if ruleset.update_interval > 0 {
+        rule_set.insert(
+            "update_interval".to_string(),
+            Value::String(format!("{}s", ruleset.update_interval)),
+        );
+    }
+
+    Some(Value::Object(rule_set))
+}
+
+/// Convert a ruleset into a sing-box source rule-set
+///
+/// Rule types that are not allowed in headless rules (GEOIP, GEOSITE, ...)
+/// are dropped, as are logical rules nesting them.
+pub fn ruleset_to_sing_box_source(content: &str, ruleset_type: RulesetType) -> Value {
+    let mut total_rules = 0;
+    let (rule_obj, logical_rules) =
+        collect_sing_box_rule(content, ruleset_type, &mut total_rules, 0);
+    let (rules, _) = partition_headless(rule_obj, logical_rules);
+
+    json!({
+        "version": 1,
+        "rules": rules.into_iter().map(Value::Object).collect::<Vec<_>>()
+    })
+}
+
+/// sing-box rule objects
+type RuleObjects = Vec<Map<String, Value>>;
+
+/// Split collected rules into those allowed in headless rules and the rest
+///
+/// Fields of the merged rule object are split between two rule objects,
+/// logical rules go to either side as a whole. Empty rule objects are left out.
+fn partition_headless(
+    mut rule_obj: Map<String, Value>,
+    logical_rules: Vec<Map<String, Value>>,
+) -> (RuleObjects, RuleObjects) {
+    let mut other_obj = Map::new();
+    rule_obj.retain(|field, value| {
+        let headless = HEADLESS_RULE_FIELDS.contains(&field.as_str());
+        if !headless {
+            other_obj.insert(field.clone(), value.take());
+        }
+        headless
+    });
+    let (headless_logical, other_logical): (Vec<_>, Vec<_>) =
+        logical_rules.into_iter().partition(is_headless_rule);
+
+    let non_empty = |rule_obj: Map<String, Value>| Some(rule_obj).filter(|obj| !obj.is_empty());
+    (
+        non_empty(rule_obj)
+            .into_iter()
+            .chain(headless_logical)
+            .collect(),
+        non_empty(other_obj)
+            .into_iter()
+            .chain(other_logical)
+            .collect(),
+    )
+}
+
+/// Route a rule to its group, turning the built-in REJECT group into a
+/// `reject` action when rule actions are enabled
+fn insert_rule_target(rule_obj: &mut Map<String, Value>, group: &str, rule_actions: bool) {
+    if rule_actions && group == "REJECT" {
+        rule_obj.insert("action".to_string(), Value::String("reject".to_string()));
+    } else {
+        rule_obj.insert("outbound".to_string(), Value::String(group.to_string()));
+    }
+}
+
+/// Migrates the base configuration to rule actions (sing-box 1.11+)
+///
+/// The legacy `dns-out` and `block` outbounds are not emitted with rule
+/// actions, so base rules still routed to them must be rewritten even when no
+/// rules are generated. Inbound sniffing becomes a leading `sniff` action.
+pub fn migrate_to_rule_actions(base_rule: &mut Value) {
+    if !base_rule.is_object() {
+        return;
+    }
+    strip_inbound_sniff(base_rule);
+
+    if !base_rule["route"].is_object() {
         base_rule["route"] = json!({});
     }
+    if !base_rule["route"]["rules"].is_array() {
+        base_rule["route"]["rules"] = json!([]);
+    }
+    if let Some(rules) = base_rule["route"]["rules"].as_array_mut() {
+        rules_to_actions(rules);
+    }
+}
 
-    // Update the rules array in the base rule
-    if let Some(route) = base_rule.get_mut("route") {
-        if let Some(route_obj) = route.as_object_mut() {
-            route_obj.insert("rules".to_string(), rules);
-            route_obj.insert("final".to_string(), Value::String(final_rule));
+/// Rewrite legacy rules to actions and make sure sniffing runs first
+fn rules_to_actions(rules: &mut Vec<Value>) {
+    for rule in rules.iter_mut() {
+        legacy_rule_to_action(rule);
+    }
+    let sniff = json!({ "action": "sniff" });
+    if rules.first() != Some(&sniff) {
+        rules.insert(0, sniff);
+    }
+}
+
+/// Rewrite a base rule that still targets a legacy special outbound
+fn legacy_rule_to_action(rule: &mut Value) {
+    let Some(rule_obj) = rule.as_object_mut() else {
+        return;
+    };
+    let action = match rule_obj.get("outbound").and_then(Value::as_str) {
+        Some("dns-out") => "hijack-dns",
+        Some("REJECT") => "reject",
+        _ => return,
+    };
+    rule_obj.remove("outbound");
+    rule_obj.insert("action".to_string(), Value::String(action.to_string()));
+}
+
+/// Remove the inbound sniffing fields deprecated in favour of the `sniff` action
+fn strip_inbound_sniff(base_rule: &mut Value) {
+    if let Some(inbounds) = base_rule.get_mut("inbounds").and_then(Value::as_array_mut) {
+        for inbound in inbounds.iter_mut().filter_map(Value::as_object_mut) {
+            inbound.remove("sniff");
+            inbound.remove("sniff_override_destination");
+            inbound.remove("sniff_timeout");
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ruleset(rule_path: &str, group: &str, content: &str) -> RulesetContent {
+        let mut ruleset = RulesetContent::new(rule_path, group);
+        ruleset.set_rule_content(content);
+        ruleset
+    }
+
+    #[test]
+    fn test_ruleset_to_sing_box_remote_rule_set() {
+        let mut geoip = ruleset(
+            "https://example.com/rule-set/geoip-cn.srs",
+            "DIRECT",
+            "binary",
+        );
+        geoip.update_interval = 3600;
+        let mut reject = ruleset(
+            "rules/reject.list",
+            "REJECT",
+            "DOMAIN-SUFFIX,ads.example.com\nGEOIP,KP\nAND,((GEOSITE,ads),(NETWORK,UDP))",
+        );
+        reject.rule_path_typed = reject.rule_path.clone();
+        let rulesets = vec![geoip, reject];
+        let mut base = json!({
+            "route": { "rule_set": [{ "tag": "geoip-cn", "type": "local", "path": "cn.srs" }] }
+        });
+
+        ruleset_to_sing_box(&mut base, &rulesets, true, true, "http://127.0.0.1:25500");
+
+        let route = &base["route"];
+        assert_eq!(
+            route["rule_set"][1],
+            json!({
+                "tag": "geoip-cn_2",
+                "type": "remote",
+                "format": "binary",
+                "url": "https://example.com/rule-set/geoip-cn.srs",
+                "download_detour": "DIRECT",
+                "update_interval": "3600s",
+            })
+        );
+        assert_eq!(
+            route["rule_set"][2]["url"],
+            format!(
+                "http://127.0.0.1:25500/getruleset?type=7&url={}",
+                url_safe_base64_encode("rules/reject.list")
+            )
+        );
+        let rules = route["rules"].as_array().unwrap();
+        assert!(rules.contains(&json!({ "rule_set": "geoip-cn_2", "outbound": "DIRECT" })));
+        assert!(rules.contains(&json!({ "rule_set": "reject", "action": "reject" })));
+        // Rules the source rule-set cannot hold stay inline
+        assert!(rules.contains(&json!({ "geoip": ["kp"], "action": "reject" })));
+        assert!(rules.iter().any(|rule| rule["type"] == "logical"
+            && rule["action"] == "reject"
+            && rule["rules"][0]["geosite"] == json!(["ads"])));
+        assert!(!rules.iter().any(|rule| rule.get("domain_suffix").is_some()));
+    }
+
+    #[test]
+    fn test_ruleset_to_sing_box_expanded_without_prefix() {
+        let rulesets = vec![ruleset(
+            "rules/reject.list",
+            "REJECT",
+            "DOMAIN-SUFFIX,ads.example.com",
+        )];
+        let mut base = json!({});
+
+        ruleset_to_sing_box(&mut base, &rulesets, true, true, "");
+
+        assert!(base["route"].get("rule_set").is_none());
+        let rules = base["route"]["rules"].as_array().unwrap();
+        assert!(
+            rules.contains(&json!({ "domain_suffix": ["ads.example.com"], "action": "reject" }))
+        );
+    }
+
+    #[test]
+    fn test_sing_box_rule_set_source_url() {
+        let mut ruleset = ruleset("https://example.com/reject.yaml", "REJECT", "payload:");
+        ruleset.rule_path_typed = format!("clash-domain:{}", ruleset.rule_path);
+
+        let rule_set = sing_box_rule_set(&ruleset, "http://127.0.0.1:25500", &[]).unwrap();
+        assert_eq!(rule_set["tag"], "reject");
+        assert_eq!(rule_set["format"], "source");
+        assert_eq!(
+            rule_set["url"],
+            format!(
+                "http://127.0.0.1:25500/getruleset?type=7&url={}",
+                url_safe_base64_encode("clash-domain:https://example.com/reject.yaml")
+            )
+        );
+
+        assert!(sing_box_rule_set(&ruleset, "", &[]).is_none());
+
+        // sing-box source rule-sets need no conversion
+        ruleset.rule_type = RulesetType::SingBox;
+        let rule_set = sing_box_rule_set(&ruleset, "", &[]).unwrap();
+        assert_eq!(rule_set["format"], "source");
+        assert_eq!(rule_set["url"], "https://example.com/reject.yaml");
+    }
+
+    #[test]
+    fn test_ruleset_to_sing_box_source() {
+        let source = ruleset_to_sing_box_source(
+            "DOMAIN-SUFFIX,Example.com\nIP-CIDR,1.1.1.0/24,no-resolve\nGEOIP,CN\n# comment",
+            RulesetType::Surge,
+        );
+        assert_eq!(
+            source,
+            json!({
+                "version": 1,
+                "rules": [{ "domain_suffix": ["example.com"], "ip_cidr": ["1.1.1.0/24"] }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_ruleset_to_sing_box_logical_rules() {
+        let rulesets = vec![
+            ruleset("", "REJECT", "[]NOT,((NETWORK,UDP))"),
+            ruleset(
+                "rules/logical.list",
+                "Proxy",
+                "DOMAIN-SUFFIX,example.com\nAND,((DOMAIN-SUFFIX,Example.org),(OR,((PORT,443),(NOT,((NETWORK,UDP))))))\nOR,((DOMAIN,a.com),(USER-AGENT,curl*))",
+            ),
+        ];
+        let mut base = json!({});
+
+        ruleset_to_sing_box(&mut base, &rulesets, true, true, "");
+
+        let rules = base["route"]["rules"].as_array().unwrap();
+        assert!(rules.contains(&json!({ "network": ["udp"], "invert": true, "action": "reject" })));
+        assert!(rules.contains(&json!({ "domain_suffix": ["example.com"], "outbound": "Proxy" })));
+        assert!(rules.contains(&json!({
+            "type": "logical",
+            "mode": "and",
+            "rules": [
+                { "domain_suffix": ["example.org"] },
+                {
+                    "type": "logical",
+                    "mode": "or",
+                    "rules": [{ "port": ["443"] }, { "network": ["udp"], "invert": true }]
+                }
+            ],
+            "outbound": "Proxy"
+        })));
+        // USER-AGENT cannot be expressed in sing-box, the whole rule is dropped
+        assert!(!rules.iter().any(|rule| rule["mode"] == "or"));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module provides functionality to convert rulesets to Surge format.
 
-use crate::models::RulesetContent;
+use crate::models::{RulesetContent, RulesetType};
 use crate::utils::base64::url_safe_base64_encode;
 use crate::utils::ini_reader::IniReader;
 use crate::utils::network::is_link;
@@ -13,8 +13,9 @@ use lazy_static::lazy_static;
 use log::warn;
 use std::collections::HashSet;
 
-use super::common::transform_rule_to_common;
+use super::common::{ruleset_name_from_path, transform_rule_to_common};
 use super::convert_ruleset::convert_ruleset;
+use super::rule_ast::{convert_logical_rule, is_logical_rule};
 
 lazy_static! {
     static ref QUANX_RULE_TYPES: HashSet<&'static str> = {
@@ -102,6 +103,167 @@ lazy_static! {
         types.insert("SRC-IP");
         types
     };
+
+    static ref LOON_RULE_TYPES: HashSet<&'static str> = {
+        let mut types = SURGE2_RULE_TYPES.clone();
+        // Logical rules
+        types.insert("AND");
+        types.insert("OR");
+        types.insert("NOT");
+        types
+    };
+}
+
+/// Returns the rule types and the name of the client `surge_ver` stands for
+fn target_rule_types(surge_ver: i32) -> (&'static HashSet<&'static str>, &'static str) {
+    match surge_ver {
+        -1 => (&QUANX_RULE_TYPES, "Quantumult X"),
+        -2 => (&QUANX_RULE_TYPES, "Quantumult"),
+        -3 => (&SURF_RULE_TYPES, "Surfboard"),
+        -4 => (&LOON_RULE_TYPES, "Loon"),
+        0 => (&SURGE2_RULE_TYPES, "Mellow"),
+        v if v > 2 => (&SURGE_RULE_TYPES, "Surge"),
+        _ => (&SURGE2_RULE_TYPES, "Surge 2"),
+    }
+}
+
+/// Converts a logical `AND`/`OR`/`NOT` rule for the target client
+///
+/// Every nested rule type is checked, a rule the client cannot express is
+/// reported and dropped. The policy is appended after the sub-rules.
+fn convert_logical_line(rule: &str, surge_ver: i32, rule_group: Option<&str>) -> Option<String> {
+    let (rule_types, target) = target_rule_types(surge_ver);
+    let node = convert_logical_rule(rule, rule_types, target)?;
+    Some(match rule_group {
+        Some(rule_group) => format!("{},{}", node, rule_group),
+        None => node.to_string(),
+    })
+}
+
+/// Converts one line of a Surge-format rule list for the target client
+///
+/// Comments and rule types the target does not support yield `None`. With a
+/// `rule_group` the policy is appended, without one the rule is kept as a
+/// policy-less `RULE-SET` entry.
+fn convert_rule_line(line: &str, surge_ver: i32, rule_group: Option<&str>) -> Option<String> {
+    let mut str_line = trim(line).to_string();
+    let line_size = str_line.len();
+
+    // Skip empty lines and comments
+    if line_size == 0
+        || (line_size >= 1 && (str_line.starts_with(';') || str_line.starts_with('#')))
+        || (line_size >= 2 && str_line.starts_with("//"))
+    {
+        return None;
+    }
+
+    if is_logical_rule(&str_line) {
+        if let Some(comment_pos) = find_str(&str_line, "//") {
+            str_line = trim(&str_line[..comment_pos]).to_string();
+        }
+        return convert_logical_line(&str_line, surge_ver, rule_group);
+    }
+
+    // Check if rule type is supported by the target
+    let rule_supported = match surge_ver {
+        -2 => {
+            if starts_with(&str_line, "IP-CIDR6") {
+                false
+            } else {
+                QUANX_RULE_TYPES
+                    .iter()
+                    .any(|&rule_type| starts_with(&str_line, rule_type))
+            }
+        }
+        -1 => QUANX_RULE_TYPES
+            .iter()
+            .any(|&rule_type| starts_with(&str_line, rule_type)),
+        -3 => SURF_RULE_TYPES
+            .iter()
+            .any(|&rule_type| starts_with(&str_line, rule_type)),
+        -4 => LOON_RULE_TYPES
+            .iter()
+            .any(|&rule_type| starts_with(&str_line, rule_type)),
+        _ => {
+            if surge_ver > 2 {
+                SURGE_RULE_TYPES
+                    .iter()
+                    .any(|&rule_type| starts_with(&str_line, rule_type))
+            } else {
+                SURGE2_RULE_TYPES
+                    .iter()
+                    .any(|&rule_type| starts_with(&str_line, rule_type))
+            }
+        }
+    };
+
+    if !rule_supported {
+        return None;
+    }
+
+    // Remove inline comments
+    if let Some(comment_pos) = find_str(&str_line, "//") {
+        str_line = str_line[..comment_pos].to_string();
+        str_line = trim(&str_line).to_string();
+    }
+
+    // Transform the rule based on target type
+    if surge_ver == -1 || surge_ver == -2 {
+        if starts_with(&str_line, "IP-CIDR6") {
+            str_line = str_line.replacen("IP-CIDR6", "IP6-CIDR", 1);
+        }
+        if let Some(rule_group) = rule_group {
+            str_line = transform_rule_to_common(&str_line, rule_group, true);
+        }
+    } else if let Some(rule_group) = rule_group {
+        str_line = transform_rule_to_common(&str_line, rule_group, false);
+    }
+
+    Some(str_line)
+}
+
+/// Converts a single ruleset into the rule list served by `/getruleset`
+///
+/// Surge and Loon fetch it as a `RULE-SET` list without policies, Quantumult X
+/// (`surge_ver` -1) fetches it through `filter_remote` with `group` on every line.
+pub fn ruleset_to_surge_list(
+    content: &str,
+    ruleset_type: RulesetType,
+    surge_ver: i32,
+    group: Option<&str>,
+) -> String {
+    convert_ruleset(content, ruleset_type)
+        .lines()
+        .filter_map(|line| convert_rule_line(line, surge_ver, group))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats a Quantumult X `filter_remote` entry fetching `url` for the group
+/// of `ruleset`
+///
+/// Tags are derived from the ruleset file name and kept unique through `tags`.
+fn quanx_filter_remote(url: &str, ruleset: &RulesetContent, tags: &mut HashSet<String>) -> String {
+    let tag = ruleset_name_from_path(&ruleset.rule_path, |name| tags.contains(name));
+    let mut str_line = format!("{}, tag={}, force-policy={}", url, tag, ruleset.group);
+    tags.insert(tag);
+
+    if ruleset.update_interval > 0 {
+        str_line.push_str(&format!(", update-interval={}", ruleset.update_interval));
+    }
+
+    str_line.push_str(", enabled=true");
+    str_line
+}
+
+/// Formats a Loon `[Remote Rule]` entry fetching `url` for `rule_group`
+fn loon_remote_rule(url: &str, rule_path: &str, rule_group: &str) -> String {
+    format!(
+        "{}, policy={}, tag={}, enabled=true",
+        url,
+        rule_group,
+        ruleset_name_from_path(rule_path, |_| false)
+    )
 }
 
 /// Converts rulesets to Surge format and updates the INI configuration
@@ -113,6 +275,8 @@ lazy_static! {
 /// * `surge_ver` - Surge version (or negative for other clients)
 /// * `overwrite_original_rules` - Whether to overwrite original rules
 /// * `remote_path_prefix` - Prefix for remote ruleset URLs
+/// * `domain_set` - Whether to reference remote domain sets as `DOMAIN-SET`
+///   rules instead of converting them, Surge 3+ only
 ///
 /// # Returns
 ///
@@ -123,6 +287,7 @@ pub async fn ruleset_to_surge(
     surge_ver: i32,
     overwrite_original_rules: bool,
     remote_path_prefix: &str,
+    domain_set: bool,
 ) {
     // Get global settings
     let settings = Settings::current();
@@ -147,6 +312,7 @@ pub async fn ruleset_to_surge(
 
     // Keep track of all rules to add
     let mut all_rules = Vec::new();
+    let mut filter_remote_tags = HashSet::new();
     let mut total_rules = 0;
 
     // Process each ruleset
@@ -167,15 +333,15 @@ pub async fn ruleset_to_surge(
                 str_line = "FINAL".to_string();
             }
 
-            if surge_ver == -1 || surge_ver == -2 {
+            if is_logical_rule(&str_line) {
+                match convert_logical_line(&str_line, surge_ver, Some(rule_group)) {
+                    Some(converted) => str_line = converted,
+                    None => continue,
+                }
+            } else if surge_ver == -1 || surge_ver == -2 {
                 str_line = transform_rule_to_common(&str_line, rule_group, true);
             } else {
-                if !starts_with(&str_line, "AND")
-                    && !starts_with(&str_line, "OR")
-                    && !starts_with(&str_line, "NOT")
-                {
-                    str_line = transform_rule_to_common(&str_line, rule_group, false);
-                }
+                str_line = transform_rule_to_common(&str_line, rule_group, false);
             }
 
             // Replace double commas with single comma
@@ -189,10 +355,7 @@ pub async fn ruleset_to_surge(
                 && ruleset.rule_type == crate::models::RulesetType::Quanx
                 && is_link(rule_path)
             {
-                let str_line = format!(
-                    "{}, tag={}, force-policy={}, enabled=true",
-                    rule_path, rule_group, rule_group
-                );
+                let str_line = quanx_filter_remote(rule_path, ruleset, &mut filter_remote_tags);
                 let _ = base_rule.set("filter_remote", "{NONAME}", &str_line);
                 continue;
             }
@@ -213,30 +376,43 @@ pub async fn ruleset_to_surge(
                     all_rules.push(str_line);
                     continue;
                 } else if surge_ver == -1 && !remote_path_prefix.is_empty() {
-                    let str_line = format!(
-                        "{}/getruleset?type=2&url={}&group={}, tag={}, enabled=true",
+                    let url = format!(
+                        "{}/getruleset?type=2&url={}&group={}",
                         remote_path_prefix,
                         url_safe_base64_encode(rule_path_typed),
-                        url_safe_base64_encode(rule_group),
-                        rule_group
+                        url_safe_base64_encode(rule_group)
                     );
 
+                    let str_line = quanx_filter_remote(&url, ruleset, &mut filter_remote_tags);
                     let _ = base_rule.set("filter_remote", "{NONAME}", &str_line);
                     continue;
                 } else if surge_ver == -4 && !remote_path_prefix.is_empty() {
-                    let str_line = format!(
-                        "{}/getruleset?type=1&url={},{}",
+                    let url = format!(
+                        "{}/getruleset?type=1&url={}",
                         remote_path_prefix,
-                        url_safe_base64_encode(rule_path_typed),
-                        rule_group
+                        url_safe_base64_encode(rule_path_typed)
                     );
 
+                    let str_line = loon_remote_rule(&url, rule_path, rule_group);
                     let _ = base_rule.set("Remote Rule", "{NONAME}", &str_line);
                     continue;
                 }
             } else if is_link(rule_path) {
                 if surge_ver > 2 {
-                    if ruleset.rule_type != crate::models::RulesetType::Surge {
+                    if ruleset.rule_type == RulesetType::DomainSet && domain_set {
+                        let mut str_line = format!("DOMAIN-SET,{},{}", rule_path, rule_group);
+
+                        if ruleset.update_interval > 0 {
+                            str_line
+                                .push_str(&format!(",update-interval={}", ruleset.update_interval));
+                        }
+
+                        all_rules.push(str_line);
+                        continue;
+                    } else if ruleset.rule_type.is_domain_list() && remote_path_prefix.is_empty() {
+                        // Domain lists are expanded below when there is no
+                        // endpoint to convert them
+                    } else if ruleset.rule_type != crate::models::RulesetType::Surge {
                         if !remote_path_prefix.is_empty() {
                             let mut str_line = format!(
                                 "RULE-SET,{}/getruleset?type=1&url={},{}",
@@ -267,20 +443,37 @@ pub async fn ruleset_to_surge(
                         continue;
                     }
                 } else if surge_ver == -1 && !remote_path_prefix.is_empty() {
-                    let str_line = format!(
-                        "{}/getruleset?type=2&url={}&group={}, tag={}, enabled=true",
+                    let url = format!(
+                        "{}/getruleset?type=2&url={}&group={}",
                         remote_path_prefix,
                         url_safe_base64_encode(rule_path_typed),
-                        url_safe_base64_encode(rule_group),
-                        rule_group
+                        url_safe_base64_encode(rule_group)
                     );
 
+                    let str_line = quanx_filter_remote(&url, ruleset, &mut filter_remote_tags);
                     let _ = base_rule.set("filter_remote", "{NONAME}", &str_line);
                     continue;
                 } else if surge_ver == -4 {
-                    let str_line = format!("{},{}", rule_path, rule_group);
-                    let _ = base_rule.set("Remote Rule", "{NONAME}", &str_line);
-                    continue;
+                    // Loon reads Surge and Quantumult X lists as they are, other
+                    // formats are converted by /getruleset or expanded below
+                    let url =
+                        if matches!(ruleset.rule_type, RulesetType::Surge | RulesetType::Quanx) {
+                            Some(rule_path.to_string())
+                        } else if !remote_path_prefix.is_empty() {
+                            Some(format!(
+                                "{}/getruleset?type=1&url={}",
+                                remote_path_prefix,
+                                url_safe_base64_encode(rule_path_typed)
+                            ))
+                        } else {
+                            None
+                        };
+
+                    if let Some(url) = url {
+                        let str_line = loon_remote_rule(&url, rule_path, rule_group);
+                        let _ = base_rule.set("Remote Rule", "{NONAME}", &str_line);
+                        continue;
+                    }
                 }
             } else {
                 continue;
@@ -311,74 +504,12 @@ pub async fn ruleset_to_surge(
                     break;
                 }
 
-                let mut str_line = trim(line).to_string();
-                let line_size = str_line.len();
-
-                // Skip empty lines and comments
-                if line_size == 0
-                    || (line_size >= 1 && (str_line.starts_with(';') || str_line.starts_with('#')))
-                    || (line_size >= 2 && str_line.starts_with("//"))
+                if let Some(str_line) =
+                    convert_rule_line(line, surge_ver, Some(rule_group.as_str()))
                 {
-                    continue;
-                }
-
-                // Check if rule type is supported by the target
-                let rule_supported = match surge_ver {
-                    -2 => {
-                        if starts_with(&str_line, "IP-CIDR6") {
-                            false
-                        } else {
-                            QUANX_RULE_TYPES
-                                .iter()
-                                .any(|&rule_type| starts_with(&str_line, rule_type))
-                        }
-                    }
-                    -1 => QUANX_RULE_TYPES
-                        .iter()
-                        .any(|&rule_type| starts_with(&str_line, rule_type)),
-                    -3 => SURF_RULE_TYPES
-                        .iter()
-                        .any(|&rule_type| starts_with(&str_line, rule_type)),
-                    _ => {
-                        if surge_ver > 2 {
-                            SURGE_RULE_TYPES
-                                .iter()
-                                .any(|&rule_type| starts_with(&str_line, rule_type))
-                        } else {
-                            SURGE2_RULE_TYPES
-                                .iter()
-                                .any(|&rule_type| starts_with(&str_line, rule_type))
-                        }
-                    }
-                };
-
-                if !rule_supported {
-                    continue;
-                }
-
-                // Remove inline comments
-                if let Some(comment_pos) = find_str(&str_line, "//") {
-                    str_line = str_line[..comment_pos].to_string();
-                    str_line = trim(&str_line).to_string();
-                }
-
-                // Transform the rule based on target type
-                if surge_ver == -1 || surge_ver == -2 {
-                    if starts_with(&str_line, "IP-CIDR6") {
-                        str_line = str_line.replacen("IP-CIDR6", "IP6-CIDR", 1);
-                    }
-                    str_line = transform_rule_to_common(&str_line, rule_group, true);
-                } else {
-                    if !starts_with(&str_line, "AND")
-                        && !starts_with(&str_line, "OR")
-                        && !starts_with(&str_line, "NOT")
-                    {
-                        str_line = transform_rule_to_common(&str_line, rule_group, false);
-                    }
+                    all_rules.push(str_line);
+                    total_rules += 1;
                 }
-
-                all_rules.push(str_line);
-                total_rules += 1;
             }
         }
     }
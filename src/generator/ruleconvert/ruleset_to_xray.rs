@@ -0,0 +1,240 @@
+//! Ruleset to Xray conversion
+//!
+//! This module provides functionality to convert rulesets to Xray routing rules.
+
+use crate::models::RulesetContent;
+use crate::utils::string::{find_str, starts_with, to_lower};
+use crate::utils::trim;
+use crate::Settings;
+use log::warn;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+use super::convert_ruleset::convert_ruleset;
+
+/// Where traffic of a proxy group is sent in Xray routing
+#[derive(Debug, Clone, PartialEq)]
+pub enum XrayRouteTarget {
+    /// Route to a single outbound
+    Outbound(String),
+    /// Route to a balancer
+    Balancer(String),
+}
+
+impl XrayRouteTarget {
+    /// Inserts the `outboundTag` or `balancerTag` of this target into a rule
+    fn apply(&self, rule: &mut Map<String, Value>) {
+        match self {
+            XrayRouteTarget::Outbound(tag) => {
+                rule.insert("outboundTag".to_string(), Value::String(tag.clone()))
+            }
+            XrayRouteTarget::Balancer(tag) => {
+                rule.insert("balancerTag".to_string(), Value::String(tag.clone()))
+            }
+        };
+    }
+}
+
+/// Maps a rule to the Xray rule field and value it matches on
+///
+/// Fields holding a list are returned as-is, `port`, `sourcePort` and
+/// `network` are comma separated strings in Xray.
+fn xray_rule_field(rule_type: &str, value: &str) -> Option<(&'static str, String)> {
+    let field = match rule_type {
+        "DOMAIN" => ("domain", format!("full:{}", value)),
+        "DOMAIN-SUFFIX" => ("domain", format!("domain:{}", value)),
+        "DOMAIN-KEYWORD" => ("domain", value.to_string()),
+        "DOMAIN-REGEX" => ("domain", format!("regexp:{}", value)),
+        "GEOSITE" => ("domain", format!("geosite:{}", to_lower(value))),
+        "IP-CIDR" | "IP-CIDR6" => ("ip", value.to_string()),
+        "GEOIP" => ("ip", format!("geoip:{}", to_lower(value))),
+        "SRC-IP-CIDR" => ("source", value.to_string()),
+        "DST-PORT" => ("port", value.to_string()),
+        "SRC-PORT" => ("sourcePort", value.to_string()),
+        "NETWORK" => ("network", to_lower(value)),
+        _ => return None,
+    };
+    Some(field)
+}
+
+/// Builds a routing rule matching any of `values` on `field`
+fn build_rule(field: &str, values: Vec<String>, target: &XrayRouteTarget) -> Value {
+    let mut rule = Map::new();
+    rule.insert("type".to_string(), Value::String("field".to_string()));
+    let values = match field {
+        "port" | "sourcePort" | "network" => Value::String(values.join(",")),
+        _ => json!(values),
+    };
+    rule.insert(field.to_string(), values);
+    target.apply(&mut rule);
+    Value::Object(rule)
+}
+
+/// Converts rulesets to Xray routing rules and updates the JSON configuration
+///
+/// Matchers of different kinds are AND-ed inside a single Xray rule, so each
+/// ruleset produces one rule per matched field. `MATCH`/`FINAL` becomes a
+/// trailing rule covering all TCP and UDP traffic.
+///
+/// # Arguments
+///
+/// * `base_rule` - The base Xray configuration as JSON
+/// * `ruleset_content_array` - Array of ruleset contents to process
+/// * `overwrite_original_rules` - Whether to overwrite original rules
+/// * `route_targets` - Routing target of each proxy group, other names are
+///   used as outbound tags
+pub fn ruleset_to_xray(
+    base_rule: &mut Value,
+    ruleset_content_array: &[RulesetContent],
+    overwrite_original_rules: bool,
+    route_targets: &HashMap<String, XrayRouteTarget>,
+) {
+    // Get global settings
+    let settings = Settings::current();
+
+    let mut rules = Vec::new();
+
+    // If not overwriting, copy existing rules
+    if !overwrite_original_rules {
+        if let Some(existing_rules) = base_rule["routing"]["rules"].as_array() {
+            rules.extend(existing_rules.iter().cloned());
+        }
+    }
+
+    let mut total_rules = 0;
+    let mut final_target = None;
+
+    for ruleset in ruleset_content_array {
+        // Check if we've reached the maximum number of rules
+        if settings.max_allowed_rules > 0 && total_rules >= settings.max_allowed_rules {
+            break;
+        }
+
+        let target = route_targets
+            .get(&ruleset.group)
+            .cloned()
+            .unwrap_or_else(|| XrayRouteTarget::Outbound(ruleset.group.clone()));
+        let retrieved_rules = ruleset.get_rule_content();
+
+        if retrieved_rules.is_empty() {
+            warn!(
+                "Failed to fetch ruleset or ruleset is empty: '{}'!",
+                ruleset.rule_path
+            );
+            continue;
+        }
+
+        let converted_rules = if starts_with(&retrieved_rules, "[]") {
+            retrieved_rules[2..].to_string()
+        } else {
+            convert_ruleset(&retrieved_rules, ruleset.rule_type)
+        };
+
+        // Values of this ruleset grouped by field, in first-seen order
+        let mut fields: Vec<(&'static str, Vec<String>)> = Vec::new();
+
+        for line in converted_rules.lines() {
+            if settings.max_allowed_rules > 0 && total_rules >= settings.max_allowed_rules {
+                break;
+            }
+
+            let mut str_line = trim(line).to_string();
+
+            // Skip empty lines and comments
+            if str_line.is_empty()
+                || str_line.starts_with(';')
+                || str_line.starts_with('#')
+                || str_line.starts_with("//")
+            {
+                continue;
+            }
+
+            // Remove inline comments
+            if let Some(comment_pos) = find_str(&str_line, "//") {
+                str_line = trim(&str_line[..comment_pos]).to_string();
+            }
+
+            let rule_parts: Vec<&str> = str_line.split(',').map(|part| part.trim()).collect();
+            if rule_parts[0] == "MATCH" || rule_parts[0] == "FINAL" {
+                final_target = Some(target.clone());
+                continue;
+            }
+            if rule_parts.len() < 2 {
+                continue;
+            }
+
+            let (field, value) = match xray_rule_field(rule_parts[0], rule_parts[1]) {
+                Some(field) => field,
+                None => {
+                    warn!("Skipping rule '{}': not supported by Xray", str_line);
+                    continue;
+                }
+            };
+
+            match fields.iter_mut().find(|(name, _)| *name == field) {
+                Some((_, values)) => values.push(value),
+                None => fields.push((field, vec![value])),
+            }
+            total_rules += 1;
+        }
+
+        for (field, values) in fields {
+            rules.push(build_rule(field, values, &target));
+        }
+    }
+
+    if let Some(target) = final_target {
+        rules.push(build_rule("network", vec!["tcp,udp".to_string()], &target));
+    }
+
+    // Ensure "routing" section exists in the base rule
+    if !base_rule["routing"].is_object() {
+        base_rule["routing"] = json!({});
+    }
+    base_rule["routing"]["rules"] = Value::Array(rules);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::test_utils::inline_rulesets;
+
+    #[test]
+    fn test_ruleset_to_xray() {
+        let mut file_ruleset = RulesetContent::new("rules/cn.list", "DIRECT");
+        file_ruleset.set_rule_content(
+            "# China\nDOMAIN-SUFFIX,cn\nIP-CIDR,1.0.1.0/24,no-resolve\nDOMAIN,qq.com\nUSER-AGENT,MicroMessenger*\n",
+        );
+        let rulesets = [
+            vec![file_ruleset],
+            inline_rulesets(&["GEOIP,CN"], "DIRECT"),
+            inline_rulesets(&["DST-PORT,443"], "Auto"),
+            inline_rulesets(&["MATCH"], "Proxy"),
+        ]
+        .concat();
+        let mut route_targets = HashMap::new();
+        route_targets.insert(
+            "Auto".to_string(),
+            XrayRouteTarget::Balancer("Auto".to_string()),
+        );
+        route_targets.insert(
+            "Proxy".to_string(),
+            XrayRouteTarget::Outbound("node".to_string()),
+        );
+
+        let mut config = json!({ "routing": { "rules": [{ "type": "field", "ip": ["geoip:private"], "outboundTag": "DIRECT" }] } });
+        ruleset_to_xray(&mut config, &rulesets, false, &route_targets);
+
+        assert_eq!(
+            config["routing"]["rules"],
+            json!([
+                { "type": "field", "ip": ["geoip:private"], "outboundTag": "DIRECT" },
+                { "type": "field", "domain": ["domain:cn", "full:qq.com"], "outboundTag": "DIRECT" },
+                { "type": "field", "ip": ["1.0.1.0/24"], "outboundTag": "DIRECT" },
+                { "type": "field", "ip": ["geoip:cn"], "outboundTag": "DIRECT" },
+                { "type": "field", "port": "443", "balancerTag": "Auto" },
+                { "type": "field", "network": "tcp,udp", "outboundTag": "node" },
+            ])
+        );
+    }
+}
@@ -150,6 +150,10 @@ pub enum ClashProxyOutput {
     VLess(VLessProxy),
     #[serde(rename = "anytls")]
     AnyTls(ClashOutputAnyTLS),
+    #[serde(rename = "tuic")]
+    Tuic(TuicProxy),
+    #[serde(rename = "ssh")]
+    Ssh(SshProxy),
 }
 
 /// Factory methods for creating various proxy types
@@ -212,6 +216,11 @@ impl ClashProxyOutput {
     pub fn new_anytls(common: CommonProxyOptions) -> Self {
         ClashProxyOutput::AnyTls(ClashOutputAnyTLS::new(common))
     }
+
+    /// Create a new TUIC proxy
+    pub fn new_tuic(common: CommonProxyOptions) -> Self {
+        ClashProxyOutput::Tuic(TuicProxy::new(common))
+    }
 }
 
 /// Trait for common operations on all ClashProxy variants
@@ -268,6 +277,8 @@ impl ClashProxyCommon for ClashProxyOutput {
             ClashProxyOutput::Hysteria2(proxy) => &proxy.common,
             ClashProxyOutput::VLess(proxy) => &proxy.common,
             ClashProxyOutput::AnyTls(proxy) => &proxy.common,
+            ClashProxyOutput::Tuic(proxy) => &proxy.common,
+            ClashProxyOutput::Ssh(proxy) => &proxy.common,
         }
     }
 
@@ -285,6 +296,8 @@ impl ClashProxyCommon for ClashProxyOutput {
             ClashProxyOutput::Hysteria2(proxy) => &mut proxy.common,
             ClashProxyOutput::VLess(proxy) => &mut proxy.common,
             ClashProxyOutput::AnyTls(proxy) => &mut proxy.common,
+            ClashProxyOutput::Tuic(proxy) => &mut proxy.common,
+            ClashProxyOutput::Ssh(proxy) => &mut proxy.common,
         }
     }
 }
@@ -423,6 +436,8 @@ impl From<Proxy> for ClashProxyOutput {
             ProxyType::Hysteria => ClashProxyOutput::Hysteria(HysteriaProxy::from(proxy)),
             ProxyType::Hysteria2 => ClashProxyOutput::Hysteria2(Hysteria2Proxy::from(proxy)),
             ProxyType::AnyTls => ClashProxyOutput::AnyTls(ClashOutputAnyTLS::from(proxy)),
+            ProxyType::Tuic => ClashProxyOutput::Tuic(TuicProxy::from(proxy)),
+            ProxyType::Ssh => ClashProxyOutput::Ssh(SshProxy::from(proxy)),
             _ => {
                 // 遇到不支持的类型，返回一个默认的HTTP代理
                 // 实际使用时应该在转换前检查并筛选掉不支持的类型
@@ -70,18 +70,33 @@ impl From<Proxy> for ShadowsocksProxy {
 
         ss.cipher = proxy.encrypt_method;
         ss.password = proxy.password;
-        ss.plugin = proxy.plugin;
-
-        if let Some(plugin_opts) = proxy.plugin_option {
+        let plugin = proxy.plugin.unwrap_or_default();
+        if !plugin.is_empty() {
             let mut opts = HashMap::new();
 
-            for opt in plugin_opts.split(';') {
-                let parts: Vec<&str> = opt.split('=').collect();
-                if parts.len() == 2 {
-                    opts.insert(parts[0].to_string(), parts[1].to_string());
+            for opt in proxy.plugin_option.as_deref().unwrap_or("").split(';') {
+                let (key, value) = match opt.split_once('=') {
+                    Some((key, value)) => (key, value),
+                    None => (opt, ""),
+                };
+                // Translate SIP003 option names to the ones Clash expects
+                let (key, value) = match (plugin.as_str(), key) {
+                    ("obfs-local" | "simple-obfs", "obfs") => ("mode", value),
+                    ("obfs-local" | "simple-obfs", "obfs-host") => ("host", value),
+                    ("v2ray-plugin", "tls") => ("tls", "true"),
+                    ("v2ray-plugin", "mux") => ("mux", "true"),
+                    (_, "") => continue,
+                    _ => (key, value),
+                };
+                if !value.is_empty() {
+                    opts.insert(key.to_string(), value.to_string());
                 }
             }
 
+            ss.plugin = Some(match plugin.as_str() {
+                "obfs-local" | "simple-obfs" => "obfs".to_string(),
+                _ => plugin,
+            });
             ss.plugin_opts = Some(opts);
         }
 
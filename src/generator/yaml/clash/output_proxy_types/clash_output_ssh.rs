@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use super::CommonProxyOptions;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::Proxy;
+use crate::utils::is_empty_option_string;
+
+/// Represents an SSH proxy for Clash output configuration (mihomo extension)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SshProxy {
+    #[serde(flatten)]
+    pub common: CommonProxyOptions,
+    pub username: String,
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub private_key: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub private_key_passphrase: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_key: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_key_algorithms: Option<Vec<String>>,
+}
+
+impl SshProxy {
+    /// Create a new SSH proxy
+    pub fn new(common: CommonProxyOptions) -> Self {
+        Self {
+            common,
+            username: String::new(),
+            password: None,
+            private_key: None,
+            private_key_passphrase: None,
+            host_key: None,
+            host_key_algorithms: None,
+        }
+    }
+}
+
+impl From<Proxy> for SshProxy {
+    fn from(proxy: Proxy) -> Self {
+        let common =
+            CommonProxyOptions::builder(proxy.remark.clone(), proxy.hostname.clone(), proxy.port)
+                .build();
+
+        let mut ssh = SshProxy::new(common);
+        if let Some(CombinedProxy::Ssh(ssh_proxy)) = proxy.combined_proxy {
+            ssh.username = ssh_proxy.username;
+            ssh.password = ssh_proxy.password;
+            ssh.private_key = ssh_proxy.private_key;
+            ssh.private_key_passphrase = ssh_proxy.private_key_passphrase;
+            ssh.host_key = ssh_proxy.host_key;
+            ssh.host_key_algorithms = ssh_proxy.host_key_algorithms;
+        }
+        ssh
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use super::CommonProxyOptions;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::Proxy;
+use crate::utils::is_empty_option_string;
+
+/// Represents a TUIC v5 proxy for Clash output configuration (mihomo extension)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TuicProxy {
+    #[serde(flatten)]
+    pub common: CommonProxyOptions,
+    pub uuid: String,
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alpn: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub congestion_controller: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub udp_relay_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_sni: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduce_rtt: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heartbeat_interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_timeout: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_udp_relay_packet_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fast_open: Option<bool>,
+}
+
+impl TuicProxy {
+    /// Create a new TUIC proxy
+    pub fn new(common: CommonProxyOptions) -> Self {
+        Self {
+            common,
+            uuid: String::new(),
+            password: String::new(),
+            alpn: None,
+            congestion_controller: None,
+            udp_relay_mode: None,
+            disable_sni: None,
+            reduce_rtt: None,
+            heartbeat_interval: None,
+            request_timeout: None,
+            max_udp_relay_packet_size: None,
+            fast_open: None,
+        }
+    }
+}
+
+impl From<Proxy> for TuicProxy {
+    fn from(proxy: Proxy) -> Self {
+        let common =
+            CommonProxyOptions::builder(proxy.remark.clone(), proxy.hostname.clone(), proxy.port)
+                .udp(proxy.udp)
+                .skip_cert_verify(proxy.allow_insecure)
+                .sni(proxy.sni.clone())
+                .build();
+
+        let mut tuic = TuicProxy::new(common);
+        if let Some(CombinedProxy::Tuic(tuic_proxy)) = proxy.combined_proxy {
+            tuic.uuid = tuic_proxy.uuid;
+            tuic.password = tuic_proxy.password;
+            tuic.congestion_controller = tuic_proxy.congestion_control;
+            tuic.udp_relay_mode = tuic_proxy.udp_relay_mode;
+            tuic.disable_sni = tuic_proxy.disable_sni;
+            tuic.reduce_rtt = tuic_proxy.reduce_rtt;
+            tuic.heartbeat_interval = tuic_proxy.heartbeat_interval;
+            tuic.request_timeout = tuic_proxy.request_timeout;
+            tuic.max_udp_relay_packet_size = tuic_proxy.max_udp_relay_packet_size;
+            tuic.fast_open = tuic_proxy.fast_open.or(proxy.tcp_fast_open);
+
+            if tuic_proxy.sni.is_some() {
+                tuic.common.sni = tuic_proxy.sni;
+            }
+            if tuic_proxy.skip_cert_verify.is_some() {
+                tuic.common.skip_cert_verify = tuic_proxy.skip_cert_verify;
+            }
+            if let Some(alpn) = tuic_proxy.alpn {
+                if !alpn.is_empty() {
+                    let mut alpn: Vec<String> = alpn.into_iter().collect();
+                    alpn.sort();
+                    tuic.alpn = Some(alpn);
+                }
+            }
+        }
+        tuic
+    }
+}
@@ -71,6 +71,49 @@ pub struct WSOptions {
     pub v2ray_http_upgrade_fast_open: Option<bool>,
 }
 
+/// XHTTP options for VLESS proxy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct XHTTPOptions {
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub host: Option<String>,
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_grpc_header: Option<bool>,
+    #[serde(skip_serializing_if = "is_empty_option_string")]
+    pub x_padding_bytes: Option<String>,
+}
+
+impl XHTTPOptions {
+    /// Build the options from the VLESS xhttp fields, reading the settings
+    /// mihomo understands out of the Xray `extra` object
+    fn from_vless(vless: &crate::models::proxy_node::vless::VlessProxy) -> Self {
+        let extra = vless
+            .xhttp_extra
+            .as_deref()
+            .and_then(|extra| serde_json::from_str::<serde_json::Value>(extra).ok())
+            .unwrap_or_default();
+
+        XHTTPOptions {
+            path: vless.xhttp_path.clone(),
+            host: vless.xhttp_host.clone(),
+            mode: vless.xhttp_mode.clone(),
+            headers: serde_json::from_value(extra["headers"].clone()).ok(),
+            no_grpc_header: extra["noGRPCHeader"].as_bool(),
+            x_padding_bytes: match &extra["xPaddingBytes"] {
+                serde_json::Value::String(bytes) => Some(bytes.clone()),
+                serde_json::Value::Number(bytes) => Some(bytes.to_string()),
+                _ => None,
+            },
+        }
+    }
+}
+
 /// VLESS proxy configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -102,6 +145,8 @@ pub struct VLessProxy {
     pub grpc_opts: Option<GrpcOptions>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ws_opts: Option<WSOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xhttp_opts: Option<XHTTPOptions>,
     #[serde(skip_serializing_if = "is_empty_option_string")]
     pub ws_path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -135,6 +180,7 @@ impl VLessProxy {
             h2_opts: None,
             grpc_opts: None,
             ws_opts: None,
+            xhttp_opts: None,
             ws_path: None,
             ws_headers: None,
             servername: None,
@@ -192,15 +238,18 @@ impl From<Proxy> for VLessProxy {
                 // 处理不同网络类型的特殊配置
                 if let Some(network) = &vless_proxy.network {
                     match network.as_str() {
-                        "ws" => {
+                        "ws" | "httpupgrade" => {
+                            // mihomo expresses HTTPUpgrade as a websocket option
+                            let http_upgrade = network == "httpupgrade";
                             let ws_opts = WSOptions {
                                 path: vless_proxy.ws_path.clone(),
                                 headers: vless_proxy.ws_headers.clone(),
                                 max_early_data: None,
                                 early_data_header_name: None,
-                                v2ray_http_upgrade: None,
+                                v2ray_http_upgrade: http_upgrade.then_some(true),
                                 v2ray_http_upgrade_fast_open: None,
                             };
+                            vless.network = Some("ws".to_string());
                             vless.ws_opts = Some(ws_opts);
                         }
                         "http" => {
@@ -224,6 +273,9 @@ impl From<Proxy> for VLessProxy {
                             };
                             vless.grpc_opts = Some(grpc_opts);
                         }
+                        "xhttp" => {
+                            vless.xhttp_opts = Some(XHTTPOptions::from_vless(vless_proxy));
+                        }
                         _ => {}
                     }
                 }
@@ -281,3 +333,59 @@ impl From<Proxy> for VLessProxy {
         vless
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::proxy_node::combined::CombinedProxy;
+    use crate::models::proxy_node::vless::VlessProxy;
+    use crate::models::ProxyType;
+
+    fn vless_node(vless: VlessProxy) -> Proxy {
+        Proxy {
+            proxy_type: ProxyType::Vless,
+            remark: "vless".to_string(),
+            hostname: "vless.example.com".to_string(),
+            port: 443,
+            combined_proxy: Some(CombinedProxy::Vless(vless)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_vless_xhttp_output() {
+        let node = vless_node(VlessProxy {
+            network: Some("xhttp".to_string()),
+            xhttp_path: Some("/xh".to_string()),
+            xhttp_host: Some("cdn.example.com".to_string()),
+            xhttp_mode: Some("auto".to_string()),
+            xhttp_extra: Some(r#"{"noGRPCHeader":true,"xPaddingBytes":"100-1000"}"#.to_string()),
+            ..Default::default()
+        });
+
+        let yaml = serde_yaml::to_value(VLessProxy::from(node)).unwrap();
+        assert_eq!(yaml["network"].as_str(), Some("xhttp"));
+        assert_eq!(yaml["xhttp-opts"]["path"].as_str(), Some("/xh"));
+        assert_eq!(yaml["xhttp-opts"]["host"].as_str(), Some("cdn.example.com"));
+        assert_eq!(yaml["xhttp-opts"]["mode"].as_str(), Some("auto"));
+        assert_eq!(yaml["xhttp-opts"]["no-grpc-header"].as_bool(), Some(true));
+        assert_eq!(
+            yaml["xhttp-opts"]["x-padding-bytes"].as_str(),
+            Some("100-1000")
+        );
+    }
+
+    #[test]
+    fn test_vless_httpupgrade_output() {
+        let node = vless_node(VlessProxy {
+            network: Some("httpupgrade".to_string()),
+            ws_path: Some("/up".to_string()),
+            ..Default::default()
+        });
+
+        let yaml = serde_yaml::to_value(VLessProxy::from(node)).unwrap();
+        assert_eq!(yaml["network"].as_str(), Some("ws"));
+        assert_eq!(yaml["ws-opts"]["path"].as_str(), Some("/up"));
+        assert_eq!(yaml["ws-opts"]["v2ray-http-upgrade"].as_bool(), Some(true));
+    }
+}
@@ -6,7 +6,9 @@ pub mod clash_output_shadowsocks;
 pub mod clash_output_shadowsocksr;
 pub mod clash_output_snell;
 pub mod clash_output_socks5;
+pub mod clash_output_ssh;
 pub mod clash_output_trojan;
+pub mod clash_output_tuic;
 pub mod clash_output_vless;
 pub mod clash_output_vmess;
 pub mod clash_output_wireguard;
@@ -19,7 +21,9 @@ pub use clash_output_shadowsocks::*;
 pub use clash_output_shadowsocksr::*;
 pub use clash_output_snell::*;
 pub use clash_output_socks5::*;
+pub use clash_output_ssh::SshProxy;
 pub use clash_output_trojan::TrojanProxy;
+pub use clash_output_tuic::TuicProxy;
 pub use clash_output_vless::VLessProxy;
 pub use clash_output_vmess::*;
 pub use clash_output_wireguard::*;
@@ -182,6 +182,14 @@ pub struct ClashProxyGroup {
     /// Whether to evaluate before use
     #[serde(rename = "evaluate-before-use", skip_serializing_if = "is_false")]
     pub evaluate_before_use: bool,
+
+    /// Regex selecting provider nodes to include (mihomo only)
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub filter: String,
+
+    /// Regex selecting provider nodes to exclude (mihomo only)
+    #[serde(rename = "exclude-filter", skip_serializing_if = "String::is_empty")]
+    pub exclude_filter: String,
 }
 
 // Helper functions for serde skip conditions
@@ -221,6 +229,8 @@ impl From<&ProxyGroupConfig> for ClashProxyGroup {
             disable_udp: config.disable_udp,
             persistent: config.persistent,
             evaluate_before_use: config.evaluate_before_use,
+            filter: config.filter.clone(),
+            exclude_filter: config.exclude_filter.clone(),
         };
 
         // Add type-specific fields
@@ -2,9 +2,11 @@ use crate::generator::config::formats::single::{proxy_to_single, ProxyUriTypes};
 use crate::generator::config::formats::ssd::proxy_to_ssd;
 use crate::generator::config::formats::{
     loon::proxy_to_loon, mellow::proxy_to_mellow, quan::proxy_to_quan, quanx::proxy_to_quanx,
-    singbox::proxy_to_singbox, ss_sub::proxy_to_ss_sub, surge::proxy_to_surge,
+    shadowrocket::proxy_to_shadowrocket, singbox::proxy_to_singbox, ss_sub::proxy_to_ss_sub,
+    surge::proxy_to_surge, xray::proxy_to_xray,
 };
 use crate::generator::exports::proxy_to_clash::proxy_to_clash;
+use crate::generator::exports::proxy_to_stash::proxy_to_stash;
 use crate::models::ruleset::RulesetConfigs;
 use crate::models::{
     ExtraSettings, Proxy, ProxyGroupConfigs, RegexMatchConfig, RulesetContent, SubconverterTarget,
@@ -15,6 +17,7 @@ use crate::rulesets::ruleset::refresh_rulesets;
 use crate::utils::file_get_async;
 use crate::utils::http::parse_proxy;
 use crate::utils::http::web_get_async;
+use crate::utils::url_encode;
 use crate::{Settings, TemplateArgs};
 use case_insensitive_string::CaseInsensitiveString;
 use log::{debug, error, info, warn};
@@ -31,8 +34,10 @@ pub struct RuleBases {
     pub quan_rule_base: String,
     pub quanx_rule_base: String,
     pub loon_rule_base: String,
+    pub shadowrocket_rule_base: String,
     pub sssub_rule_base: String,
     pub singbox_rule_base: String,
+    pub xray_rule_base: String,
 }
 
 /// Configuration for subconverter
@@ -338,6 +343,18 @@ impl SubconverterConfigBuilder {
         self
     }
 
+    /// Set the sing-box version whose configuration schema is emitted
+    pub fn singbox_version(&mut self, version: String) -> &mut Self {
+        self.config.extra.singbox_version = version;
+        self
+    }
+
+    /// Set whether to reference domain set rulesets as Surge `DOMAIN-SET` rules
+    pub fn surge_domain_set(&mut self, enable: bool) -> &mut Self {
+        self.config.extra.surge_domain_set = enable;
+        self
+    }
+
     /// Set whether to generate node list
     pub fn nodelist(&mut self, nodelist: bool) -> &mut Self {
         self.config.extra.nodelist = nodelist;
@@ -392,6 +409,13 @@ impl SubconverterConfigBuilder {
         self
     }
 
+    /// Set the prefix of `/getruleset` links that rulesets are referenced
+    /// through instead of being expanded inline
+    pub fn remote_ruleset_prefix(&mut self, prefix: String) -> &mut Self {
+        self.config.extra.managed_config_prefix = prefix;
+        self
+    }
+
     /// Set upload path
     pub fn upload_path(&mut self, path: Option<String>) -> &mut Self {
         self.config.upload_path = path;
@@ -481,6 +505,12 @@ impl SubconverterConfigBuilder {
         self
     }
 
+    /// Set rule base for Shadowrocket
+    pub fn shadowrocket_rule_base(&mut self, path: &str) -> &mut Self {
+        self.config.rule_bases.shadowrocket_rule_base = path.to_string();
+        self
+    }
+
     /// Set rule base for SS Subscription
     pub fn sssub_rule_base(&mut self, path: &str) -> &mut Self {
         self.config.rule_bases.sssub_rule_base = path.to_string();
@@ -493,6 +523,12 @@ impl SubconverterConfigBuilder {
         self
     }
 
+    /// Set rule base for Xray
+    pub fn xray_rule_base(&mut self, path: &str) -> &mut Self {
+        self.config.rule_bases.xray_rule_base = path.to_string();
+        self
+    }
+
     /// Set request headers
     pub fn request_headers(&mut self, headers: HashMap<String, String>) -> &mut Self {
         self.config.request_headers = Some(headers);
@@ -767,7 +803,7 @@ pub async fn subconverter(mut config: SubconverterConfig) -> Result<Subconverter
                 &base,
                 &mut ruleset_content,
                 &config.proxy_groups,
-                false,
+                &SubconverterTarget::Clash,
                 &mut config.extra,
             )
         }
@@ -782,10 +818,60 @@ pub async fn subconverter(mut config: SubconverterConfig) -> Result<Subconverter
                 &base,
                 &mut ruleset_content,
                 &config.proxy_groups,
-                true,
+                &SubconverterTarget::ClashR,
                 &mut config.extra,
             )
         }
+        SubconverterTarget::Mihomo => {
+            info!("Generate target: mihomo");
+            let base = config
+                .rule_bases
+                .get_base_content(&SubconverterTarget::Mihomo, config.template_args.as_ref())
+                .await;
+            proxy_to_clash(
+                &mut nodes,
+                &base,
+                &mut ruleset_content,
+                &config.proxy_groups,
+                &SubconverterTarget::Mihomo,
+                &mut config.extra,
+            )
+        }
+        SubconverterTarget::Stash => {
+            info!("Generate target: Stash");
+            let base = config
+                .rule_bases
+                .get_base_content(&SubconverterTarget::Stash, config.template_args.as_ref())
+                .await;
+            let output = proxy_to_stash(
+                &mut nodes,
+                &base,
+                &mut ruleset_content,
+                &config.proxy_groups,
+                &mut config.extra,
+            );
+
+            // Stash takes the update interval in whole hours from this header,
+            // a disabled interval leaves it out and one under half an hour
+            // still updates hourly
+            if config.update_interval > 0 {
+                let hours = (config.update_interval.saturating_add(1800) / 3600).max(1);
+                response_headers.insert("profile-update-interval".to_string(), hours.to_string());
+            }
+
+            // Add managed configuration header if needed
+            if !config.managed_config_prefix.is_empty() && config.extra.enable_rule_generator {
+                let managed_url = format!(
+                    "{}sub?target=stash&url={}",
+                    config.managed_config_prefix,
+                    url_encode(&config.urls.join("|"))
+                );
+
+                format!("#SUBSCRIBED {}\n{}", managed_url, output)
+            } else {
+                output
+            }
+        }
         SubconverterTarget::Surge(ver) => {
             info!("Generate target: Surge {}", ver);
             let base = config
@@ -904,7 +990,11 @@ pub async fn subconverter(mut config: SubconverterConfig) -> Result<Subconverter
         }
         SubconverterTarget::V2Ray => {
             info!("Generate target: V2Ray");
-            proxy_to_single(&mut nodes, ProxyUriTypes::VMESS, &mut config.extra)
+            proxy_to_single(
+                &mut nodes,
+                ProxyUriTypes::VMESS | ProxyUriTypes::VLESS,
+                &mut config.extra,
+            )
         }
         SubconverterTarget::Trojan => {
             info!("Generate target: Trojan");
@@ -959,6 +1049,21 @@ pub async fn subconverter(mut config: SubconverterConfig) -> Result<Subconverter
             )
             .await
         }
+        SubconverterTarget::Shadowrocket => {
+            info!("Generate target: Shadowrocket");
+            let base = config
+                .rule_bases
+                .get_base_content(&config.target, config.template_args.as_ref())
+                .await;
+            proxy_to_shadowrocket(
+                &mut nodes,
+                &base,
+                &mut ruleset_content,
+                &config.proxy_groups,
+                &mut config.extra,
+            )
+            .await
+        }
         SubconverterTarget::SSD => {
             info!("Generate target: SSD");
             proxy_to_ssd(
@@ -982,6 +1087,20 @@ pub async fn subconverter(mut config: SubconverterConfig) -> Result<Subconverter
                 &mut config.extra,
             )
         }
+        SubconverterTarget::Xray => {
+            info!("Generate target: Xray");
+            let base = config
+                .rule_bases
+                .get_base_content(&config.target, config.template_args.as_ref())
+                .await;
+            proxy_to_xray(
+                &mut nodes,
+                &base,
+                &mut ruleset_content,
+                &config.proxy_groups,
+                &mut config.extra,
+            )
+        }
         SubconverterTarget::Auto => {
             // When target is Auto, we should have decided on a specific target earlier
             // based on user agent If we still have Auto at this point, default
@@ -996,7 +1115,7 @@ pub async fn subconverter(mut config: SubconverterConfig) -> Result<Subconverter
                 &base,
                 &mut ruleset_content,
                 &config.proxy_groups,
-                false,
+                &SubconverterTarget::Clash,
                 &mut config.extra,
             )
         }
@@ -1018,6 +1137,8 @@ pub async fn subconverter(mut config: SubconverterConfig) -> Result<Subconverter
         let (gist_name, write_manage_url) = match &config.target {
             SubconverterTarget::Clash => ("clash".to_string(), false),
             SubconverterTarget::ClashR => ("clashr".to_string(), false),
+            SubconverterTarget::Mihomo => ("mihomo".to_string(), false),
+            SubconverterTarget::Stash => ("stash".to_string(), false),
             SubconverterTarget::Surge(ver) => {
                 let name = format!("surge{}", ver);
                 if config.extra.nodelist {
@@ -1037,8 +1158,10 @@ pub async fn subconverter(mut config: SubconverterConfig) -> Result<Subconverter
             SubconverterTarget::Quantumult => ("quan".to_string(), false),
             SubconverterTarget::QuantumultX => ("quanx".to_string(), false),
             SubconverterTarget::Loon => ("loon".to_string(), false),
+            SubconverterTarget::Shadowrocket => ("shadowrocket".to_string(), false),
             SubconverterTarget::SSD => ("ssd".to_string(), false),
             SubconverterTarget::SingBox => ("singbox".to_string(), false),
+            SubconverterTarget::Xray => ("xray".to_string(), false),
             SubconverterTarget::Auto => ("clash".to_string(), false), /* Defaulting to clash like
                                                                        * the main logic */
         };
@@ -1163,7 +1286,9 @@ impl RuleBases {
         // Load rule bases for each target format
         if let Some(content) = load_content(&self.clash_rule_base).await {
             base_content.insert(SubconverterTarget::Clash, content.clone());
-            base_content.insert(SubconverterTarget::ClashR, content);
+            base_content.insert(SubconverterTarget::ClashR, content.clone());
+            base_content.insert(SubconverterTarget::Mihomo, content.clone());
+            base_content.insert(SubconverterTarget::Stash, content);
         }
 
         if let Some(content) = load_content(&self.surge_rule_base).await {
@@ -1191,6 +1316,10 @@ impl RuleBases {
             base_content.insert(SubconverterTarget::Loon, content);
         }
 
+        if let Some(content) = load_content(&self.shadowrocket_rule_base).await {
+            base_content.insert(SubconverterTarget::Shadowrocket, content);
+        }
+
         if let Some(content) = load_content(&self.sssub_rule_base).await {
             base_content.insert(SubconverterTarget::SSSub, content);
         }
@@ -1199,6 +1328,10 @@ impl RuleBases {
             base_content.insert(SubconverterTarget::SingBox, content);
         }
 
+        if let Some(content) = load_content(&self.xray_rule_base).await {
+            base_content.insert(SubconverterTarget::Xray, content);
+        }
+
         base_content
     }
 
@@ -1251,15 +1384,20 @@ impl RuleBases {
 
         // Get path based on target
         let path = match target {
-            SubconverterTarget::Clash | SubconverterTarget::ClashR => &self.clash_rule_base,
+            SubconverterTarget::Clash
+            | SubconverterTarget::ClashR
+            | SubconverterTarget::Mihomo
+            | SubconverterTarget::Stash => &self.clash_rule_base,
             SubconverterTarget::Surge(_) => &self.surge_rule_base,
             SubconverterTarget::Surfboard => &self.surfboard_rule_base,
             SubconverterTarget::Mellow => &self.mellow_rule_base,
             SubconverterTarget::Quantumult => &self.quan_rule_base,
             SubconverterTarget::QuantumultX => &self.quanx_rule_base,
             SubconverterTarget::Loon => &self.loon_rule_base,
+            SubconverterTarget::Shadowrocket => &self.shadowrocket_rule_base,
             SubconverterTarget::SSSub => &self.sssub_rule_base,
             SubconverterTarget::SingBox => &self.singbox_rule_base,
+            SubconverterTarget::Xray => &self.xray_rule_base,
             _ => return String::new(),
         };
 
@@ -1340,6 +1478,12 @@ impl RuleBases {
             base_path,
         )
         .await;
+        Self::check_external_base(
+            &ext_conf.shadowrocket_rule_base,
+            &mut self.shadowrocket_rule_base,
+            base_path,
+        )
+        .await;
         Self::check_external_base(
             &ext_conf.sssub_rule_base,
             &mut self.sssub_rule_base,
@@ -1352,6 +1496,12 @@ impl RuleBases {
             base_path,
         )
         .await;
+        Self::check_external_base(
+            &ext_conf.xray_rule_base,
+            &mut self.xray_rule_base,
+            base_path,
+        )
+        .await;
     }
 
     /// Check if a path is a link or exists in the base path and update the
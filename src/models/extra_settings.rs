@@ -52,6 +52,10 @@ pub struct ExtraSettings {
     pub clash_proxies_style: String,
     /// Style for Clash proxy groups output
     pub clash_proxy_groups_style: String,
+    /// sing-box version whose configuration schema should be emitted
+    pub singbox_version: String,
+    /// Whether to reference domain set rulesets as Surge `DOMAIN-SET` rules
+    pub surge_domain_set: bool,
     /// Whether the export is authorized
     pub authorized: bool,
     /// JavaScript runtime context (not implemented in Rust version)
@@ -88,6 +92,8 @@ impl std::fmt::Debug for ExtraSettings {
             .field("sort_script", &self.sort_script)
             .field("clash_proxies_style", &self.clash_proxies_style)
             .field("clash_proxy_groups_style", &self.clash_proxy_groups_style)
+            .field("singbox_version", &self.singbox_version)
+            .field("surge_domain_set", &self.surge_domain_set)
             .field("authorized", &self.authorized)
             .finish()
     }
@@ -129,6 +135,8 @@ impl Default for ExtraSettings {
             } else {
                 global.clash_proxy_groups_style.clone()
             },
+            singbox_version: global.singbox_version.clone(),
+            surge_domain_set: false,
             authorized: false,
             #[cfg(feature = "js-runtime")]
             js_context: None,
@@ -75,7 +75,7 @@ pub use ruleset::{RulesetConfig, RulesetContent, RulesetType};
 // Default proxy group names
 pub use ciphers::{SSR_CIPHERS, SS_CIPHERS};
 pub use proxy::{
-    HTTP_DEFAULT_GROUP, HYSTERIA2_DEFAULT_GROUP, HYSTERIA_DEFAULT_GROUP, SNELL_DEFAULT_GROUP,
-    SOCKS_DEFAULT_GROUP, SSR_DEFAULT_GROUP, SS_DEFAULT_GROUP, TROJAN_DEFAULT_GROUP,
-    V2RAY_DEFAULT_GROUP, WG_DEFAULT_GROUP,
+    ANYTLS_DEFAULT_GROUP, HTTP_DEFAULT_GROUP, HYSTERIA2_DEFAULT_GROUP, HYSTERIA_DEFAULT_GROUP, SNELL_DEFAULT_GROUP,
+    SOCKS_DEFAULT_GROUP, SSH_DEFAULT_GROUP, SSR_DEFAULT_GROUP, SS_DEFAULT_GROUP,
+    TROJAN_DEFAULT_GROUP, TUIC_DEFAULT_GROUP, V2RAY_DEFAULT_GROUP, WG_DEFAULT_GROUP,
 };
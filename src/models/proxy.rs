@@ -28,6 +28,8 @@ pub enum ProxyType {
     // new proxy types could be added as enum combined proxy types
     Vless,
     AnyTls,
+    Tuic,
+    Ssh,
 }
 
 /// Converts a `ProxyType` into a human-readable name.
@@ -47,6 +49,8 @@ impl ProxyType {
             ProxyType::Hysteria2 => "Hysteria2",
             ProxyType::Vless => "Vless",
             ProxyType::AnyTls => "AnyTLS",
+            ProxyType::Tuic => "TUIC",
+            ProxyType::Ssh => "SSH",
             ProxyType::Unknown => "Unknown",
         }
     }
@@ -219,7 +223,7 @@ impl Proxy {
     pub fn is_combined_proxy(&self) -> bool {
         matches!(
             self.proxy_type,
-            ProxyType::Vless | ProxyType::Shadowsocks | ProxyType::AnyTls
+            ProxyType::Vless | ProxyType::Shadowsocks | ProxyType::AnyTls | ProxyType::Tuic
         )
     }
 
@@ -305,3 +309,6 @@ pub const SNELL_DEFAULT_GROUP: &str = "SnellProvider";
 pub const WG_DEFAULT_GROUP: &str = "WireGuardProvider";
 pub const HYSTERIA_DEFAULT_GROUP: &str = "HysteriaProvider";
 pub const HYSTERIA2_DEFAULT_GROUP: &str = "Hysteria2Provider";
+pub const TUIC_DEFAULT_GROUP: &str = "TUICProvider";
+pub const SSH_DEFAULT_GROUP: &str = "SSHProvider";
+pub const ANYTLS_DEFAULT_GROUP: &str = "AnyTLSProvider";
@@ -71,6 +71,10 @@ pub struct ProxyGroupConfig {
     pub persistent: bool,
     /// Whether to evaluate before use
     pub evaluate_before_use: bool,
+    /// Regex selecting provider nodes to include (mihomo `filter`)
+    pub filter: String,
+    /// Regex selecting provider nodes to exclude (mihomo `exclude-filter`)
+    pub exclude_filter: String,
 }
 
 impl Default for ProxyGroupConfig {
@@ -89,6 +93,8 @@ impl Default for ProxyGroupConfig {
             disable_udp: false,
             persistent: false,
             evaluate_before_use: false,
+            filter: String::new(),
+            exclude_filter: String::new(),
         }
     }
 }
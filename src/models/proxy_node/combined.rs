@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 
 use super::anytls::AnyTlsProxy;
 use super::shadowsocks::ShadowsocksProxy;
+use super::ssh::SshProxy;
+use super::tuic::TuicProxy;
 use super::vless::VlessProxy;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,4 +12,6 @@ pub enum CombinedProxy {
     Vless(VlessProxy),
     Shadowsocks(ShadowsocksProxy),
     AnyTls(AnyTlsProxy),
+    Tuic(TuicProxy),
+    Ssh(SshProxy),
 }
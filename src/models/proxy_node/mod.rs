@@ -1,4 +1,6 @@
 pub mod anytls;
 pub mod combined;
 pub mod shadowsocks;
+pub mod ssh;
+pub mod tuic;
 pub mod vless;
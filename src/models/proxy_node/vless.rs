@@ -22,6 +22,11 @@ pub struct VlessProxy {
     pub grpc_service_name: Option<String>,
     pub ws_path: Option<String>,
     pub ws_headers: Option<HashMap<String, String>>,
+    pub xhttp_path: Option<String>,
+    pub xhttp_host: Option<String>,
+    pub xhttp_mode: Option<String>,
+    /// Raw Xray `extra` object of the xhttp transport, serialized as JSON
+    pub xhttp_extra: Option<String>,
     pub skip_cert_verify: Option<bool>,
     pub fingerprint: Option<String>,
     pub servername: Option<String>,
@@ -50,6 +55,10 @@ impl Default for VlessProxy {
             grpc_service_name: None,
             ws_path: None,
             ws_headers: None,
+            xhttp_path: None,
+            xhttp_host: None,
+            xhttp_mode: None,
+            xhttp_extra: None,
             skip_cert_verify: None,
             fingerprint: None,
             servername: None,
@@ -11,6 +11,11 @@ pub enum RulesetType {
     ClashDomain,
     ClashIpcidr,
     ClashClassical,
+    SingBox,
+    DomainSet,
+    AdBlock,
+    Hosts,
+    Dnsmasq,
 }
 
 impl Default for RulesetType {
@@ -19,6 +24,20 @@ impl Default for RulesetType {
     }
 }
 
+impl RulesetType {
+    /// Returns true for plain domain lists, which convert to nothing but
+    /// `DOMAIN` and `DOMAIN-SUFFIX` rules
+    pub fn is_domain_list(&self) -> bool {
+        matches!(
+            self,
+            RulesetType::DomainSet
+                | RulesetType::AdBlock
+                | RulesetType::Hosts
+                | RulesetType::Dnsmasq
+        )
+    }
+}
+
 /// Mapping from URL prefix to ruleset type
 pub type RulesetMapping = HashMap<String, RulesetType>;
 
@@ -31,6 +50,11 @@ pub static RULESET_TYPES: once_cell::sync::Lazy<RulesetMapping> =
         types.insert("clash-classical:".to_string(), RulesetType::ClashClassical);
         types.insert("quanx:".to_string(), RulesetType::Quanx);
         types.insert("surge:".to_string(), RulesetType::Surge);
+        types.insert("singbox:".to_string(), RulesetType::SingBox);
+        types.insert("domainset:".to_string(), RulesetType::DomainSet);
+        types.insert("adblock:".to_string(), RulesetType::AdBlock);
+        types.insert("hosts:".to_string(), RulesetType::Hosts);
+        types.insert("dnsmasq:".to_string(), RulesetType::Dnsmasq);
         types
     });
 
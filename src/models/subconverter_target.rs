@@ -6,6 +6,8 @@ pub enum SubconverterTarget {
     Auto,
     Clash,
     ClashR,
+    Mihomo,
+    Stash,
     Surge(i32), // Surge version as parameter
     Surfboard,
     Mellow,
@@ -18,8 +20,10 @@ pub enum SubconverterTarget {
     Quantumult,
     QuantumultX,
     Loon,
+    Shadowrocket,
     SSD,
     SingBox,
+    Xray,
 }
 
 impl SubconverterTarget {
@@ -29,6 +33,8 @@ impl SubconverterTarget {
             "auto" => Some(SubconverterTarget::Auto),
             "clash" => Some(SubconverterTarget::Clash),
             "clashr" => Some(SubconverterTarget::ClashR),
+            "mihomo" | "clash.meta" | "clashmeta" | "meta" => Some(SubconverterTarget::Mihomo),
+            "stash" => Some(SubconverterTarget::Stash),
             "surge" => Some(SubconverterTarget::Surge(3)), // Default to Surge 3
             "surfboard" => Some(SubconverterTarget::Surfboard),
             "mellow" => Some(SubconverterTarget::Mellow),
@@ -43,8 +49,8 @@ impl SubconverterTarget {
             "loon" => Some(SubconverterTarget::Loon),
             "ssd" => Some(SubconverterTarget::SSD),
             "singbox" => Some(SubconverterTarget::SingBox),
-            // Map shadowrocket to Mixed
-            "shadowrocket" => Some(SubconverterTarget::Mixed),
+            "xray" => Some(SubconverterTarget::Xray),
+            "shadowrocket" => Some(SubconverterTarget::Shadowrocket),
             // Map surfboardios to regular Surfboard
             "surfboardios" => Some(SubconverterTarget::Surfboard),
             _ => None,
@@ -57,6 +63,8 @@ impl SubconverterTarget {
             SubconverterTarget::Auto => "auto".to_string(),
             SubconverterTarget::Clash => "clash".to_string(),
             SubconverterTarget::ClashR => "clashr".to_string(),
+            SubconverterTarget::Mihomo => "mihomo".to_string(),
+            SubconverterTarget::Stash => "stash".to_string(),
             SubconverterTarget::Surge(ver) => format!("surge{}", ver),
             SubconverterTarget::Surfboard => "surfboard".to_string(),
             SubconverterTarget::Mellow => "mellow".to_string(),
@@ -69,13 +77,21 @@ impl SubconverterTarget {
             SubconverterTarget::Quantumult => "quan".to_string(),
             SubconverterTarget::QuantumultX => "quanx".to_string(),
             SubconverterTarget::Loon => "loon".to_string(),
+            SubconverterTarget::Shadowrocket => "shadowrocket".to_string(),
             SubconverterTarget::SSD => "ssd".to_string(),
             SubconverterTarget::SingBox => "singbox".to_string(),
+            SubconverterTarget::Xray => "xray".to_string(),
         }
     }
 
     pub fn is_clash(&self) -> bool {
-        matches!(self, SubconverterTarget::Clash | SubconverterTarget::ClashR)
+        matches!(
+            self,
+            SubconverterTarget::Clash
+                | SubconverterTarget::ClashR
+                | SubconverterTarget::Mihomo
+                | SubconverterTarget::Stash
+        )
     }
 
     /// Returns true if the target represents a simple type (e.g. ss, ssr, trojan)
@@ -93,7 +109,10 @@ impl SubconverterTarget {
     /// Gets the base content for this target from the external config
     pub fn get_base_content_from_external(&self, external: &ExternalSettings) -> Option<String> {
         match self {
-            SubconverterTarget::Clash | SubconverterTarget::ClashR => {
+            SubconverterTarget::Clash
+            | SubconverterTarget::ClashR
+            | SubconverterTarget::Mihomo
+            | SubconverterTarget::Stash => {
                 if !external.clash_rule_base.is_empty() {
                     Some(external.clash_rule_base.clone())
                 } else {
@@ -142,6 +161,13 @@ impl SubconverterTarget {
                     None
                 }
             }
+            SubconverterTarget::Shadowrocket => {
+                if !external.shadowrocket_rule_base.is_empty() {
+                    Some(external.shadowrocket_rule_base.clone())
+                } else {
+                    None
+                }
+            }
             SubconverterTarget::SSSub => {
                 if !external.sssub_rule_base.is_empty() {
                     Some(external.sssub_rule_base.clone())
@@ -156,6 +182,13 @@ impl SubconverterTarget {
                     None
                 }
             }
+            SubconverterTarget::Xray => {
+                if !external.xray_rule_base.is_empty() {
+                    Some(external.xray_rule_base.clone())
+                } else {
+                    None
+                }
+            }
             _ => None,
         }
     }
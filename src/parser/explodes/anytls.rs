@@ -0,0 +1,144 @@
+use crate::models::proxy_node::anytls::AnyTlsProxy;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::{Proxy, ProxyType, ANYTLS_DEFAULT_GROUP};
+use crate::utils::url_decode;
+use std::collections::{HashMap, HashSet};
+use url::Url;
+
+/// Parse an AnyTLS link into a Proxy object
+///
+/// Format: anytls://password@server:port?sni=example.com&insecure=1&alpn=h2#remark
+pub fn explode_anytls(anytls: &str, node: &mut Proxy) -> bool {
+    // Check if the link starts with anytls://
+    if !anytls.starts_with("anytls://") {
+        return false;
+    }
+
+    // Try to parse as URL
+    let url = match Url::parse(anytls) {
+        Ok(url) => url,
+        Err(_) => return false,
+    };
+
+    // Extract parameters from the query string
+    let mut params = HashMap::new();
+    for (key, value) in url.query_pairs() {
+        params.insert(key.to_string(), url_decode(&value));
+    }
+
+    let password = url_decode(url.username());
+    if password.is_empty() {
+        return false;
+    }
+
+    // Extract host and port
+    let host = match url.host_str() {
+        Some(host) => host.trim_start_matches('[').trim_end_matches(']'),
+        None => return false,
+    };
+    let port = url.port().unwrap_or(443);
+
+    let alpn = params.get("alpn").map(|s| {
+        s.split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<HashSet<_>>()
+    });
+
+    let anytls_proxy = AnyTlsProxy {
+        password,
+        alpn,
+        sni: params.get("sni").or_else(|| params.get("peer")).cloned(),
+        skip_cert_verify: params
+            .get("insecure")
+            .or_else(|| params.get("allowInsecure"))
+            .map(|v| v == "1" || v.to_lowercase() == "true"),
+        client_fingerprint: params.get("fp").cloned(),
+        ..Default::default()
+    };
+
+    // Extract remark from the fragment
+    let remark = url_decode(url.fragment().unwrap_or(""));
+
+    node.proxy_type = ProxyType::AnyTls;
+    node.group = ANYTLS_DEFAULT_GROUP.to_string();
+    node.remark = if remark.is_empty() {
+        format!("{} ({})", host, port)
+    } else {
+        remark
+    };
+    node.hostname = host.to_string();
+    node.port = port;
+    node.sni = anytls_proxy.sni.clone();
+    node.allow_insecure = anytls_proxy.skip_cert_verify;
+    node.combined_proxy = Some(CombinedProxy::AnyTls(anytls_proxy));
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::single::{proxy_to_single, ProxyUriTypes};
+    use crate::models::ExtraSettings;
+
+    fn anytls_of(node: &Proxy) -> &AnyTlsProxy {
+        match node.combined_proxy {
+            Some(CombinedProxy::AnyTls(ref anytls)) => anytls,
+            _ => panic!("expected an AnyTLS combined proxy"),
+        }
+    }
+
+    #[test]
+    fn test_explode_anytls_valid_link() {
+        let link = "anytls://p%40ss@anytls.example.com:8443?sni=sni.example.com&insecure=1&alpn=h2,http/1.1&fp=chrome#AnyTLS%20Node";
+        let mut node = Proxy::default();
+
+        assert!(explode_anytls(link, &mut node));
+        assert_eq!(node.proxy_type, ProxyType::AnyTls);
+        assert_eq!(node.group, ANYTLS_DEFAULT_GROUP);
+        assert_eq!(node.remark, "AnyTLS Node");
+        assert_eq!(node.hostname, "anytls.example.com");
+        assert_eq!(node.port, 8443);
+        assert_eq!(node.allow_insecure, Some(true));
+
+        let anytls = anytls_of(&node);
+        assert_eq!(anytls.password, "p@ss");
+        assert_eq!(anytls.sni.as_deref(), Some("sni.example.com"));
+        assert_eq!(anytls.client_fingerprint.as_deref(), Some("chrome"));
+        assert!(anytls.alpn.as_ref().unwrap().contains("http/1.1"));
+    }
+
+    #[test]
+    fn test_explode_anytls_missing_password() {
+        let mut node = Proxy::default();
+        assert!(!explode_anytls(
+            "anytls://anytls.example.com:443",
+            &mut node
+        ));
+    }
+
+    #[test]
+    fn test_anytls_link_round_trip() {
+        let link = "anytls://secret@anytls.example.com:443?sni=anytls.example.com&insecure=1&alpn=h2&fp=firefox#Round%20Trip";
+        let mut node = Proxy::default();
+        assert!(explode_anytls(link, &mut node));
+
+        let mut ext = ExtraSettings::default();
+        ext.nodelist = true;
+        let output = proxy_to_single(&mut vec![node.clone()], ProxyUriTypes::ANYTLS, &mut ext);
+
+        let mut reparsed = Proxy::default();
+        assert!(explode_anytls(output.trim(), &mut reparsed));
+        assert_eq!(reparsed.remark, node.remark);
+        assert_eq!(reparsed.hostname, node.hostname);
+        assert_eq!(reparsed.port, node.port);
+
+        let (before, after) = (anytls_of(&node), anytls_of(&reparsed));
+        assert_eq!(after.password, before.password);
+        assert_eq!(after.sni, before.sni);
+        assert_eq!(after.alpn, before.alpn);
+        assert_eq!(after.skip_cert_verify, before.skip_cert_verify);
+        assert_eq!(after.client_fingerprint, before.client_fingerprint);
+    }
+}
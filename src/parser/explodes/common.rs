@@ -1,4 +1,4 @@
-use crate::utils::base64::url_safe_base64_decode;
+use crate::utils::base64::url_safe_base64_decode_lenient;
 use crate::Proxy;
 
 /// Explode a proxy link into a Proxy object
@@ -45,8 +45,7 @@ pub fn explode(link: &str, node: &mut Proxy) -> bool {
     } else if link.starts_with("ss://") {
         super::ss::explode_ss(link, node)
     } else if link.starts_with("ssr://") {
-        // super::ssr::explode_ssr(link, node)
-        false
+        super::ssr::explode_ssr(link, node)
     } else if link.starts_with("socks://")
         || link.starts_with("https://t.me/socks")
         || link.starts_with("tg://socks")
@@ -71,10 +70,13 @@ pub fn explode(link: &str, node: &mut Proxy) -> bool {
     } else if link.starts_with("hysteria2://") || link.starts_with("hy2://") {
         super::hysteria2::explode_hysteria2(link, node)
     } else if link.starts_with("vmess+") {
-        false
-        // super::vmess::explode_std_vmess(link, node)
+        super::vmess::explode_std_vmess(link, node)
     } else if link.starts_with("vless://") {
         super::vless::explode_vless(link, node)
+    } else if link.starts_with("tuic://") {
+        super::tuic::explode_tuic(link, node)
+    } else if link.starts_with("anytls://") {
+        super::anytls::explode_anytls(link, node)
     } else {
         false
     }
@@ -102,6 +104,15 @@ pub fn explode_sub(sub: &str, nodes: &mut Vec<Proxy>) -> bool {
         }
     }
 
+    // Try to parse as sing-box configuration
+    if !processed
+        && sub.starts_with('{')
+        && sub.contains("\"outbounds\"")
+        && super::singbox::explode_singbox(sub, nodes)
+    {
+        processed = true;
+    }
+
     // Try to parse as Clash configuration
     if !processed
         && (sub.contains("\"Proxy\":")
@@ -114,23 +125,35 @@ pub fn explode_sub(sub: &str, nodes: &mut Vec<Proxy>) -> bool {
         }
     }
 
+    // Try to parse as Loon configuration
+    if !processed && super::loon::explode_loon(sub, nodes) {
+        processed = true;
+    }
+
     // Try to parse as Surge configuration
     if !processed && super::surge::explode_surge(sub, nodes) {
         processed = true;
     }
 
+    // Try to parse as Quantumult X configuration
+    if !processed && super::quanx::explode_quanx(sub, nodes) {
+        processed = true;
+    }
+
     // If no specific format was detected, try as a normal subscription
     if !processed {
         // Try to decode as base64
-        let decoded = url_safe_base64_decode(sub);
+        let decoded = url_safe_base64_decode_lenient(sub);
 
-        // Check if it's a Surge format after decoding
+        // Check if it's a Quantumult X format after decoding
         if decoded.contains("vmess=")
+            || decoded.contains("vless=")
             || decoded.contains("shadowsocks=")
             || decoded.contains("http=")
+            || decoded.contains("socks5=")
             || decoded.contains("trojan=")
         {
-            if super::surge::explode_surge(&decoded, nodes) {
+            if super::quanx::explode_quanx(&decoded, nodes) {
                 return true;
             }
         }
@@ -189,8 +212,11 @@ pub fn explode_conf_content(content: &str, nodes: &mut Vec<Proxy>) -> i32 {
 
     // Try to parse as JSON
     if content.starts_with('{') {
-        // Try to parse as V2Ray configuration
-        if super::vmess::explode_vmess_conf(content, nodes) {
+        // Try to parse as Xray/V2Ray client, V2Ray GUI or sing-box configuration
+        if super::xray::explode_xray_conf(content, nodes)
+            || super::vmess::explode_vmess_conf(content, nodes)
+            || super::singbox::explode_singbox(content, nodes)
+        {
             parsed = true;
         }
         // Try Netch configuration
@@ -218,9 +244,16 @@ pub fn explode_conf_content(content: &str, nodes: &mut Vec<Proxy>) -> i32 {
             parsed = true;
         }
     }
-    // Try to parse as Surge configuration
+    // Try to parse as Loon or Surge configuration
     else if content.contains("[Proxy]") {
-        if super::surge::explode_surge(content, nodes) {
+        if super::loon::explode_loon(content, nodes) || super::surge::explode_surge(content, nodes)
+        {
+            parsed = true;
+        }
+    }
+    // Try to parse as Quantumult X configuration
+    else if content.contains("[server_local]") || content.contains("[server_remote]") {
+        if super::quanx::explode_quanx(content, nodes) {
             parsed = true;
         }
     }
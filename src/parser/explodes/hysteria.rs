@@ -63,11 +63,11 @@ pub fn explode_hysteria(hysteria: &str, node: &mut Proxy) -> bool {
         .unwrap_or(false);
 
     // Extract remark from the fragment
-    let remark = url.fragment().unwrap_or("");
+    let remark = url_decode(url.fragment().unwrap_or(""));
     let formatted_remark = if remark.is_empty() {
         format!("{} ({})", host, port)
     } else {
-        remark.to_string()
+        remark
     };
 
     // Create the proxy object using the hysteria_construct method
@@ -99,3 +99,39 @@ pub fn explode_hysteria(hysteria: &str, node: &mut Proxy) -> bool {
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::single::{proxy_to_single, ProxyUriTypes};
+    use crate::models::{ExtraSettings, ProxyType};
+
+    #[test]
+    fn test_hysteria_link_round_trip() {
+        let link = "hysteria://hy.example.com:443?protocol=wechat-video&auth=p%40ss&peer=sni.example.com\
+                    &insecure=1&upmbps=20&downmbps=100&alpn=hysteria&obfs=xplus&obfsParam=secret#Hysteria%20Node";
+        let mut node = Proxy::default();
+        assert!(explode_hysteria(link, &mut node));
+        assert_eq!(node.remark, "Hysteria Node");
+
+        let mut ext = ExtraSettings::default();
+        ext.nodelist = true;
+        let output = proxy_to_single(&mut vec![node.clone()], ProxyUriTypes::HYSTERIA, &mut ext);
+
+        let mut reparsed = Proxy::default();
+        assert!(explode_hysteria(output.trim(), &mut reparsed));
+        assert_eq!(reparsed.proxy_type, ProxyType::Hysteria);
+        assert_eq!(reparsed.remark, node.remark);
+        assert_eq!(reparsed.hostname, node.hostname);
+        assert_eq!(reparsed.port, node.port);
+        assert_eq!(reparsed.protocol.as_deref(), Some("wechat-video"));
+        assert_eq!(reparsed.auth_str.as_deref(), Some("p@ss"));
+        assert_eq!(reparsed.sni, node.sni);
+        assert_eq!(reparsed.allow_insecure, Some(true));
+        assert_eq!(reparsed.up_speed, 20);
+        assert_eq!(reparsed.down_speed, 100);
+        assert_eq!(reparsed.alpn, node.alpn);
+        assert_eq!(reparsed.obfs, node.obfs);
+        assert_eq!(reparsed.obfs_param.as_deref(), Some("secret"));
+    }
+}
@@ -19,7 +19,7 @@ pub fn explode_hysteria2(hysteria2: &str, node: &mut Proxy) -> bool {
     let port = url.port().unwrap_or(443);
 
     // Extract password (username in URL)
-    let password = url.username();
+    let password = url_decode(url.username());
 
     // Extract parameters from the query string
     let mut up_speed = None;
@@ -46,7 +46,7 @@ pub fn explode_hysteria2(hysteria2: &str, node: &mut Proxy) -> bool {
                 allow_insecure =
                     Some(value.as_ref() == "1" || value.as_ref().to_lowercase() == "true")
             }
-            "fingerprint" => fingerprint = url_decode(&value),
+            "fingerprint" | "pinSHA256" => fingerprint = url_decode(&value),
             "ca" => ca = url_decode(&value),
             "caStr" => ca_str = url_decode(&value),
             "ports" => ports = url_decode(&value),
@@ -80,7 +80,7 @@ pub fn explode_hysteria2(hysteria2: &str, node: &mut Proxy) -> bool {
         Some(ports),
         up_speed,
         down_speed,
-        password.to_string(),
+        password,
         Some(obfs),
         Some(obfs_param),
         Some(sni),
@@ -198,3 +198,38 @@ pub fn explode_std_hysteria2(hysteria2: &str, node: &mut Proxy) -> bool {
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::single::{proxy_to_single, ProxyUriTypes};
+    use crate::models::{ExtraSettings, ProxyType};
+
+    #[test]
+    fn test_hysteria2_link_round_trip() {
+        let link = "hysteria2://p%40ss@hy2.example.com:443?sni=sni.example.com&insecure=1&obfs=salamander\
+                    &obfs-password=secret&mport=20000-30000&alpn=h3&pinSHA256=deadbeef#Hysteria2%20Node";
+        let mut node = Proxy::default();
+        assert!(explode_hysteria2(link, &mut node));
+        assert_eq!(node.password.as_deref(), Some("p@ss"));
+
+        let mut ext = ExtraSettings::default();
+        ext.nodelist = true;
+        let output = proxy_to_single(&mut vec![node.clone()], ProxyUriTypes::HYSTERIA2, &mut ext);
+
+        let mut reparsed = Proxy::default();
+        assert!(explode_hysteria2(output.trim(), &mut reparsed));
+        assert_eq!(reparsed.proxy_type, ProxyType::Hysteria2);
+        assert_eq!(reparsed.remark, "Hysteria2 Node");
+        assert_eq!(reparsed.hostname, node.hostname);
+        assert_eq!(reparsed.port, node.port);
+        assert_eq!(reparsed.password, node.password);
+        assert_eq!(reparsed.sni, node.sni);
+        assert_eq!(reparsed.allow_insecure, Some(true));
+        assert_eq!(reparsed.obfs.as_deref(), Some("salamander"));
+        assert_eq!(reparsed.obfs_param.as_deref(), Some("secret"));
+        assert_eq!(reparsed.ports.as_deref(), Some("20000-30000"));
+        assert_eq!(reparsed.alpn, node.alpn);
+        assert_eq!(reparsed.fingerprint.as_deref(), Some("deadbeef"));
+    }
+}
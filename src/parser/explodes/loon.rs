@@ -0,0 +1,796 @@
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::vless::VlessProxy;
+use crate::models::{
+    Proxy, ProxyType, HTTP_DEFAULT_GROUP, HYSTERIA2_DEFAULT_GROUP, SOCKS_DEFAULT_GROUP,
+    SSR_DEFAULT_GROUP, SS_DEFAULT_GROUP, TROJAN_DEFAULT_GROUP, V2RAY_DEFAULT_GROUP,
+    WG_DEFAULT_GROUP,
+};
+use std::collections::HashMap;
+
+/// Parse a Loon configuration into a vector of Proxy objects
+///
+/// Only contents that look like a Loon configuration are handled, so Surge
+/// configurations (which share the `[Proxy]` section) are left to
+/// `explode_surge`.
+pub fn explode_loon(content: &str, nodes: &mut Vec<Proxy>) -> bool {
+    if !is_loon_conf(content) {
+        return false;
+    }
+
+    let mut success = false;
+
+    for (name, config) in section_entries(content, "[Proxy]") {
+        let params = split_params(config);
+        let mut node = Proxy::default();
+
+        let parsed = match params[0].to_lowercase().as_str() {
+            "shadowsocks" | "ss" => parse_loon_ss(&params, name, &mut node),
+            "shadowsocksr" | "ssr" => parse_loon_ssr(&params, name, &mut node),
+            "vmess" => parse_loon_vmess(&params, name, &mut node),
+            "vless" => parse_loon_vless(&params, name, &mut node),
+            "trojan" => parse_loon_trojan(&params, name, &mut node),
+            "http" | "https" => parse_loon_http(&params, name, &mut node),
+            "socks5" => parse_loon_socks(&params, name, &mut node),
+            "wireguard" => parse_loon_wireguard(&params, name, &mut node),
+            "hysteria2" => parse_loon_hysteria2(&params, name, &mut node),
+            _ => false,
+        };
+
+        if parsed {
+            nodes.push(node);
+            success = true;
+        }
+    }
+
+    success
+}
+
+/// Extract the `[Remote Proxy]` references of a Loon configuration
+///
+/// Returns (name, url) pairs in declaration order. Disabled entries
+/// (`enabled=false`) are skipped.
+pub fn extract_loon_remote_proxies(content: &str) -> Vec<(String, String)> {
+    let mut remotes = Vec::new();
+
+    for (name, config) in section_entries(content, "[Remote Proxy]") {
+        let params = split_params(config);
+        let url = unquote(&params[0]);
+        if url.is_empty() {
+            continue;
+        }
+
+        let enabled = params[1..]
+            .iter()
+            .filter_map(|param| split_key_value(param))
+            .all(|(key, value)| key != "enabled" || parse_bool(&value));
+        if enabled {
+            remotes.push((name.to_string(), url));
+        }
+    }
+
+    remotes
+}
+
+/// Check whether a content is a Loon configuration rather than a Surge one
+fn is_loon_conf(content: &str) -> bool {
+    if content.lines().any(|line| line.trim() == "[Remote Proxy]") {
+        return true;
+    }
+
+    section_entries(content, "[Proxy]")
+        .into_iter()
+        .any(|(_, config)| {
+            let params = split_params(config);
+            match params[0].to_lowercase().as_str() {
+                // Types Surge does not have
+                "shadowsocksr" | "vless" => true,
+                // Loon passes credentials positionally, Surge uses key=value
+                "shadowsocks" | "vmess" | "trojan" | "hysteria2" => {
+                    params.len() > 3 && !params[3].contains('=')
+                }
+                "wireguard" => params.iter().any(|p| p.starts_with("interface-ip")),
+                _ => false,
+            }
+        })
+}
+
+/// Collect the `name = config` lines of a section
+fn section_entries<'a>(content: &'a str, section: &str) -> Vec<(&'a str, &'a str)> {
+    let mut entries = Vec::new();
+    let mut in_section = false;
+
+    for line in content.lines() {
+        // Skip empty lines and comments
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        // Check section headers
+        if line.starts_with('[') && line.ends_with(']') {
+            in_section = line == section;
+            continue;
+        }
+
+        if !in_section {
+            continue;
+        }
+
+        if let Some((name, config)) = line.split_once('=') {
+            let name = name.trim();
+            let config = config.trim();
+            if !name.is_empty() && !config.is_empty() {
+                entries.push((name, config));
+            }
+        }
+    }
+
+    entries
+}
+
+/// Split a Loon parameter list by commas, keeping quoted strings and
+/// bracketed values (such as WireGuard peers) intact
+fn split_params(config: &str) -> Vec<String> {
+    let mut params = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut depth = 0;
+
+    for c in config.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '[' | '{' if !in_quotes => depth += 1,
+            ']' | '}' if !in_quotes => depth -= 1,
+            ',' if !in_quotes && depth == 0 => {
+                params.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    params.push(current.trim().to_string());
+
+    params
+}
+
+/// Split a `key=value` parameter, with the value unquoted
+fn split_key_value(param: &str) -> Option<(String, String)> {
+    let (key, value) = param.split_once('=')?;
+    Some((key.trim().to_lowercase(), unquote(value)))
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
+
+fn parse_bool(value: &str) -> bool {
+    value == "true" || value == "1"
+}
+
+fn parse_port(value: &str) -> Option<u16> {
+    value.parse::<u16>().ok().filter(|port| *port != 0)
+}
+
+/// Options shared by most Loon proxy types
+#[derive(Debug, Default)]
+struct LoonOptions {
+    params: HashMap<String, String>,
+}
+
+impl LoonOptions {
+    fn new(params: &[String]) -> Self {
+        LoonOptions {
+            params: params
+                .iter()
+                .filter_map(|param| split_key_value(param))
+                .collect(),
+        }
+    }
+
+    fn get(&self, key: &str) -> &str {
+        self.params.get(key).map(|s| s.as_str()).unwrap_or("")
+    }
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        self.params.get(key).map(|value| parse_bool(value))
+    }
+
+    /// TLS server name, Loon accepts both `sni` and `tls-name`
+    fn sni(&self) -> &str {
+        match self.get("sni") {
+            "" => self.get("tls-name"),
+            sni => sni,
+        }
+    }
+
+    fn udp(&self) -> Option<bool> {
+        self.get_bool("udp")
+    }
+
+    fn tfo(&self) -> Option<bool> {
+        self.get_bool("fast-open")
+    }
+
+    fn scv(&self) -> Option<bool> {
+        self.get_bool("skip-cert-verify")
+    }
+}
+
+/// Parse a Loon Shadowsocks line
+/// `Shadowsocks,server,port,method,"password",obfs-name=http,obfs-host=host`
+fn parse_loon_ss(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    if params.len() < 5 {
+        return false;
+    }
+    let port = match parse_port(&params[2]) {
+        Some(port) => port,
+        None => return false,
+    };
+    let options = LoonOptions::new(&params[5..]);
+
+    let mut obfs = options.get("obfs-name").to_string();
+    let mut obfs_host = options.get("obfs-host").to_string();
+    // Older Loon versions put the obfs mode and host positionally
+    if obfs.is_empty() && params.len() > 5 && !params[5].contains('=') {
+        obfs = params[5].clone();
+        if params.len() > 6 && !params[6].contains('=') {
+            obfs_host = params[6].clone();
+        }
+    }
+
+    let mut plugin = String::new();
+    let mut plugin_opts = String::new();
+    if !obfs.is_empty() && obfs != "none" {
+        plugin = "obfs-local".to_string();
+        plugin_opts = format!("obfs={}", obfs);
+        if !obfs_host.is_empty() {
+            plugin_opts.push_str(&format!(";obfs-host={}", obfs_host));
+        }
+    }
+
+    *node = Proxy::ss_construct(
+        SS_DEFAULT_GROUP,
+        name,
+        &params[1],
+        port,
+        &unquote(&params[4]),
+        &params[3],
+        &plugin,
+        &plugin_opts,
+        options.udp(),
+        options.tfo(),
+        None,
+        None,
+        "",
+    );
+
+    true
+}
+
+/// Parse a Loon ShadowsocksR line
+/// `ShadowsocksR,server,port,method,"password",protocol=..,protocol-param=..,obfs=..,obfs-param=..`
+fn parse_loon_ssr(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    if params.len() < 5 {
+        return false;
+    }
+    let port = match parse_port(&params[2]) {
+        Some(port) => port,
+        None => return false,
+    };
+    let options = LoonOptions::new(&params[5..]);
+
+    *node = Proxy::ssr_construct(
+        SSR_DEFAULT_GROUP,
+        name,
+        &params[1],
+        port,
+        options.get("protocol"),
+        &params[3],
+        options.get("obfs"),
+        &unquote(&params[4]),
+        options.get("obfs-param"),
+        options.get("protocol-param"),
+        options.udp(),
+        options.tfo(),
+        None,
+        "",
+    );
+
+    true
+}
+
+/// Parse a Loon VMess line
+/// `vmess,server,port,method,"uuid",transport=ws,path=/,host=..,over-tls=true,sni=..`
+fn parse_loon_vmess(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    if params.len() < 5 {
+        return false;
+    }
+    let port = match parse_port(&params[2]) {
+        Some(port) => port,
+        None => return false,
+    };
+    let options = LoonOptions::new(&params[5..]);
+
+    let net = match options.get("transport") {
+        "" => "tcp",
+        transport => transport,
+    };
+    let tls = if options.get_bool("over-tls").unwrap_or(false) {
+        "tls"
+    } else {
+        ""
+    };
+    let aid = options.get("alterid").parse::<u16>().unwrap_or(0);
+
+    *node = Proxy::vmess_construct(
+        V2RAY_DEFAULT_GROUP,
+        name,
+        &params[1],
+        port,
+        "",
+        &unquote(&params[4]),
+        aid,
+        net,
+        &params[3],
+        options.get("path"),
+        options.get("host"),
+        "",
+        tls,
+        options.sni(),
+        options.udp(),
+        options.tfo(),
+        options.scv(),
+        None,
+        "",
+    );
+
+    true
+}
+
+/// Parse a Loon VLESS line
+/// `VLESS,server,port,"uuid",transport=tcp,flow=..,public-key=..,short-id=..,over-tls=true,sni=..`
+fn parse_loon_vless(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    if params.len() < 4 {
+        return false;
+    }
+    let port = match parse_port(&params[2]) {
+        Some(port) => port,
+        None => return false,
+    };
+    let options = LoonOptions::new(&params[4..]);
+
+    let network = match options.get("transport") {
+        "" => "tcp",
+        transport => transport,
+    };
+    let public_key = options.get("public-key");
+
+    let mut vless = VlessProxy {
+        uuid: unquote(&params[3]),
+        tls: options.get_bool("over-tls").unwrap_or(false) || !public_key.is_empty(),
+        udp: options.udp().unwrap_or(true),
+        network: Some(network.to_string()),
+        skip_cert_verify: options.scv(),
+        ..Default::default()
+    };
+
+    let flow = options.get("flow");
+    if !flow.is_empty() {
+        vless.flow = Some(flow.to_string());
+    }
+    if !options.sni().is_empty() {
+        vless.servername = Some(options.sni().to_string());
+    }
+    if !public_key.is_empty() {
+        vless.reality_public_key = Some(public_key.to_string());
+        vless.reality_short_id = Some(options.get("short-id").to_string());
+    }
+
+    let host = options.get("host");
+    let path = options.get("path");
+    match network {
+        "ws" => {
+            let mut headers = HashMap::new();
+            if !host.is_empty() {
+                headers.insert("Host".to_string(), host.to_string());
+            }
+            vless.ws_path = Some(path.to_string());
+            vless.ws_headers = Some(headers);
+        }
+        "http" => {
+            let mut headers = HashMap::new();
+            if !host.is_empty() {
+                headers.insert("Host".to_string(), vec![host.to_string()]);
+            }
+            vless.http_path = Some(if path.is_empty() { "/" } else { path }.to_string());
+            vless.http_headers = Some(headers);
+        }
+        "grpc" => {
+            vless.grpc_service_name = Some(options.get("service-name").to_string());
+        }
+        _ => {}
+    }
+
+    *node = Proxy {
+        proxy_type: ProxyType::Vless,
+        group: V2RAY_DEFAULT_GROUP.to_string(),
+        remark: name.to_string(),
+        hostname: params[1].clone(),
+        port,
+        udp: options.udp(),
+        tcp_fast_open: options.tfo(),
+        allow_insecure: options.scv(),
+        sni: vless.servername.clone(),
+        combined_proxy: Some(CombinedProxy::Vless(vless)),
+        ..Default::default()
+    };
+
+    true
+}
+
+/// Parse a Loon Trojan line
+/// `trojan,server,port,"password",tls-name=..,skip-cert-verify=..,transport=ws,path=..,host=..`
+fn parse_loon_trojan(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    if params.len() < 4 {
+        return false;
+    }
+    let port = match parse_port(&params[2]) {
+        Some(port) => port,
+        None => return false,
+    };
+    let options = LoonOptions::new(&params[4..]);
+
+    let non_empty = |value: &str| {
+        if value.is_empty() {
+            None
+        } else {
+            Some(value.to_string())
+        }
+    };
+    let network = match options.get("transport") {
+        "ws" => Some("ws".to_string()),
+        _ => None,
+    };
+
+    *node = Proxy::trojan_construct(
+        TROJAN_DEFAULT_GROUP.to_string(),
+        name.to_string(),
+        params[1].clone(),
+        port,
+        unquote(&params[3]),
+        network,
+        non_empty(options.get("host")),
+        non_empty(options.get("path")),
+        non_empty(options.sni()),
+        true,
+        options.udp(),
+        options.tfo(),
+        options.scv(),
+        None,
+        None,
+    );
+
+    true
+}
+
+/// Parse a Loon HTTP/HTTPS line
+/// `http,server,port,username,"password"`
+fn parse_loon_http(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    if params.len() < 3 {
+        return false;
+    }
+    let port = match parse_port(&params[2]) {
+        Some(port) => port,
+        None => return false,
+    };
+    let (username, password, options) = positional_credentials(params);
+
+    *node = Proxy::http_construct(
+        HTTP_DEFAULT_GROUP,
+        name,
+        &params[1],
+        port,
+        &username,
+        &password,
+        params[0].eq_ignore_ascii_case("https"),
+        options.tfo(),
+        options.scv(),
+        None,
+        "",
+    );
+    if !options.sni().is_empty() {
+        node.host = Some(options.sni().to_string());
+    }
+
+    true
+}
+
+/// Parse a Loon SOCKS5 line
+/// `socks5,server,port,username,"password",over-tls=..,sni=..`
+fn parse_loon_socks(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    if params.len() < 3 {
+        return false;
+    }
+    let port = match parse_port(&params[2]) {
+        Some(port) => port,
+        None => return false,
+    };
+    let (username, password, options) = positional_credentials(params);
+
+    *node = Proxy::socks_construct(
+        SOCKS_DEFAULT_GROUP,
+        name,
+        &params[1],
+        port,
+        &username,
+        &password,
+        options.udp(),
+        options.tfo(),
+        options.scv(),
+        "",
+    );
+    if options.get_bool("over-tls").unwrap_or(false) {
+        node.tls_secure = true;
+        if !options.sni().is_empty() {
+            node.host = Some(options.sni().to_string());
+        }
+    }
+
+    true
+}
+
+/// Read the optional positional username and password of HTTP/SOCKS5 lines
+fn positional_credentials(params: &[String]) -> (String, String, LoonOptions) {
+    let mut username = String::new();
+    let mut password = String::new();
+    let mut index = 3;
+
+    if params.len() > index && !params[index].contains('=') {
+        username = unquote(&params[index]);
+        index += 1;
+        if params.len() > index && !params[index].contains('=') {
+            password = unquote(&params[index]);
+            index += 1;
+        }
+    }
+
+    (username, password, LoonOptions::new(&params[index..]))
+}
+
+/// Parse a Loon WireGuard line
+/// `WireGuard,interface-ip=..,private-key=..,dns=..,mtu=..,peers=[{public-key=..,endpoint=host:port,..}]`
+fn parse_loon_wireguard(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    let options = LoonOptions::new(&params[1..]);
+
+    // Only the first peer is supported
+    let peers = options.get("peers");
+    let peer = match peers.find('{').zip(peers.find('}')) {
+        Some((start, end)) if start < end => &peers[start + 1..end],
+        _ => return false,
+    };
+    let peer = LoonOptions::new(&split_params(peer));
+
+    let (server, port) = match peer.get("endpoint").rsplit_once(':') {
+        Some((server, port)) => (server.trim_matches(|c| c == '[' || c == ']'), port),
+        None => return false,
+    };
+    let port = match parse_port(port) {
+        Some(port) => port,
+        None => return false,
+    };
+
+    let mut dns_servers = Vec::new();
+    for key in ["dns", "dnsv6"] {
+        if !options.get(key).is_empty() {
+            dns_servers.push(options.get(key).to_string());
+        }
+    }
+    let reserved = peer
+        .get("reserved")
+        .trim_matches(|c| c == '[' || c == ']')
+        .replace(' ', "");
+
+    *node = Proxy::wireguard_construct(
+        WG_DEFAULT_GROUP.to_string(),
+        name.to_string(),
+        server.to_string(),
+        port,
+        options.get("interface-ip").to_string(),
+        options.get("interface-ipv6").to_string(),
+        options.get("private-key").to_string(),
+        peer.get("public-key").to_string(),
+        peer.get("preshared-key").to_string(),
+        dns_servers,
+        options.get("mtu").parse::<u16>().ok(),
+        options.get("keepalive").parse::<u16>().ok(),
+        String::new(),
+        reserved,
+        options.udp(),
+        None,
+    );
+    if !peer.get("allowed-ips").is_empty() {
+        node.allowed_ips = peer.get("allowed-ips").to_string();
+    }
+
+    true
+}
+
+/// Parse a Loon Hysteria2 line
+/// `Hysteria2,server,port,"password",sni=..,skip-cert-verify=..,download-bandwidth=..`
+fn parse_loon_hysteria2(params: &[String], name: &str, node: &mut Proxy) -> bool {
+    if params.len() < 4 {
+        return false;
+    }
+    let port = match parse_port(&params[2]) {
+        Some(port) => port,
+        None => return false,
+    };
+    let options = LoonOptions::new(&params[4..]);
+
+    let sni = options.sni();
+    let obfs_param = options.get("salamander-password");
+
+    *node = Proxy::hysteria2_construct(
+        HYSTERIA2_DEFAULT_GROUP.to_string(),
+        name.to_string(),
+        params[1].clone(),
+        port,
+        None,
+        None,
+        options.get("download-bandwidth").parse::<u32>().ok(),
+        unquote(&params[3]),
+        if obfs_param.is_empty() {
+            None
+        } else {
+            Some("salamander".to_string())
+        },
+        if obfs_param.is_empty() {
+            None
+        } else {
+            Some(obfs_param.to_string())
+        },
+        if sni.is_empty() {
+            None
+        } else {
+            Some(sni.to_string())
+        },
+        None,
+        Vec::new(),
+        None,
+        None,
+        None,
+        options.tfo(),
+        options.scv(),
+        None,
+    );
+    node.udp = options.udp();
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOON_CONF: &str = r#"
+[General]
+skip-proxy = 192.168.0.0/16
+
+[Proxy]
+SS = Shadowsocks,ss.example.com,8388,aes-128-gcm,"pass,word",obfs-name=http,obfs-host=www.bing.com,fast-open=false,udp=true
+SS Old = Shadowsocks,ss.example.com,8389,chacha20-ietf-poly1305,"secret",http,obfs.example.com
+SSR = ShadowsocksR,ssr.example.com,443,aes-256-cfb,"secret",protocol=auth_aes128_md5,protocol-param=param,obfs=tls1.2_ticket_auth,obfs-param=obfs.example.com
+VMess = vmess,vmess.example.com,443,auto,"b831381d-6324-4d53-ad4f-8cda48b30811",transport=ws,alterId=0,path=/ws,host=cdn.example.com,over-tls=true,sni=sni.example.com,skip-cert-verify=true
+VLESS = VLESS,vless.example.com,443,"b831381d-6324-4d53-ad4f-8cda48b30811",transport=tcp,flow=xtls-rprx-vision,public-key="pubkey",short-id=abcd,sni=www.apple.com,udp=true
+Trojan = trojan,trojan.example.com,443,"secret",tls-name=trojan.example.com,skip-cert-verify=false
+HTTP = http,http.example.com,8080,user,"pass"
+HTTPS = https,https.example.com,443,sni=tls.example.com
+SOCKS5 = socks5,socks.example.com,1080,user,"pass",udp=true
+WG = wireguard, interface-ip=10.0.0.2, private-key=privkey, dns=1.1.1.1, mtu=1280, keepalive=25, peers=[{public-key = pubkey, endpoint = wg.example.com:51820, allowed-ips = "0.0.0.0/0,::/0", reserved = [1,2,3]}]
+Hysteria2 = Hysteria2,hy2.example.com,443,"secret",sni=hy2.example.com,skip-cert-verify=true,download-bandwidth=100,udp=true
+DIRECT = direct
+
+[Remote Proxy]
+Sub = https://example.com/sub, udp=true, enabled=true
+Off = https://example.com/off, enabled=false
+"#;
+
+    fn find<'a>(nodes: &'a [Proxy], remark: &str) -> &'a Proxy {
+        nodes.iter().find(|node| node.remark == remark).unwrap()
+    }
+
+    #[test]
+    fn test_explode_loon_proxies() {
+        let mut nodes = Vec::new();
+        assert!(explode_loon(LOON_CONF, &mut nodes));
+        assert_eq!(nodes.len(), 11);
+
+        let ss = find(&nodes, "SS");
+        assert_eq!(ss.proxy_type, ProxyType::Shadowsocks);
+        assert_eq!(ss.password.as_deref(), Some("pass,word"));
+        assert_eq!(ss.plugin.as_deref(), Some("obfs-local"));
+        assert_eq!(
+            ss.plugin_option.as_deref(),
+            Some("obfs=http;obfs-host=www.bing.com")
+        );
+        assert_eq!(
+            find(&nodes, "SS Old").plugin_option.as_deref(),
+            Some("obfs=http;obfs-host=obfs.example.com")
+        );
+
+        let ssr = find(&nodes, "SSR");
+        assert_eq!(ssr.proxy_type, ProxyType::ShadowsocksR);
+        assert_eq!(ssr.protocol.as_deref(), Some("auth_aes128_md5"));
+        assert_eq!(ssr.obfs_param.as_deref(), Some("obfs.example.com"));
+
+        let vmess = find(&nodes, "VMess");
+        assert_eq!(vmess.proxy_type, ProxyType::VMess);
+        assert_eq!(vmess.transfer_protocol.as_deref(), Some("ws"));
+        assert_eq!(vmess.path.as_deref(), Some("/ws"));
+        assert_eq!(vmess.host.as_deref(), Some("cdn.example.com"));
+        assert!(vmess.tls_secure);
+        assert_eq!(vmess.allow_insecure, Some(true));
+
+        let vless = find(&nodes, "VLESS");
+        assert_eq!(vless.proxy_type, ProxyType::Vless);
+        match &vless.combined_proxy {
+            Some(CombinedProxy::Vless(vless)) => {
+                assert_eq!(vless.flow.as_deref(), Some("xtls-rprx-vision"));
+                assert_eq!(vless.reality_public_key.as_deref(), Some("pubkey"));
+                assert_eq!(vless.reality_short_id.as_deref(), Some("abcd"));
+                assert_eq!(vless.servername.as_deref(), Some("www.apple.com"));
+                assert!(vless.tls);
+            }
+            _ => panic!("expected a VLESS proxy"),
+        }
+
+        let trojan = find(&nodes, "Trojan");
+        assert_eq!(trojan.proxy_type, ProxyType::Trojan);
+        assert_eq!(trojan.sni.as_deref(), Some("trojan.example.com"));
+        assert_eq!(trojan.allow_insecure, Some(false));
+
+        let http = find(&nodes, "HTTP");
+        assert_eq!(http.proxy_type, ProxyType::HTTP);
+        assert_eq!(http.username.as_deref(), Some("user"));
+        assert_eq!(http.password.as_deref(), Some("pass"));
+        assert_eq!(find(&nodes, "HTTPS").proxy_type, ProxyType::HTTPS);
+
+        let socks = find(&nodes, "SOCKS5");
+        assert_eq!(socks.proxy_type, ProxyType::Socks5);
+        assert_eq!(socks.udp, Some(true));
+
+        let wg = find(&nodes, "WG");
+        assert_eq!(wg.proxy_type, ProxyType::WireGuard);
+        assert_eq!(wg.hostname, "wg.example.com");
+        assert_eq!(wg.port, 51820);
+        assert_eq!(wg.self_ip.as_deref(), Some("10.0.0.2"));
+        assert_eq!(wg.public_key.as_deref(), Some("pubkey"));
+        assert_eq!(wg.allowed_ips, "0.0.0.0/0,::/0");
+        assert_eq!(wg.client_id.as_deref(), Some("1,2,3"));
+        assert_eq!(wg.mtu, 1280);
+
+        let hy2 = find(&nodes, "Hysteria2");
+        assert_eq!(hy2.proxy_type, ProxyType::Hysteria2);
+        assert_eq!(hy2.password.as_deref(), Some("secret"));
+        assert_eq!(hy2.down_speed, 100);
+        assert_eq!(hy2.allow_insecure, Some(true));
+    }
+
+    #[test]
+    fn test_explode_loon_ignores_surge_conf() {
+        let surge = "[Proxy]\nSS = ss, 1.2.3.4, 8388, encrypt-method=aes-128-gcm, password=secret\nHTTP = http, 1.2.3.4, 8080, user, pass\n";
+        let mut nodes = Vec::new();
+        assert!(!explode_loon(surge, &mut nodes));
+        assert!(nodes.is_empty());
+    }
+
+    #[test]
+    fn test_extract_loon_remote_proxies() {
+        assert_eq!(
+            extract_loon_remote_proxies(LOON_CONF),
+            vec![("Sub".to_string(), "https://example.com/sub".to_string())]
+        );
+    }
+}
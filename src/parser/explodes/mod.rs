@@ -1,11 +1,15 @@
+mod anytls;
 mod common;
 mod explode_clash;
 mod http;
 mod httpsub;
 mod hysteria;
 mod hysteria2;
+mod loon;
 mod netch;
 mod quan;
+mod quanx;
+mod singbox;
 mod snell;
 mod socks;
 mod ss;
@@ -13,18 +17,24 @@ mod ssr;
 mod sstap;
 mod surge;
 mod trojan;
+mod tuic;
 mod vless;
 mod vmess;
 mod wireguard;
+mod xray;
 
+pub use anytls::explode_anytls;
 pub use common::{explode, explode_conf_content, explode_sub};
 pub use explode_clash::explode_clash;
 pub use http::explode_http;
 pub use httpsub::explode_http_sub;
 pub use hysteria::explode_hysteria;
 pub use hysteria2::{explode_hysteria2, explode_std_hysteria2};
+pub use loon::{explode_loon, extract_loon_remote_proxies};
 pub use netch::{explode_netch, explode_netch_conf};
 pub use quan::explode_quan;
+pub use quanx::{explode_quanx, extract_quanx_remote_servers};
+pub use singbox::explode_singbox;
 pub use snell::{explode_snell, explode_snell_surge};
 pub use socks::explode_socks;
 pub use ss::{explode_ss, explode_ss_android, explode_ss_conf, explode_ssd};
@@ -32,9 +42,11 @@ pub use ssr::{explode_ssr, explode_ssr_conf};
 pub use sstap::explode_sstap;
 pub use surge::explode_surge;
 pub use trojan::{explode_trojan, explode_trojan_go};
+pub use tuic::explode_tuic;
 pub use vless::explode_vless;
 pub use vmess::{
     explode_kitsunebi, explode_shadowrocket, explode_std_vmess, explode_std_vmess_new,
     explode_vmess, explode_vmess_conf,
 };
 pub use wireguard::explode_wireguard;
+pub use xray::explode_xray_conf;
@@ -0,0 +1,610 @@
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::vless::VlessProxy;
+use crate::models::{
+    Proxy, ProxyType, HTTP_DEFAULT_GROUP, SOCKS_DEFAULT_GROUP, SSR_DEFAULT_GROUP, SS_DEFAULT_GROUP,
+    TROJAN_DEFAULT_GROUP, V2RAY_DEFAULT_GROUP,
+};
+use std::collections::HashMap;
+
+/// Parse a Quantumult X configuration into a vector of Proxy objects
+///
+/// Handles both full profiles, where nodes live in `[server_local]`, and
+/// plain node lists such as the contents of a `server_remote` subscription.
+pub fn explode_quanx(content: &str, nodes: &mut Vec<Proxy>) -> bool {
+    let mut success = false;
+
+    for line in section_lines(content, "server_local") {
+        let mut node = Proxy::default();
+        if parse_quanx_line(line, &mut node) {
+            nodes.push(node);
+            success = true;
+        }
+    }
+
+    success
+}
+
+/// Extract the `[server_remote]` subscriptions of a Quantumult X profile
+///
+/// Returns (tag, url) pairs in declaration order. Disabled entries
+/// (`enabled=false`) are skipped, and the url is used when no tag is set.
+pub fn extract_quanx_remote_servers(content: &str) -> Vec<(String, String)> {
+    let mut remotes = Vec::new();
+
+    for line in section_lines(content, "server_remote") {
+        let mut params = line.split(',').map(|s| s.trim());
+        let url = match params.next() {
+            Some(url) if !url.is_empty() && !url.contains('=') => url.to_string(),
+            _ => continue,
+        };
+        let options = parse_options(params);
+        if options.get("enabled").map(|s| s.as_str()) == Some("false") {
+            continue;
+        }
+
+        let tag = options.get("tag").cloned().unwrap_or_else(|| url.clone());
+        remotes.push((tag, url));
+    }
+
+    remotes
+}
+
+/// Collect the lines of a section, plus any lines that come before the first
+/// section header
+fn section_lines<'a>(content: &'a str, section: &str) -> Vec<&'a str> {
+    let mut lines = Vec::new();
+    let mut in_section = true;
+
+    for line in content.lines() {
+        // Skip empty lines and comments
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        // Check section headers
+        if line.starts_with('[') && line.ends_with(']') {
+            in_section = line[1..line.len() - 1].eq_ignore_ascii_case(section);
+            continue;
+        }
+
+        if in_section {
+            lines.push(line);
+        }
+    }
+
+    lines
+}
+
+/// Collect `key=value` options, keys are lowercased
+fn parse_options<'a>(params: impl Iterator<Item = &'a str>) -> HashMap<String, String> {
+    params
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+        .collect()
+}
+
+/// Options of a single Quantumult X server line
+struct QuanXOptions {
+    params: HashMap<String, String>,
+}
+
+impl QuanXOptions {
+    fn get(&self, key: &str) -> &str {
+        self.params.get(key).map(|s| s.as_str()).unwrap_or("")
+    }
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        self.params.get(key).map(|value| value == "true")
+    }
+
+    /// Credentials use `none` as an explicit empty value
+    fn get_credential(&self, key: &str) -> &str {
+        match self.get(key) {
+            "none" => "",
+            value => value,
+        }
+    }
+
+    fn udp(&self) -> Option<bool> {
+        self.get_bool("udp-relay")
+    }
+
+    fn tfo(&self) -> Option<bool> {
+        self.get_bool("fast-open")
+    }
+
+    /// QuanX exposes certificate verification rather than skipping it
+    fn scv(&self) -> Option<bool> {
+        self.get_bool("tls-verification").map(|verify| !verify)
+    }
+
+    fn tls13(&self) -> Option<bool> {
+        self.get_bool("tls13")
+    }
+
+    /// TLS server name, falling back to the obfs host
+    fn tls_host(&self) -> &str {
+        match self.get("tls-host") {
+            "" => self.get("obfs-host"),
+            host => host,
+        }
+    }
+}
+
+/// Parse a single `type=host:port, key=value, ..., tag=name` line
+fn parse_quanx_line(line: &str, node: &mut Proxy) -> bool {
+    let (proxy_type, config) = match line.split_once('=') {
+        Some((proxy_type, config)) => (proxy_type.trim().to_lowercase(), config),
+        None => return false,
+    };
+
+    let mut params = config.split(',').map(|s| s.trim());
+    let (server, port) = match params.next().and_then(|addr| addr.rsplit_once(':')) {
+        Some((server, port)) => (server.trim_matches(|c| c == '[' || c == ']'), port),
+        None => return false,
+    };
+    let port = match port.parse::<u16>() {
+        Ok(port) if port != 0 => port,
+        _ => return false,
+    };
+
+    let options = QuanXOptions {
+        params: parse_options(params),
+    };
+    let remark = match options.get("tag") {
+        "" => format!("{}:{}", server, port),
+        tag => tag.to_string(),
+    };
+
+    match proxy_type.as_str() {
+        "shadowsocks" if !options.get("ssr-protocol").is_empty() => {
+            parse_quanx_ssr(&remark, server, port, &options, node)
+        }
+        "shadowsocks" => parse_quanx_ss(&remark, server, port, &options, node),
+        "vmess" => parse_quanx_vmess(&remark, server, port, &options, node),
+        "vless" => parse_quanx_vless(&remark, server, port, &options, node),
+        "trojan" => parse_quanx_trojan(&remark, server, port, &options, node),
+        "http" => parse_quanx_http(&remark, server, port, &options, node),
+        "socks5" => parse_quanx_socks(&remark, server, port, &options, node),
+        _ => false,
+    }
+}
+
+/// Parse a Quantumult X Shadowsocks line
+fn parse_quanx_ss(
+    remark: &str,
+    server: &str,
+    port: u16,
+    options: &QuanXOptions,
+    node: &mut Proxy,
+) -> bool {
+    let obfs = options.get("obfs");
+    let obfs_host = options.get("obfs-host");
+    let obfs_uri = options.get("obfs-uri");
+
+    let mut plugin = String::new();
+    let mut plugin_opts = String::new();
+    match obfs {
+        "http" | "tls" => {
+            plugin = "obfs-local".to_string();
+            plugin_opts = format!("obfs={}", obfs);
+            if !obfs_host.is_empty() {
+                plugin_opts.push_str(&format!(";obfs-host={}", obfs_host));
+            }
+            if !obfs_uri.is_empty() {
+                plugin_opts.push_str(&format!(";obfs-uri={}", obfs_uri));
+            }
+        }
+        "ws" | "wss" => {
+            plugin = "v2ray-plugin".to_string();
+            plugin_opts = "mode=websocket".to_string();
+            if obfs == "wss" {
+                plugin_opts.push_str(";tls");
+            }
+            if !obfs_host.is_empty() {
+                plugin_opts.push_str(&format!(";host={}", obfs_host));
+            }
+            if !obfs_uri.is_empty() {
+                plugin_opts.push_str(&format!(";path={}", obfs_uri));
+            }
+        }
+        _ => {}
+    }
+
+    *node = Proxy::ss_construct(
+        SS_DEFAULT_GROUP,
+        remark,
+        server,
+        port,
+        options.get("password"),
+        options.get("method"),
+        &plugin,
+        &plugin_opts,
+        options.udp(),
+        options.tfo(),
+        None,
+        options.tls13(),
+        "",
+    );
+
+    true
+}
+
+/// Parse a Quantumult X ShadowsocksR line, a `shadowsocks` line with `ssr-protocol`
+fn parse_quanx_ssr(
+    remark: &str,
+    server: &str,
+    port: u16,
+    options: &QuanXOptions,
+    node: &mut Proxy,
+) -> bool {
+    let obfs = match options.get("obfs") {
+        "" => "plain",
+        obfs => obfs,
+    };
+
+    *node = Proxy::ssr_construct(
+        SSR_DEFAULT_GROUP,
+        remark,
+        server,
+        port,
+        options.get("ssr-protocol"),
+        options.get("method"),
+        obfs,
+        options.get("password"),
+        options.get("obfs-host"),
+        options.get("ssr-protocol-param"),
+        options.udp(),
+        options.tfo(),
+        None,
+        "",
+    );
+
+    true
+}
+
+/// Parse a Quantumult X VMess line
+fn parse_quanx_vmess(
+    remark: &str,
+    server: &str,
+    port: u16,
+    options: &QuanXOptions,
+    node: &mut Proxy,
+) -> bool {
+    // obfs selects both the transport and whether TLS is used
+    let (net, fake_type, tls) = match options.get("obfs") {
+        "ws" => ("ws", "none", false),
+        "wss" => ("ws", "none", true),
+        "over-tls" => ("tcp", "none", true),
+        "http" => ("tcp", "http", false),
+        _ => ("tcp", "none", false),
+    };
+    let aid = if options.get_bool("aead") == Some(false) {
+        1
+    } else {
+        0
+    };
+
+    *node = Proxy::vmess_construct(
+        V2RAY_DEFAULT_GROUP,
+        remark,
+        server,
+        port,
+        fake_type,
+        options.get("password"),
+        aid,
+        net,
+        options.get("method"),
+        options.get("obfs-uri"),
+        options.get("obfs-host"),
+        "",
+        if tls { "tls" } else { "" },
+        if tls { options.tls_host() } else { "" },
+        options.udp(),
+        options.tfo(),
+        options.scv(),
+        options.tls13(),
+        "",
+    );
+
+    true
+}
+
+/// Parse a Quantumult X VLESS line
+fn parse_quanx_vless(
+    remark: &str,
+    server: &str,
+    port: u16,
+    options: &QuanXOptions,
+    node: &mut Proxy,
+) -> bool {
+    let obfs = options.get("obfs");
+    let public_key = options.get("reality-base64-pubkey");
+    let tls = matches!(obfs, "wss" | "over-tls") || !public_key.is_empty();
+    let network = match obfs {
+        "ws" | "wss" => "ws",
+        _ => "tcp",
+    };
+
+    let mut vless = VlessProxy {
+        uuid: options.get("password").to_string(),
+        tls,
+        udp: options.udp().unwrap_or(true),
+        network: Some(network.to_string()),
+        skip_cert_verify: options.scv(),
+        ..Default::default()
+    };
+
+    let flow = options.get("vless-flow");
+    if !flow.is_empty() {
+        vless.flow = Some(flow.to_string());
+    }
+    if tls && !options.tls_host().is_empty() {
+        vless.servername = Some(options.tls_host().to_string());
+    }
+    if !public_key.is_empty() {
+        vless.reality_public_key = Some(public_key.to_string());
+        vless.reality_short_id = Some(options.get("reality-hex-shortid").to_string());
+    }
+    if network == "ws" {
+        let mut headers = HashMap::new();
+        if !options.get("obfs-host").is_empty() {
+            headers.insert("Host".to_string(), options.get("obfs-host").to_string());
+        }
+        vless.ws_path = Some(options.get("obfs-uri").to_string());
+        vless.ws_headers = Some(headers);
+    }
+
+    *node = Proxy {
+        proxy_type: ProxyType::Vless,
+        group: V2RAY_DEFAULT_GROUP.to_string(),
+        remark: remark.to_string(),
+        hostname: server.to_string(),
+        port,
+        udp: options.udp(),
+        tcp_fast_open: options.tfo(),
+        allow_insecure: options.scv(),
+        tls13: options.tls13(),
+        sni: vless.servername.clone(),
+        combined_proxy: Some(CombinedProxy::Vless(vless)),
+        ..Default::default()
+    };
+
+    true
+}
+
+/// Parse a Quantumult X Trojan line
+fn parse_quanx_trojan(
+    remark: &str,
+    server: &str,
+    port: u16,
+    options: &QuanXOptions,
+    node: &mut Proxy,
+) -> bool {
+    let non_empty = |value: &str| {
+        if value.is_empty() {
+            None
+        } else {
+            Some(value.to_string())
+        }
+    };
+    let obfs = options.get("obfs");
+    let websocket = matches!(obfs, "ws" | "wss");
+    let tls = options.get_bool("over-tls").unwrap_or(false) || obfs == "wss";
+
+    *node = Proxy::trojan_construct(
+        TROJAN_DEFAULT_GROUP.to_string(),
+        remark.to_string(),
+        server.to_string(),
+        port,
+        options.get("password").to_string(),
+        if websocket {
+            Some("ws".to_string())
+        } else {
+            None
+        },
+        non_empty(options.tls_host()),
+        if websocket {
+            non_empty(options.get("obfs-uri"))
+        } else {
+            None
+        },
+        if tls {
+            non_empty(options.tls_host())
+        } else {
+            None
+        },
+        tls,
+        options.udp(),
+        options.tfo(),
+        options.scv(),
+        options.tls13(),
+        None,
+    );
+
+    true
+}
+
+/// Parse a Quantumult X HTTP line, `over-tls=true` makes it HTTPS
+fn parse_quanx_http(
+    remark: &str,
+    server: &str,
+    port: u16,
+    options: &QuanXOptions,
+    node: &mut Proxy,
+) -> bool {
+    let tls = options.get_bool("over-tls").unwrap_or(false);
+
+    *node = Proxy::http_construct(
+        HTTP_DEFAULT_GROUP,
+        remark,
+        server,
+        port,
+        options.get_credential("username"),
+        options.get_credential("password"),
+        tls,
+        options.tfo(),
+        options.scv(),
+        options.tls13(),
+        "",
+    );
+    if tls && !options.tls_host().is_empty() {
+        node.host = Some(options.tls_host().to_string());
+    }
+
+    true
+}
+
+/// Parse a Quantumult X SOCKS5 line
+fn parse_quanx_socks(
+    remark: &str,
+    server: &str,
+    port: u16,
+    options: &QuanXOptions,
+    node: &mut Proxy,
+) -> bool {
+    *node = Proxy::socks_construct(
+        SOCKS_DEFAULT_GROUP,
+        remark,
+        server,
+        port,
+        options.get_credential("username"),
+        options.get_credential("password"),
+        options.udp(),
+        options.tfo(),
+        options.scv(),
+        "",
+    );
+    if options.get_bool("over-tls").unwrap_or(false) {
+        node.tls_secure = true;
+        if !options.tls_host().is_empty() {
+            node.host = Some(options.tls_host().to_string());
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUANX_CONF: &str = r#"
+[general]
+network_check_url=http://www.gstatic.com/generate_204
+
+[server_remote]
+https://example.com/sub, tag=Remote, update-interval=86400, opt-parser=false, enabled=true
+https://example.com/off, tag=Off, enabled=false
+https://example.com/untagged
+
+[server_local]
+shadowsocks=ss.example.com:8388, method=aes-128-gcm, password=secret, obfs=http, obfs-host=www.bing.com, fast-open=false, udp-relay=true, tag=SS
+shadowsocks=ws.example.com:443, method=aes-128-gcm, password=secret, obfs=wss, obfs-host=cdn.example.com, obfs-uri=/ws, tag=SS WS
+shadowsocks=ssr.example.com:443, method=aes-256-cfb, password=secret, ssr-protocol=auth_aes128_md5, ssr-protocol-param=param, obfs=tls1.2_ticket_auth, obfs-host=obfs.example.com, tag=SSR
+vmess=vmess.example.com:443, method=chacha20-ietf-poly1305, password=b831381d-6324-4d53-ad4f-8cda48b30811, obfs=wss, obfs-host=cdn.example.com, obfs-uri=/ws, tls-verification=false, tag=VMess
+vmess=tls.example.com:443, method=none, password=b831381d-6324-4d53-ad4f-8cda48b30811, obfs=over-tls, obfs-host=tls.example.com, aead=false, tag=VMess TLS
+vless=vless.example.com:443, method=none, password=b831381d-6324-4d53-ad4f-8cda48b30811, obfs=over-tls, obfs-host=www.apple.com, vless-flow=xtls-rprx-vision, reality-base64-pubkey=pubkey, reality-hex-shortid=abcd, tag=VLESS
+trojan=trojan.example.com:443, password=secret, over-tls=true, tls-host=trojan.example.com, tls-verification=true, tag=Trojan
+http=http.example.com:443, username=none, password=none, over-tls=true, tls-host=http.example.com, tag=HTTPS
+socks5=socks.example.com:1080, username=user, password=pass, tag=SOCKS5
+
+[filter_local]
+host-suffix, example.com, proxy
+"#;
+
+    fn find<'a>(nodes: &'a [Proxy], remark: &str) -> &'a Proxy {
+        nodes.iter().find(|node| node.remark == remark).unwrap()
+    }
+
+    #[test]
+    fn test_explode_quanx_server_local() {
+        let mut nodes = Vec::new();
+        assert!(explode_quanx(QUANX_CONF, &mut nodes));
+        assert_eq!(nodes.len(), 9);
+
+        let ss = find(&nodes, "SS");
+        assert_eq!(ss.proxy_type, ProxyType::Shadowsocks);
+        assert_eq!(ss.plugin.as_deref(), Some("obfs-local"));
+        assert_eq!(
+            ss.plugin_option.as_deref(),
+            Some("obfs=http;obfs-host=www.bing.com")
+        );
+        assert_eq!(ss.udp, Some(true));
+        assert_eq!(
+            find(&nodes, "SS WS").plugin_option.as_deref(),
+            Some("mode=websocket;tls;host=cdn.example.com;path=/ws")
+        );
+
+        let ssr = find(&nodes, "SSR");
+        assert_eq!(ssr.proxy_type, ProxyType::ShadowsocksR);
+        assert_eq!(ssr.protocol.as_deref(), Some("auth_aes128_md5"));
+        assert_eq!(ssr.obfs.as_deref(), Some("tls1.2_ticket_auth"));
+        assert_eq!(ssr.obfs_param.as_deref(), Some("obfs.example.com"));
+
+        let vmess = find(&nodes, "VMess");
+        assert_eq!(vmess.proxy_type, ProxyType::VMess);
+        assert_eq!(vmess.transfer_protocol.as_deref(), Some("ws"));
+        assert_eq!(vmess.host.as_deref(), Some("cdn.example.com"));
+        assert_eq!(vmess.path.as_deref(), Some("/ws"));
+        assert!(vmess.tls_secure);
+        assert_eq!(vmess.allow_insecure, Some(true));
+
+        let vmess_tls = find(&nodes, "VMess TLS");
+        assert_eq!(vmess_tls.transfer_protocol.as_deref(), Some("tcp"));
+        assert!(vmess_tls.tls_secure);
+        assert_eq!(vmess_tls.alter_id, 1);
+
+        let vless = find(&nodes, "VLESS");
+        assert_eq!(vless.proxy_type, ProxyType::Vless);
+        match &vless.combined_proxy {
+            Some(CombinedProxy::Vless(vless)) => {
+                assert!(vless.tls);
+                assert_eq!(vless.flow.as_deref(), Some("xtls-rprx-vision"));
+                assert_eq!(vless.servername.as_deref(), Some("www.apple.com"));
+                assert_eq!(vless.reality_public_key.as_deref(), Some("pubkey"));
+            }
+            _ => panic!("expected a VLESS proxy"),
+        }
+
+        let trojan = find(&nodes, "Trojan");
+        assert_eq!(trojan.proxy_type, ProxyType::Trojan);
+        assert!(trojan.tls_secure);
+        assert_eq!(trojan.sni.as_deref(), Some("trojan.example.com"));
+        assert_eq!(trojan.allow_insecure, Some(false));
+
+        let https = find(&nodes, "HTTPS");
+        assert_eq!(https.proxy_type, ProxyType::HTTPS);
+        assert_eq!(https.username.as_deref(), Some(""));
+
+        let socks = find(&nodes, "SOCKS5");
+        assert_eq!(socks.proxy_type, ProxyType::Socks5);
+        assert_eq!(socks.username.as_deref(), Some("user"));
+    }
+
+    #[test]
+    fn test_explode_quanx_node_list() {
+        let content = "shadowsocks=1.2.3.4:8388, method=aes-256-gcm, password=secret\n\
+                       trojan=5.6.7.8:443, password=secret, over-tls=true, tag=Trojan";
+        let mut nodes = Vec::new();
+        assert!(explode_quanx(content, &mut nodes));
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].remark, "1.2.3.4:8388");
+        assert_eq!(nodes[1].remark, "Trojan");
+    }
+
+    #[test]
+    fn test_extract_quanx_remote_servers() {
+        assert_eq!(
+            extract_quanx_remote_servers(QUANX_CONF),
+            vec![
+                ("Remote".to_string(), "https://example.com/sub".to_string()),
+                (
+                    "https://example.com/untagged".to_string(),
+                    "https://example.com/untagged".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,897 @@
+use crate::models::proxy_node::anytls::AnyTlsProxy;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::tuic::TuicProxy;
+use crate::models::proxy_node::vless::VlessProxy;
+use crate::models::{
+    Proxy, ProxyType, ANYTLS_DEFAULT_GROUP, HTTP_DEFAULT_GROUP, HYSTERIA2_DEFAULT_GROUP,
+    HYSTERIA_DEFAULT_GROUP, SOCKS_DEFAULT_GROUP, SS_DEFAULT_GROUP, TROJAN_DEFAULT_GROUP,
+    TUIC_DEFAULT_GROUP, V2RAY_DEFAULT_GROUP, WG_DEFAULT_GROUP,
+};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// TLS options shared by most sing-box outbounds
+#[derive(Debug, Default)]
+struct SingboxTls {
+    enabled: bool,
+    server_name: Option<String>,
+    insecure: Option<bool>,
+    disable_sni: Option<bool>,
+    alpn: Vec<String>,
+    fingerprint: Option<String>,
+    reality_public_key: Option<String>,
+    reality_short_id: Option<String>,
+}
+
+/// V2Ray transport options shared by vmess, vless and trojan outbounds
+#[derive(Debug, Default)]
+struct SingboxTransport {
+    network: String,
+    host: Vec<String>,
+    path: Option<String>,
+    method: Option<String>,
+    headers: HashMap<String, String>,
+    service_name: Option<String>,
+}
+
+/// Get a string field from a JSON object
+fn get_str<'a>(value: &'a Value, key: &str) -> &'a str {
+    value[key].as_str().unwrap_or("")
+}
+
+/// Get a non-empty string field from a JSON object
+fn get_opt_str(value: &Value, key: &str) -> Option<String> {
+    value[key]
+        .as_str()
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+/// Read a field that may be either a single string or an array of strings
+fn get_str_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => vec![s.clone()],
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse a sing-box duration string (e.g. "30s", "1m", "500ms") into milliseconds
+fn parse_duration_ms(value: &Value) -> Option<u64> {
+    if let Some(seconds) = value.as_u64() {
+        return Some(seconds * 1000);
+    }
+
+    let text = value.as_str()?.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+
+        let mut unit = c.to_string();
+        while let Some(next) = chars.peek() {
+            if next.is_ascii_alphabetic() {
+                unit.push(*next);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        let amount: f64 = number.parse().ok()?;
+        number.clear();
+        let factor = match unit.as_str() {
+            "ms" => 1.0,
+            "s" => 1000.0,
+            "m" => 60_000.0,
+            "h" => 3_600_000.0,
+            _ => return None,
+        };
+        total += (amount * factor) as u64;
+    }
+
+    // A bare number is treated as seconds
+    if !number.is_empty() {
+        total += (number.parse::<f64>().ok()? * 1000.0) as u64;
+    }
+
+    Some(total)
+}
+
+/// Parse the `tls` object of a sing-box outbound
+fn parse_tls(outbound: &Value) -> SingboxTls {
+    let tls = &outbound["tls"];
+    if !tls.is_object() {
+        return SingboxTls::default();
+    }
+
+    let mut result = SingboxTls {
+        enabled: tls["enabled"].as_bool().unwrap_or(false),
+        server_name: get_opt_str(tls, "server_name"),
+        insecure: tls["insecure"].as_bool(),
+        disable_sni: tls["disable_sni"].as_bool(),
+        alpn: get_str_list(&tls["alpn"]),
+        ..Default::default()
+    };
+
+    let utls = &tls["utls"];
+    if utls["enabled"].as_bool().unwrap_or(false) {
+        result.fingerprint = get_opt_str(utls, "fingerprint");
+    }
+
+    let reality = &tls["reality"];
+    if reality["enabled"].as_bool().unwrap_or(false) {
+        result.reality_public_key = get_opt_str(reality, "public_key");
+        result.reality_short_id = get_opt_str(reality, "short_id");
+    }
+
+    result
+}
+
+/// Parse the `transport` object of a sing-box outbound
+fn parse_transport(outbound: &Value) -> SingboxTransport {
+    let transport = &outbound["transport"];
+    let mut result = SingboxTransport {
+        network: "tcp".to_string(),
+        ..Default::default()
+    };
+    if !transport.is_object() {
+        return result;
+    }
+
+    result.network = get_str(transport, "type").to_lowercase();
+    result.host = get_str_list(&transport["host"]);
+    result.path = get_opt_str(transport, "path");
+    result.method = get_opt_str(transport, "method");
+    result.service_name = get_opt_str(transport, "service_name");
+
+    if let Some(headers) = transport["headers"].as_object() {
+        for (key, value) in headers {
+            if let Some(first) = get_str_list(value).into_iter().next() {
+                result.headers.insert(key.clone(), first);
+            }
+        }
+    }
+
+    // ws/httpupgrade carry the host in the headers, http carries it as a list
+    if result.host.is_empty() {
+        if let Some(host) = result.headers.get("Host") {
+            result.host.push(host.clone());
+        }
+    }
+
+    result
+}
+
+/// Parse a single sing-box outbound (or endpoint) into a Proxy object
+///
+/// Returns `None` for unsupported or non-proxy outbounds such as
+/// selector, urltest, direct, block and dns.
+fn parse_singbox_outbound(outbound: &Value) -> Option<Proxy> {
+    let outbound_type = get_str(outbound, "type");
+    let remark = get_str(outbound, "tag").to_string();
+    let server = get_str(outbound, "server").to_string();
+    let port = outbound["server_port"].as_u64().unwrap_or(0) as u16;
+
+    // WireGuard endpoints keep the server inside their peers
+    if outbound_type != "wireguard" && (server.is_empty() || port == 0) {
+        return None;
+    }
+
+    let tfo = outbound["tcp_fast_open"].as_bool();
+    let udp = match outbound["network"].as_str() {
+        Some("tcp") => Some(false),
+        _ => None,
+    };
+    let tls = parse_tls(outbound);
+
+    let node = match outbound_type {
+        "shadowsocks" => {
+            let plugin = get_str(outbound, "plugin");
+            let plugin_opts = get_str(outbound, "plugin_opts");
+            Proxy::ss_construct(
+                SS_DEFAULT_GROUP,
+                &remark,
+                &server,
+                port,
+                get_str(outbound, "password"),
+                get_str(outbound, "method"),
+                plugin,
+                plugin_opts,
+                udp,
+                tfo,
+                None,
+                None,
+                "",
+            )
+        }
+        "vmess" => {
+            let transport = parse_transport(outbound);
+            let (net, fake_type) = match transport.network.as_str() {
+                "http" if tls.enabled => ("h2", ""),
+                "http" => ("tcp", "http"),
+                "grpc" => ("grpc", ""),
+                "ws" => ("ws", ""),
+                "httpupgrade" => ("httpupgrade", ""),
+                _ => ("tcp", ""),
+            };
+            let path = if net == "grpc" {
+                transport.service_name.clone().unwrap_or_default()
+            } else {
+                transport.path.clone().unwrap_or_default()
+            };
+            let host = transport.host.first().cloned().unwrap_or_default();
+
+            Proxy::vmess_construct(
+                V2RAY_DEFAULT_GROUP,
+                &remark,
+                &server,
+                port,
+                fake_type,
+                get_str(outbound, "uuid"),
+                outbound["alter_id"].as_u64().unwrap_or(0) as u16,
+                net,
+                get_str(outbound, "security"),
+                &path,
+                &host,
+                "",
+                if tls.enabled { "tls" } else { "" },
+                tls.server_name.as_deref().unwrap_or(""),
+                udp,
+                tfo,
+                tls.insecure,
+                None,
+                "",
+            )
+        }
+        "vless" => {
+            let transport = parse_transport(outbound);
+            let mut vless = VlessProxy {
+                uuid: get_str(outbound, "uuid").to_string(),
+                flow: get_opt_str(outbound, "flow"),
+                tls: tls.enabled,
+                alpn: tls.alpn.iter().cloned().collect(),
+                servername: tls.server_name.clone(),
+                skip_cert_verify: tls.insecure,
+                client_fingerprint: tls.fingerprint.clone(),
+                reality_public_key: tls.reality_public_key.clone(),
+                reality_short_id: tls.reality_short_id.clone(),
+                ..Default::default()
+            };
+
+            if let Some(packet_encoding) = get_opt_str(outbound, "packet_encoding") {
+                vless.xudp = Some(packet_encoding == "xudp");
+                vless.packet_addr = Some(packet_encoding == "packetaddr");
+                vless.packet_encoding = Some(packet_encoding);
+            }
+
+            match transport.network.as_str() {
+                "ws" | "httpupgrade" => {
+                    let mut headers = transport.headers.clone();
+                    if let Some(host) = transport.host.first() {
+                        headers.insert("Host".to_string(), host.clone());
+                    }
+                    vless.ws_path = transport.path.clone();
+                    vless.ws_headers = Some(headers);
+                }
+                "http" => {
+                    if tls.enabled {
+                        vless.network = Some("h2".to_string());
+                        vless.h2_host = Some(transport.host.clone());
+                        vless.h2_path = transport.path.clone();
+                    } else {
+                        let mut headers = HashMap::new();
+                        if !transport.host.is_empty() {
+                            headers.insert("Host".to_string(), transport.host.clone());
+                        }
+                        vless.http_method = transport.method.clone();
+                        vless.http_path = transport.path.clone();
+                        vless.http_headers = Some(headers);
+                    }
+                }
+                "grpc" => {
+                    vless.grpc_service_name = transport.service_name.clone();
+                }
+                _ => {}
+            }
+            if vless.network.is_none() {
+                vless.network = Some(match transport.network.as_str() {
+                    "http" => "tcp".to_string(),
+                    network => network.to_string(),
+                });
+            }
+
+            Proxy {
+                proxy_type: ProxyType::Vless,
+                group: V2RAY_DEFAULT_GROUP.to_string(),
+                remark: remark.clone(),
+                hostname: server.clone(),
+                port,
+                udp,
+                tcp_fast_open: tfo,
+                allow_insecure: tls.insecure,
+                sni: tls.server_name.clone(),
+                combined_proxy: Some(CombinedProxy::Vless(vless)),
+                ..Default::default()
+            }
+        }
+        "trojan" => {
+            let transport = parse_transport(outbound);
+            let (network, path) = match transport.network.as_str() {
+                network @ ("ws" | "httpupgrade") => {
+                    (Some(network.to_string()), transport.path.clone())
+                }
+                "grpc" => (Some("grpc".to_string()), transport.service_name.clone()),
+                "http" if tls.enabled => (Some("h2".to_string()), transport.path.clone()),
+                "http" => (Some("http".to_string()), transport.path.clone()),
+                _ => (None, None),
+            };
+
+            Proxy::trojan_construct(
+                TROJAN_DEFAULT_GROUP.to_string(),
+                remark.clone(),
+                server.clone(),
+                port,
+                get_str(outbound, "password").to_string(),
+                network,
+                transport.host.first().cloned(),
+                path,
+                tls.server_name.clone(),
+                tls.enabled,
+                udp,
+                tfo,
+                tls.insecure,
+                None,
+                None,
+            )
+        }
+        "hysteria" => {
+            let up = outbound["up_mbps"].as_u64().map(|v| v as u32);
+            let down = outbound["down_mbps"].as_u64().map(|v| v as u32);
+
+            Proxy::hysteria_construct(
+                HYSTERIA_DEFAULT_GROUP.to_string(),
+                remark.clone(),
+                server.clone(),
+                port,
+                String::new(),
+                "udp".to_string(),
+                String::new(),
+                up,
+                down,
+                get_str(outbound, "auth_str").to_string(),
+                get_str(outbound, "obfs").to_string(),
+                tls.server_name.clone().unwrap_or_default(),
+                String::new(),
+                String::new(),
+                String::new(),
+                outbound["recv_window_conn"].as_u64().map(|v| v as u32),
+                outbound["recv_window"].as_u64().map(|v| v as u32),
+                outbound["disable_mtu_discovery"].as_bool(),
+                None,
+                tls.alpn.clone(),
+                tfo,
+                tls.insecure,
+                None,
+            )
+        }
+        "hysteria2" => {
+            let up = outbound["up_mbps"].as_u64().map(|v| v as u32);
+            let down = outbound["down_mbps"].as_u64().map(|v| v as u32);
+            let obfs = &outbound["obfs"];
+            let ports = get_str_list(&outbound["server_ports"])
+                .iter()
+                .map(|range| range.replace(':', "-"))
+                .collect::<Vec<_>>()
+                .join(",");
+
+            let mut node = Proxy::hysteria2_construct(
+                HYSTERIA2_DEFAULT_GROUP.to_string(),
+                remark.clone(),
+                server.clone(),
+                port,
+                if ports.is_empty() { None } else { Some(ports) },
+                up,
+                down,
+                get_str(outbound, "password").to_string(),
+                get_opt_str(obfs, "type"),
+                get_opt_str(obfs, "password"),
+                tls.server_name.clone(),
+                None,
+                tls.alpn.clone(),
+                None,
+                None,
+                None,
+                tfo,
+                tls.insecure,
+                None,
+            );
+            if let Some(interval) = parse_duration_ms(&outbound["hop_interval"]) {
+                node.hop_interval = (interval / 1000) as u32;
+            }
+            node
+        }
+        "tuic" => {
+            let tuic = TuicProxy {
+                uuid: get_str(outbound, "uuid").to_string(),
+                password: get_str(outbound, "password").to_string(),
+                congestion_control: get_opt_str(outbound, "congestion_control"),
+                udp_relay_mode: get_opt_str(outbound, "udp_relay_mode"),
+                alpn: if tls.alpn.is_empty() {
+                    None
+                } else {
+                    Some(tls.alpn.iter().cloned().collect::<HashSet<_>>())
+                },
+                sni: tls.server_name.clone(),
+                disable_sni: tls.disable_sni,
+                skip_cert_verify: tls.insecure,
+                reduce_rtt: outbound["zero_rtt_handshake"].as_bool(),
+                heartbeat_interval: parse_duration_ms(&outbound["heartbeat"]).map(|v| v as u32),
+                request_timeout: None,
+                max_udp_relay_packet_size: None,
+                fast_open: None,
+            };
+
+            Proxy {
+                proxy_type: ProxyType::Tuic,
+                group: TUIC_DEFAULT_GROUP.to_string(),
+                remark: remark.clone(),
+                hostname: server.clone(),
+                port,
+                udp: Some(true),
+                allow_insecure: tls.insecure,
+                sni: tls.server_name.clone(),
+                combined_proxy: Some(CombinedProxy::Tuic(tuic)),
+                ..Default::default()
+            }
+        }
+        "anytls" => {
+            let anytls = AnyTlsProxy {
+                password: get_str(outbound, "password").to_string(),
+                alpn: if tls.alpn.is_empty() {
+                    None
+                } else {
+                    Some(tls.alpn.iter().cloned().collect::<HashSet<_>>())
+                },
+                sni: tls.server_name.clone(),
+                skip_cert_verify: tls.insecure,
+                client_fingerprint: tls.fingerprint.clone(),
+                idle_session_check_interval: parse_duration_ms(
+                    &outbound["idle_session_check_interval"],
+                )
+                .map(|v| (v / 1000) as i32),
+                idle_session_timeout: parse_duration_ms(&outbound["idle_session_timeout"])
+                    .map(|v| (v / 1000) as i32),
+                min_idle_session: outbound["min_idle_session"].as_i64().map(|v| v as i32),
+                tfo,
+                ..Default::default()
+            };
+
+            Proxy {
+                proxy_type: ProxyType::AnyTls,
+                group: ANYTLS_DEFAULT_GROUP.to_string(),
+                remark: remark.clone(),
+                hostname: server.clone(),
+                port,
+                udp,
+                tcp_fast_open: tfo,
+                allow_insecure: tls.insecure,
+                sni: tls.server_name.clone(),
+                combined_proxy: Some(CombinedProxy::AnyTls(anytls)),
+                ..Default::default()
+            }
+        }
+        "wireguard" => return parse_singbox_wireguard(outbound),
+        "socks" => Proxy::socks_construct(
+            SOCKS_DEFAULT_GROUP,
+            &remark,
+            &server,
+            port,
+            get_str(outbound, "username"),
+            get_str(outbound, "password"),
+            udp,
+            tfo,
+            None,
+            "",
+        ),
+        "http" => Proxy::http_construct(
+            HTTP_DEFAULT_GROUP,
+            &remark,
+            &server,
+            port,
+            get_str(outbound, "username"),
+            get_str(outbound, "password"),
+            tls.enabled,
+            tfo,
+            tls.insecure,
+            None,
+            "",
+        ),
+        // selector, urltest, direct, block, dns and unknown types
+        _ => return None,
+    };
+
+    Some(node)
+}
+
+/// Parse a sing-box WireGuard outbound or 1.11+ endpoint into a Proxy object
+fn parse_singbox_wireguard(outbound: &Value) -> Option<Proxy> {
+    let remark = get_str(outbound, "tag").to_string();
+
+    // Legacy outbounds keep the peer at the top level, endpoints use `peers`
+    let peer = match outbound["peers"].as_array().and_then(|peers| peers.first()) {
+        Some(peer) => peer,
+        None => outbound,
+    };
+    let (server, port) = if peer.get("address").is_some() && peer.get("public_key").is_some() {
+        (get_str(peer, "address"), peer["port"].as_u64().unwrap_or(0))
+    } else {
+        (
+            get_str(outbound, "server"),
+            outbound["server_port"].as_u64().unwrap_or(0),
+        )
+    };
+    if server.is_empty() || port == 0 {
+        return None;
+    }
+
+    let public_key = get_opt_str(peer, "public_key")
+        .or_else(|| get_opt_str(outbound, "peer_public_key"))
+        .unwrap_or_default();
+    let pre_shared_key = get_opt_str(peer, "pre_shared_key")
+        .or_else(|| get_opt_str(outbound, "pre_shared_key"))
+        .unwrap_or_default();
+
+    let mut local_address = get_str_list(&outbound["local_address"]);
+    local_address.extend(get_str_list(&outbound["address"]));
+    let strip_prefix = |addr: &String| addr.split('/').next().unwrap_or("").to_string();
+    let self_ip = local_address
+        .iter()
+        .find(|addr| !addr.contains(':'))
+        .map(strip_prefix)
+        .unwrap_or_default();
+    let self_ipv6 = local_address
+        .iter()
+        .find(|addr| addr.contains(':'))
+        .map(strip_prefix)
+        .unwrap_or_default();
+
+    let reserved = match peer["reserved"]
+        .as_array()
+        .or(outbound["reserved"].as_array())
+    {
+        Some(values) => values
+            .iter()
+            .filter_map(|v| v.as_u64())
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        None => String::new(),
+    };
+
+    let mut node = Proxy::wireguard_construct(
+        WG_DEFAULT_GROUP.to_string(),
+        remark,
+        server.to_string(),
+        port as u16,
+        self_ip,
+        self_ipv6,
+        get_str(outbound, "private_key").to_string(),
+        public_key,
+        pre_shared_key,
+        Vec::new(),
+        outbound["mtu"].as_u64().map(|v| v as u16),
+        peer["persistent_keepalive_interval"]
+            .as_u64()
+            .map(|v| v as u16),
+        String::new(),
+        reserved,
+        None,
+        None,
+    );
+    node.allowed_ips = get_str_list(&peer["allowed_ips"]).join(",");
+
+    Some(node)
+}
+
+/// Parse a sing-box client configuration into a vector of Proxy objects
+///
+/// Reads proxy outbounds from the `outbounds` array, as well as WireGuard
+/// nodes from the `endpoints` array introduced in sing-box 1.11.
+pub fn explode_singbox(content: &str, nodes: &mut Vec<Proxy>) -> bool {
+    let json: Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(_) => return false,
+    };
+
+    let mut outbounds: Vec<&Value> = Vec::new();
+    for key in ["outbounds", "endpoints"] {
+        if let Some(items) = json[key].as_array() {
+            outbounds.extend(items.iter());
+        }
+    }
+    if outbounds.is_empty() {
+        return false;
+    }
+
+    let orig_size = nodes.len();
+    for outbound in outbounds {
+        if let Some(node) = parse_singbox_outbound(outbound) {
+            nodes.push(node);
+        }
+    }
+
+    nodes.len() > orig_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SINGBOX_CONFIG: &str = r#"{
+        "outbounds": [
+            { "type": "selector", "tag": "proxy", "outbounds": ["ss", "vless-reality"] },
+            { "type": "urltest", "tag": "auto", "outbounds": ["ss"] },
+            { "type": "direct", "tag": "direct" },
+            { "type": "block", "tag": "block" },
+            { "type": "dns", "tag": "dns-out" },
+            {
+                "type": "shadowsocks", "tag": "ss", "server": "1.2.3.4", "server_port": 8388,
+                "method": "2022-blake3-aes-128-gcm", "password": "c2VjcmV0"
+            },
+            {
+                "type": "vmess", "tag": "vmess-ws", "server": "vmess.example.com", "server_port": 443,
+                "uuid": "b831381d-6324-4d53-ad4f-8cda48b30811", "security": "auto", "alter_id": 0,
+                "tls": { "enabled": true, "server_name": "vmess.example.com" },
+                "transport": { "type": "ws", "path": "/ray", "headers": { "Host": "cdn.example.com" } }
+            },
+            {
+                "type": "vless", "tag": "vless-reality", "server": "reality.example.com", "server_port": 443,
+                "uuid": "b831381d-6324-4d53-ad4f-8cda48b30811", "flow": "xtls-rprx-vision",
+                "tls": {
+                    "enabled": true, "server_name": "www.microsoft.com",
+                    "utls": { "enabled": true, "fingerprint": "chrome" },
+                    "reality": { "enabled": true, "public_key": "pubkey", "short_id": "0123abcd" }
+                }
+            },
+            {
+                "type": "vless", "tag": "vless-grpc", "server": "grpc.example.com", "server_port": 443,
+                "uuid": "b831381d-6324-4d53-ad4f-8cda48b30811",
+                "tls": { "enabled": true },
+                "transport": { "type": "grpc", "service_name": "grpc-svc" }
+            },
+            {
+                "type": "trojan", "tag": "trojan", "server": "trojan.example.com", "server_port": 443,
+                "password": "trojan-pass", "tls": { "enabled": true, "server_name": "trojan.example.com", "insecure": true }
+            },
+            {
+                "type": "hysteria", "tag": "hy1", "server": "hy.example.com", "server_port": 443,
+                "up_mbps": 50, "down_mbps": 100, "auth_str": "auth", "obfs": "obfs-pass",
+                "tls": { "enabled": true, "server_name": "hy.example.com", "alpn": ["h3"] }
+            },
+            {
+                "type": "hysteria2", "tag": "hy2", "server": "hy2.example.com", "server_port": 443,
+                "server_ports": ["20000:30000"], "hop_interval": "30s", "password": "hy2-pass",
+                "obfs": { "type": "salamander", "password": "obfs" },
+                "tls": { "enabled": true, "server_name": "hy2.example.com" }
+            },
+            {
+                "type": "tuic", "tag": "tuic", "server": "tuic.example.com", "server_port": 443,
+                "uuid": "b831381d-6324-4d53-ad4f-8cda48b30811", "password": "tuic-pass",
+                "congestion_control": "bbr", "udp_relay_mode": "native", "heartbeat": "10s",
+                "tls": { "enabled": true, "server_name": "tuic.example.com", "alpn": ["h3"] }
+            },
+            {
+                "type": "anytls", "tag": "anytls", "server": "anytls.example.com", "server_port": 443,
+                "password": "anytls-pass", "idle_session_check_interval": "30s",
+                "tls": { "enabled": true, "server_name": "anytls.example.com" }
+            },
+            {
+                "type": "wireguard", "tag": "wg-legacy", "server": "wg.example.com", "server_port": 51820,
+                "local_address": ["172.16.0.2/32", "fd01::2/128"], "private_key": "priv",
+                "peer_public_key": "pub", "reserved": [1, 2, 3], "mtu": 1280
+            }
+        ],
+        "endpoints": [
+            {
+                "type": "wireguard", "tag": "wg-endpoint", "address": ["10.0.0.2/32"], "private_key": "priv2",
+                "peers": [{ "address": "wg2.example.com", "port": 51821, "public_key": "pub2", "allowed_ips": ["0.0.0.0/0"] }]
+            }
+        ]
+    }"#;
+
+    fn parse_all() -> Vec<Proxy> {
+        let mut nodes = Vec::new();
+        assert!(explode_singbox(SINGBOX_CONFIG, &mut nodes));
+        nodes
+    }
+
+    fn find<'a>(nodes: &'a [Proxy], remark: &str) -> &'a Proxy {
+        nodes.iter().find(|n| n.remark == remark).unwrap()
+    }
+
+    #[test]
+    fn test_explode_singbox_skips_non_proxy_outbounds() {
+        let nodes = parse_all();
+        assert_eq!(nodes.len(), 11);
+        for skipped in ["proxy", "auto", "direct", "block", "dns-out"] {
+            assert!(nodes.iter().all(|n| n.remark != skipped));
+        }
+    }
+
+    #[test]
+    fn test_explode_singbox_vmess_ws() {
+        let nodes = parse_all();
+        let node = find(&nodes, "vmess-ws");
+        assert_eq!(node.proxy_type, ProxyType::VMess);
+        assert_eq!(node.transfer_protocol.as_deref(), Some("ws"));
+        assert_eq!(node.path.as_deref(), Some("/ray"));
+        assert_eq!(node.host.as_deref(), Some("cdn.example.com"));
+        assert!(node.tls_secure);
+    }
+
+    #[test]
+    fn test_explode_singbox_vless_reality() {
+        let nodes = parse_all();
+        let node = find(&nodes, "vless-reality");
+        assert_eq!(node.proxy_type, ProxyType::Vless);
+        match &node.combined_proxy {
+            Some(CombinedProxy::Vless(vless)) => {
+                assert_eq!(vless.flow.as_deref(), Some("xtls-rprx-vision"));
+                assert_eq!(vless.reality_public_key.as_deref(), Some("pubkey"));
+                assert_eq!(vless.reality_short_id.as_deref(), Some("0123abcd"));
+                assert_eq!(vless.client_fingerprint.as_deref(), Some("chrome"));
+                assert_eq!(vless.servername.as_deref(), Some("www.microsoft.com"));
+                assert_eq!(vless.network.as_deref(), Some("tcp"));
+            }
+            _ => panic!("expected a VLESS combined proxy"),
+        }
+
+        let grpc = find(&nodes, "vless-grpc");
+        match &grpc.combined_proxy {
+            Some(CombinedProxy::Vless(vless)) => {
+                assert_eq!(vless.network.as_deref(), Some("grpc"));
+                assert_eq!(vless.grpc_service_name.as_deref(), Some("grpc-svc"));
+            }
+            _ => panic!("expected a VLESS combined proxy"),
+        }
+    }
+
+    #[test]
+    fn test_explode_singbox_plain_transports() {
+        let config = r#"{
+            "outbounds": [
+                {
+                    "type": "trojan", "tag": "trojan-no-tls", "server": "trojan.example.com",
+                    "server_port": 80, "password": "trojan-pass"
+                },
+                {
+                    "type": "trojan", "tag": "trojan-http", "server": "trojan.example.com",
+                    "server_port": 80, "password": "trojan-pass", "tls": { "enabled": false },
+                    "transport": { "type": "http", "path": "/http" }
+                },
+                {
+                    "type": "vmess", "tag": "vmess-upgrade", "server": "vmess.example.com",
+                    "server_port": 80, "uuid": "b831381d-6324-4d53-ad4f-8cda48b30811",
+                    "transport": { "type": "httpupgrade", "path": "/upgrade", "host": "cdn.example.com" }
+                },
+                {
+                    "type": "vless", "tag": "vless-upgrade", "server": "vless.example.com",
+                    "server_port": 80, "uuid": "b831381d-6324-4d53-ad4f-8cda48b30811",
+                    "transport": { "type": "httpupgrade", "path": "/upgrade" }
+                }
+            ]
+        }"#;
+        let mut nodes = Vec::new();
+        assert!(explode_singbox(config, &mut nodes));
+
+        let trojan = find(&nodes, "trojan-no-tls");
+        assert_eq!(trojan.proxy_type, ProxyType::Trojan);
+        assert!(!trojan.tls_secure);
+
+        let trojan = find(&nodes, "trojan-http");
+        assert!(!trojan.tls_secure);
+        assert_eq!(trojan.transfer_protocol.as_deref(), Some("http"));
+        assert_eq!(trojan.path.as_deref(), Some("/http"));
+
+        let vmess = find(&nodes, "vmess-upgrade");
+        assert_eq!(vmess.transfer_protocol.as_deref(), Some("httpupgrade"));
+        assert_eq!(vmess.path.as_deref(), Some("/upgrade"));
+
+        match &find(&nodes, "vless-upgrade").combined_proxy {
+            Some(CombinedProxy::Vless(vless)) => {
+                assert_eq!(vless.network.as_deref(), Some("httpupgrade"));
+                assert_eq!(vless.ws_path.as_deref(), Some("/upgrade"));
+            }
+            _ => panic!("expected a VLESS combined proxy"),
+        }
+    }
+
+    #[test]
+    fn test_explode_singbox_hysteria_family() {
+        let nodes = parse_all();
+        let hy1 = find(&nodes, "hy1");
+        assert_eq!(hy1.proxy_type, ProxyType::Hysteria);
+        assert_eq!(hy1.up_speed, 50);
+        assert_eq!(hy1.down_speed, 100);
+
+        let hy2 = find(&nodes, "hy2");
+        assert_eq!(hy2.proxy_type, ProxyType::Hysteria2);
+        assert_eq!(hy2.password.as_deref(), Some("hy2-pass"));
+        assert_eq!(hy2.obfs.as_deref(), Some("salamander"));
+        assert_eq!(hy2.ports.as_deref(), Some("20000-30000"));
+        assert_eq!(hy2.hop_interval, 30);
+    }
+
+    #[test]
+    fn test_explode_singbox_tuic_and_anytls() {
+        let nodes = parse_all();
+        let tuic = find(&nodes, "tuic");
+        match &tuic.combined_proxy {
+            Some(CombinedProxy::Tuic(tuic)) => {
+                assert_eq!(tuic.password, "tuic-pass");
+                assert_eq!(tuic.congestion_control.as_deref(), Some("bbr"));
+                assert_eq!(tuic.heartbeat_interval, Some(10_000));
+            }
+            _ => panic!("expected a TUIC combined proxy"),
+        }
+
+        let anytls = find(&nodes, "anytls");
+        assert_eq!(anytls.group, ANYTLS_DEFAULT_GROUP);
+        match &anytls.combined_proxy {
+            Some(CombinedProxy::AnyTls(anytls)) => {
+                assert_eq!(anytls.password, "anytls-pass");
+                assert_eq!(anytls.idle_session_check_interval, Some(30));
+            }
+            _ => panic!("expected an AnyTLS combined proxy"),
+        }
+    }
+
+    #[test]
+    fn test_explode_singbox_wireguard() {
+        let nodes = parse_all();
+        let legacy = find(&nodes, "wg-legacy");
+        assert_eq!(legacy.proxy_type, ProxyType::WireGuard);
+        assert_eq!(legacy.self_ip.as_deref(), Some("172.16.0.2"));
+        assert_eq!(legacy.self_ipv6.as_deref(), Some("fd01::2"));
+        assert_eq!(legacy.client_id.as_deref(), Some("1,2,3"));
+        assert_eq!(legacy.mtu, 1280);
+
+        let endpoint = find(&nodes, "wg-endpoint");
+        assert_eq!(endpoint.hostname, "wg2.example.com");
+        assert_eq!(endpoint.port, 51821);
+        assert_eq!(endpoint.public_key.as_deref(), Some("pub2"));
+        assert_eq!(endpoint.allowed_ips, "0.0.0.0/0");
+    }
+
+    #[test]
+    fn test_explode_sub_detects_singbox() {
+        let mut nodes = Vec::new();
+        assert!(crate::parser::explodes::explode_sub(
+            SINGBOX_CONFIG,
+            &mut nodes
+        ));
+        assert_eq!(nodes.len(), 11);
+    }
+
+    #[test]
+    fn test_parse_duration_ms() {
+        assert_eq!(parse_duration_ms(&Value::from("30s")), Some(30_000));
+        assert_eq!(parse_duration_ms(&Value::from("1m30s")), Some(90_000));
+        assert_eq!(parse_duration_ms(&Value::from("500ms")), Some(500));
+        assert_eq!(parse_duration_ms(&Value::from("bogus")), None);
+    }
+}
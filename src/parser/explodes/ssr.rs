@@ -1,71 +1,87 @@
 use crate::models::{Proxy, SSR_DEFAULT_GROUP, SS_CIPHERS};
-use crate::utils::base64::url_safe_base64_decode;
+use crate::utils::base64::url_safe_base64_decode_lenient;
 use serde_json::Value;
-use url::Url;
 
 /// Parse a ShadowsocksR link into a Proxy object
 /// Based on the C++ implementation in explodeSSR function
+///
+/// Accepts both URL-safe and standard base64 bodies with or without padding,
+/// as found in real-world SSR subscriptions.
 pub fn explode_ssr(ssr: &str, node: &mut Proxy) -> bool {
     // Check if the link starts with ssr://
     if !ssr.starts_with("ssr://") {
         return false;
     }
 
-    // Extract the base64 part and decode it
-    let encoded = &ssr[6..];
+    // Extract the base64 part, dropping any fragment some clients append
+    let encoded = ssr[6..].split('#').next().unwrap_or("").trim();
+    if encoded.is_empty() {
+        return false;
+    }
 
     // Decode base64
-    let mut decoded = url_safe_base64_decode(encoded);
-    if decoded.is_empty() {
+    let mut decoded = url_safe_base64_decode_lenient(encoded);
+    if decoded.is_empty() || decoded == encoded {
         return false;
     }
 
     // Replace \r with empty string
-    decoded = decoded.replace('\r', "");
+    decoded = decoded.replace('\r', "").trim().to_string();
 
     // Extract query parameters if present
-    let mut _strobfs = String::new();
     let mut group = String::new();
     let mut remarks = String::new();
     let mut obfsparam = String::new();
     let mut protoparam = String::new();
 
-    if let Some(query_pos) = decoded.find("/?") {
-        _strobfs = decoded[query_pos + 2..].to_string();
-        decoded = decoded[..query_pos].to_string();
-
-        // Parse query parameters
-        let url_str = format!("http://localhost/?{}", _strobfs);
-        if let Ok(url) = Url::parse(&url_str) {
-            for (key, value) in url.query_pairs() {
-                let decoded_value = url_safe_base64_decode(&value);
-
-                match key.as_ref() {
-                    "group" => group = decoded_value,
-                    "remarks" => remarks = decoded_value,
-                    "obfsparam" => obfsparam = decoded_value.replace(" ", ""),
-                    "protoparam" => protoparam = decoded_value.replace(" ", ""),
-                    _ => {}
-                }
+    let query_pos = decoded.find("/?").or_else(|| decoded.find('?'));
+    if let Some(query_pos) = query_pos {
+        let strobfs = decoded[query_pos..]
+            .trim_start_matches('/')
+            .trim_start_matches('?')
+            .to_string();
+        decoded = decoded[..query_pos].trim_end_matches('/').to_string();
+
+        // Parse query parameters by hand, as form decoding would turn the
+        // '+' of standard base64 values into spaces
+        for pair in strobfs.split('&') {
+            let (key, value) = match pair.split_once('=') {
+                Some((key, value)) => (key, value),
+                None => continue,
+            };
+            let decoded_value = url_safe_base64_decode_lenient(value.trim());
+
+            match key.trim() {
+                "group" => group = decoded_value,
+                "remarks" => remarks = decoded_value,
+                "obfsparam" => obfsparam = decoded_value.replace(' ', ""),
+                "protoparam" => protoparam = decoded_value.replace(' ', ""),
+                _ => {}
             }
         }
     }
 
-    // Parse the main part of the URL (server:port:protocol:method:obfs:password)
-    let parts: Vec<&str> = decoded.split(':').collect();
+    // Parse the main part of the URL (server:port:protocol:method:obfs:password).
+    // Split from the right so IPv6 server addresses stay intact.
+    let mut parts: Vec<&str> = decoded.rsplitn(6, ':').collect();
     if parts.len() < 6 {
         return false;
     }
+    parts.reverse();
 
-    let server = parts[0];
+    let server = parts[0].trim_start_matches('[').trim_end_matches(']');
     let port_str = parts[1];
     let protocol = parts[2];
     let method = parts[3];
     let obfs = parts[4];
     let password_encoded = parts[5];
 
+    if server.is_empty() || method.is_empty() {
+        return false;
+    }
+
     // Decode password (base64 encoded)
-    let password = url_safe_base64_decode(password_encoded);
+    let password = url_safe_base64_decode_lenient(password_encoded);
 
     // Parse port
     let port = match port_str.parse::<u16>() {
@@ -87,7 +103,7 @@ pub fn explode_ssr(ssr: &str, node: &mut Proxy) -> bool {
     }
 
     // Check if this should be an SS or SSR proxy
-    if SS_CIPHERS.iter().any(|c| *c == method)
+    if SS_CIPHERS.contains(&method)
         && (obfs.is_empty() || obfs == "plain")
         && (protocol.is_empty() || protocol == "origin")
     {
@@ -403,4 +419,108 @@ mod tests {
         assert_eq!(nodes[0].remark, "example.com (8388)");
         assert_eq!(nodes[0].group, SSR_DEFAULT_GROUP);
     }
+
+    /// Real-world SSR links as produced by common clients and airport
+    /// subscriptions, covering URL-safe and standard alphabets, missing or
+    /// superfluous padding, IPv6 servers and SS-compatible parameters.
+    const SSR_FIXTURES: &[&str] = &[
+        // ShadowsocksR-Android export: URL-safe, unpadded, UTF-8 remarks/group
+        "ssr://aGswMS5leGFtcGxlLm5ldDoxMDA4NjphdXRoX2NoYWluX2E6Y2hhY2hhMjAtaWV0Zjp0bHMxLjJfdGlja2V0X2F1dGg6VUdFMU5YY3djbVEvP29iZnNwYXJhbT1aRzkzYm14dllXUXVkMmx1Wkc5M2MzVndaR0YwWlM1amIyMCZwcm90b3BhcmFtPU1USXpORFU2WVdKalpHVm0mcmVtYXJrcz04Si1IcmZDZmg3QWc2YWFaNXJpdklEQXhJSHdnTVhnJmdyb3VwPTVweTY1Wnk2Nks2aTZaaUY",
+        // Panel export: standard alphabet with '+' and '/' plus padding
+        "ssr://anAuZXhhbXBsZS5vcmc6NDQzOmF1dGhfYWVzMTI4X3NoYTE6YWVzLTEyOC1jdHI6aHR0cF9zaW1wbGU6Y0Q0L1BqOD0vP29iZnNwYXJhbT1ZVE5tTVM1cWNDNWxlR0Z0Y0d4bExtOXladz09JnByb3RvcGFyYW09dzcvRHZpc3YmcmVtYXJrcz1TbEFnNG9tcklGUnZhM2x2Jmdyb3VwPVVISnZkbWxrWlhKK2ZnPT0=",
+        // IPv6 server address
+        "ssr://MjAwMTpkYjg6OjE6ODQ0MzpvcmlnaW46YWVzLTI1Ni1jZmI6cGxhaW46ZGpad1lYTnovP3JlbWFya3M9U1ZCMk5pQk9iMlJs",
+        // origin/plain with an SS cipher, URL-safe '_' in the body
+        "ssr://MS4yLjMuNDo4Mzg4Om9yaWdpbjphZXMtMjU2LWdjbTpwbGFpbjpjM053WVhOei8_cmVtYXJrcz1VR3hoYVc0Z1UxTSZncm91cD1VMU5TSUVkeWIzVnc",
+        // No query string, superfluous padding and trailing CRLF
+        "ssr://dXMuZXhhbXBsZS5jb206MjMzMzphdXRoX3NoYTFfdjQ6cmM0LW1kNTpodHRwX3Bvc3Q6ZEdWemRB==\r\n",
+    ];
+
+    #[test]
+    fn test_explode_ssr_fixtures_all_parse() {
+        for link in SSR_FIXTURES {
+            let mut node = Proxy::default();
+            assert!(
+                crate::parser::explodes::explode(link, &mut node),
+                "failed to parse {}",
+                link
+            );
+            assert!(node.port > 0);
+            assert!(!node.hostname.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_explode_ssr_fixture_urlsafe_unicode() {
+        let mut node = Proxy::default();
+        assert!(explode_ssr(SSR_FIXTURES[0], &mut node));
+        assert_eq!(node.proxy_type, ProxyType::ShadowsocksR);
+        assert_eq!(node.hostname, "hk01.example.net");
+        assert_eq!(node.port, 10086);
+        assert_eq!(node.protocol.as_deref(), Some("auth_chain_a"));
+        assert_eq!(node.encrypt_method.as_deref(), Some("chacha20-ietf"));
+        assert_eq!(node.password.as_deref(), Some("Pa55w0rd"));
+        assert_eq!(
+            node.obfs_param.as_deref(),
+            Some("download.windowsupdate.com")
+        );
+        assert_eq!(node.protocol_param.as_deref(), Some("12345:abcdef"));
+        assert_eq!(node.remark, "🇭🇰 香港 01 | 1x");
+        assert_eq!(node.group, "机场订阅");
+    }
+
+    #[test]
+    fn test_explode_ssr_fixture_standard_alphabet() {
+        let mut node = Proxy::default();
+        assert!(explode_ssr(SSR_FIXTURES[1], &mut node));
+        assert_eq!(node.hostname, "jp.example.org");
+        assert_eq!(node.port, 443);
+        assert_eq!(node.obfs.as_deref(), Some("http_simple"));
+        assert_eq!(node.password.as_deref(), Some("p>?>?"));
+        assert_eq!(node.obfs_param.as_deref(), Some("a3f1.jp.example.org"));
+        assert_eq!(node.protocol_param.as_deref(), Some("ÿþ+/"));
+        assert_eq!(node.remark, "JP ≫ Tokyo");
+        assert_eq!(node.group, "Provider~~");
+    }
+
+    #[test]
+    fn test_explode_ssr_fixture_ipv6() {
+        let mut node = Proxy::default();
+        assert!(explode_ssr(SSR_FIXTURES[2], &mut node));
+        assert_eq!(node.hostname, "2001:db8::1");
+        assert_eq!(node.port, 8443);
+        assert_eq!(node.remark, "IPv6 Node");
+    }
+
+    #[test]
+    fn test_explode_ssr_fixture_ss_compatible() {
+        let mut node = Proxy::default();
+        assert!(explode_ssr(SSR_FIXTURES[3], &mut node));
+        assert_eq!(node.proxy_type, ProxyType::Shadowsocks);
+        assert_eq!(node.encrypt_method.as_deref(), Some("aes-256-gcm"));
+        assert_eq!(node.password.as_deref(), Some("sspass"));
+        assert_eq!(node.remark, "Plain SS");
+        assert_eq!(node.group, "SSR Group");
+    }
+
+    #[test]
+    fn test_explode_ssr_fixture_bad_padding() {
+        let mut node = Proxy::default();
+        assert!(explode_ssr(SSR_FIXTURES[4].trim(), &mut node));
+        assert_eq!(node.hostname, "us.example.com");
+        assert_eq!(node.port, 2333);
+        assert_eq!(node.password.as_deref(), Some("test"));
+        assert_eq!(node.group, SSR_DEFAULT_GROUP);
+        assert_eq!(node.remark, "us.example.com (2333)");
+    }
+
+    #[test]
+    fn test_explode_ssr_invalid_port() {
+        let mut node = Proxy::default();
+        let link = format!(
+            "ssr://{}",
+            STANDARD.encode("example.com:0:origin:aes-256-cfb:plain:dGVzdA")
+        );
+        assert!(!explode_ssr(&link, &mut node));
+    }
 }
@@ -60,15 +60,21 @@ pub fn explode_vless(vless: &str, node: &mut Proxy) -> bool {
 
     let sni = params.get("sni").map(|s| s.to_string());
 
+    let allow_insecure = params
+        .get("allowInsecure")
+        .or_else(|| params.get("insecure"))
+        .map(|s| s == "1" || s.to_lowercase() == "true");
+
     let flow = params.get("flow").map(|s| s.to_string());
 
     let packet_encoding = params.get("packetEncoding").map(|s| s.to_string());
     let packet_addr = packet_encoding.as_deref() == Some("packet");
 
-    let network = params
-        .get("type")
-        .map(|s| s.to_lowercase())
-        .unwrap_or_else(|| "tcp".to_string());
+    let network = match params.get("type").map(|s| s.to_lowercase()) {
+        Some(network) if network == "splithttp" => "xhttp".to_string(),
+        Some(network) => network,
+        None => "tcp".to_string(),
+    };
 
     let fake_type = params
         .get("headerType")
@@ -89,6 +95,7 @@ pub fn explode_vless(vless: &str, node: &mut Proxy) -> bool {
     vless_proxy.servername = sni;
     vless_proxy.client_fingerprint = Some(fingerprint);
     vless_proxy.flow = flow;
+    vless_proxy.skip_cert_verify = allow_insecure;
 
     // Handle Reality options
     if let Some(public_key) = params.get("pbk") {
@@ -159,6 +166,21 @@ pub fn explode_vless(vless: &str, node: &mut Proxy) -> bool {
         "grpc" => {
             vless_proxy.grpc_service_name = params.get("serviceName").map(|s| s.to_string());
         }
+        "xhttp" => {
+            vless_proxy.xhttp_path = Some(
+                params
+                    .get("path")
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "/".to_string()),
+            );
+            vless_proxy.xhttp_host = params.get("host").map(|s| s.to_string());
+            vless_proxy.xhttp_mode = params.get("mode").map(|s| s.to_string());
+            // extra is a JSON object, only keep it when it parses
+            vless_proxy.xhttp_extra = params
+                .get("extra")
+                .filter(|extra| serde_json::from_str::<serde_json::Value>(extra).is_ok())
+                .map(|s| s.to_string());
+        }
         _ => {}
     }
 
@@ -167,6 +189,139 @@ pub fn explode_vless(vless: &str, node: &mut Proxy) -> bool {
     node.remark = url_decode(url.fragment().unwrap_or(""));
     node.hostname = host.to_string();
     node.port = port;
+    node.allow_insecure = allow_insecure;
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::config::formats::single::{proxy_to_single, ProxyUriTypes};
+    use crate::models::ExtraSettings;
+
+    fn parse(link: &str) -> VlessProxy {
+        let mut node = Proxy::default();
+        assert!(explode_vless(link, &mut node));
+        match node.combined_proxy {
+            Some(CombinedProxy::Vless(vless)) => vless,
+            _ => panic!("expected a VLESS proxy"),
+        }
+    }
+
+    /// Parse a link, write it back with `proxy_to_single` and parse the output again
+    fn round_trip(link: &str) -> (Proxy, Proxy) {
+        let mut node = Proxy::default();
+        assert!(explode_vless(link, &mut node));
+
+        let mut ext = ExtraSettings::default();
+        ext.nodelist = true;
+        let output = proxy_to_single(&mut vec![node.clone()], ProxyUriTypes::VLESS, &mut ext);
+
+        let mut reparsed = Proxy::default();
+        assert!(explode_vless(output.trim(), &mut reparsed));
+        assert_eq!(reparsed.remark, node.remark);
+        assert_eq!(reparsed.hostname, node.hostname);
+        assert_eq!(reparsed.port, node.port);
+        (node, reparsed)
+    }
+
+    fn vless_of(node: &Proxy) -> &VlessProxy {
+        match node.combined_proxy {
+            Some(CombinedProxy::Vless(ref vless)) => vless,
+            _ => panic!("expected a VLESS proxy"),
+        }
+    }
+
+    #[test]
+    fn test_explode_vless_xhttp() {
+        let vless = parse(
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@x.example.com:443?security=tls&type=xhttp\
+             &path=%2Fxh&host=cdn.example.com&mode=packet-up\
+             &extra=%7B%22xPaddingBytes%22%3A%22100-1000%22%7D#xhttp",
+        );
+        assert_eq!(vless.network.as_deref(), Some("xhttp"));
+        assert_eq!(vless.xhttp_path.as_deref(), Some("/xh"));
+        assert_eq!(vless.xhttp_host.as_deref(), Some("cdn.example.com"));
+        assert_eq!(vless.xhttp_mode.as_deref(), Some("packet-up"));
+        assert_eq!(
+            vless.xhttp_extra.as_deref(),
+            Some(r#"{"xPaddingBytes":"100-1000"}"#)
+        );
+
+        // SplitHTTP is the former name of XHTTP
+        let vless =
+            parse("vless://b831381d-6324-4d53-ad4f-8cda48b30811@x.example.com:443?type=splithttp");
+        assert_eq!(vless.network.as_deref(), Some("xhttp"));
+        assert_eq!(vless.xhttp_path.as_deref(), Some("/"));
+    }
+
+    #[test]
+    fn test_explode_vless_httpupgrade() {
+        let vless = parse(
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@u.example.com:80?type=httpupgrade\
+             &path=%2Fup&host=cdn.example.com#upgrade",
+        );
+        assert_eq!(vless.network.as_deref(), Some("httpupgrade"));
+        assert_eq!(vless.ws_path.as_deref(), Some("/up"));
+        assert_eq!(
+            vless.ws_headers.unwrap().get("Host").map(|s| s.as_str()),
+            Some("cdn.example.com")
+        );
+    }
+
+    #[test]
+    fn test_vless_reality_link_round_trip() {
+        let (node, reparsed) = round_trip(
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@reality.example.com:443?encryption=none\
+             &security=reality&sni=www.microsoft.com&fp=chrome&pbk=pubkey&sid=6ba85179e30d4fc2\
+             &flow=xtls-rprx-vision&type=grpc&serviceName=svc#REALITY%20Node",
+        );
+        let (before, after) = (vless_of(&node), vless_of(&reparsed));
+        assert!(after.tls);
+        assert_eq!(after.uuid, before.uuid);
+        assert_eq!(after.flow.as_deref(), Some("xtls-rprx-vision"));
+        assert_eq!(after.servername, before.servername);
+        assert_eq!(after.client_fingerprint, before.client_fingerprint);
+        assert_eq!(after.reality_public_key.as_deref(), Some("pubkey"));
+        assert_eq!(after.reality_short_id, before.reality_short_id);
+        assert_eq!(after.network.as_deref(), Some("grpc"));
+        assert_eq!(after.grpc_service_name.as_deref(), Some("svc"));
+    }
+
+    #[test]
+    fn test_vless_transport_link_round_trip() {
+        for link in [
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@ws.example.com:443?security=tls&sni=ws.example.com\
+             &alpn=h2,http%2F1.1&allowInsecure=1&type=ws&host=cdn.example.com&path=%2Fws%3Fed%3D2048#ws",
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@u.example.com:80?type=httpupgrade\
+             &host=cdn.example.com&path=%2Fup#upgrade",
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@h2.example.com:443?security=tls&type=h2\
+             &host=cdn.example.com&path=%2Fh2#h2",
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@x.example.com:443?security=tls&type=xhttp\
+             &path=%2Fxh&host=cdn.example.com&mode=packet-up\
+             &extra=%7B%22xPaddingBytes%22%3A%22100-1000%22%7D#xhttp",
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@tcp.example.com:80?type=tcp&headerType=http\
+             &host=cdn.example.com&path=%2Fobfs#tcp-http",
+            "vless://b831381d-6324-4d53-ad4f-8cda48b30811@[2001:db8::1]:443?security=tls&type=tcp\
+             &headerType=none#ipv6",
+        ] {
+            let (node, reparsed) = round_trip(link);
+            let (before, after) = (vless_of(&node), vless_of(&reparsed));
+            assert_eq!(after.tls, before.tls, "{}", link);
+            assert_eq!(after.alpn, before.alpn, "{}", link);
+            assert_eq!(after.skip_cert_verify, before.skip_cert_verify, "{}", link);
+            assert_eq!(after.network, before.network, "{}", link);
+            assert_eq!(after.ws_path, before.ws_path, "{}", link);
+            assert_eq!(after.ws_headers, before.ws_headers, "{}", link);
+            assert_eq!(after.h2_path, before.h2_path, "{}", link);
+            assert_eq!(after.h2_host, before.h2_host, "{}", link);
+            assert_eq!(after.http_path, before.http_path, "{}", link);
+            assert_eq!(after.http_headers, before.http_headers, "{}", link);
+            assert_eq!(after.xhttp_path, before.xhttp_path, "{}", link);
+            assert_eq!(after.xhttp_host, before.xhttp_host, "{}", link);
+            assert_eq!(after.xhttp_mode, before.xhttp_mode, "{}", link);
+            assert_eq!(after.xhttp_extra, before.xhttp_extra, "{}", link);
+        }
+    }
+}
@@ -3,7 +3,6 @@ use crate::{
     utils::{base64::url_safe_base64_decode, url_decode},
 };
 use base64::{engine::general_purpose::STANDARD, Engine};
-use regex::Regex;
 use serde_json::Value;
 use std::collections::HashMap;
 use url::Url;
@@ -102,111 +101,129 @@ pub fn explode_vmess(vmess: &str, node: &mut Proxy) -> bool {
 }
 
 /// Parse a standard VMess link into a Proxy object
-/// Format: vmess[+tls]://uuid-alterId@hostname:port[/?network=ws&host=xxx&
-/// path=yyy]
+/// Format: vmess[+network][+tls]://uuid[-alterId]@hostname:port[/?query][#remark]
+///
+/// The transport and TLS may be encoded in the scheme (V2RayN std and
+/// Kitsunebi style, e.g. `vmess+ws+tls://`) or in the query string
+/// (`network=ws&tls=1`), the query taking precedence.
 pub fn explode_std_vmess(vmess: &str, node: &mut Proxy) -> bool {
-    // Check if the link starts with vmess:// or vmess+tls://
-    if !vmess.starts_with("vmess://") && !vmess.starts_with("vmess+") {
+    let url = match Url::parse(vmess) {
+        Ok(url) => url,
+        Err(_) => return false,
+    };
+
+    // Split the scheme into its transport and TLS parts
+    let mut scheme_parts = url.scheme().split('+');
+    if scheme_parts.next() != Some("vmess") {
         return false;
     }
+    let mut net = "tcp".to_string();
+    let mut tls = String::new();
+    for part in scheme_parts {
+        match part {
+            "tls" => tls = "tls".to_string(),
+            "tcp" | "kcp" | "ws" | "http" | "h2" | "quic" | "grpc" | "httpupgrade" => {
+                net = part.to_string()
+            }
+            _ => {
+                log::warn!("Unknown vmess link scheme part '{}': {}", part, vmess);
+                return false;
+            }
+        }
+    }
 
-    // Extract the protocol part and check TLS
-    let protocol_end = match vmess.find("://") {
-        Some(pos) => pos,
-        None => return false,
+    let host = match url.host_str() {
+        Some(host) if !host.is_empty() => host.trim_matches(|c| c == '[' || c == ']'),
+        _ => return false,
     };
-
-    let protocol = vmess[..protocol_end].to_string();
-    let tls = protocol.contains("+tls");
-
-    // Extract the rest of the URL
-    let url_part = &vmess[protocol_end + 3..];
-
-    // Split URL and fragment (remark)
-    let (url_without_fragment, remark) = match url_part.find('#') {
-        Some(pos) => (url_part[..pos].to_string(), url_part[pos + 1..].to_string()),
-        None => (url_part.to_string(), String::new()),
+    let port = match url.port() {
+        Some(port) if port != 0 => port,
+        _ => return false,
     };
 
-    // Parse the URL-like string
-    let re = Regex::new(
-        r"^([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})-(\d+)@([^:]+):(\d+)(.*)$",
-    )
-    .unwrap();
-
-    let caps = match re.captures(&url_without_fragment) {
-        Some(c) => c,
-        None => {
-            log::warn!("Failed to explode vmess link by regex: {}", vmess);
-            return false;
-        }
+    // The user info is either a bare UUID or UUID-alterId
+    let user_info = url.username();
+    let (id, mut aid) = match user_info.rsplit_once('-') {
+        Some((id, aid)) if user_info.len() > 36 => match aid.parse::<u16>() {
+            Ok(aid) => (id.to_string(), aid),
+            Err(_) => return false,
+        },
+        _ => (user_info.to_string(), 0),
     };
+    if id.is_empty() {
+        return false;
+    }
 
-    let id = caps.get(1).map_or("", |m| m.as_str()).to_string();
-    let aid = caps
-        .get(2)
-        .map_or("0", |m| m.as_str())
-        .parse::<u16>()
-        .unwrap_or(0);
-    let host = caps.get(3).map_or("", |m| m.as_str()).to_string();
-    let port = caps
-        .get(4)
-        .map_or("0", |m| m.as_str())
-        .parse::<u16>()
-        .unwrap_or(0);
-    let query = caps.get(5).map_or("", |m| m.as_str()).to_string();
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let param = |key: &str| params.get(key).map(|s| s.as_str()).unwrap_or("");
 
-    // Default values
-    let mut net = "tcp".to_string();
-    let mut path = "/".to_string();
-    let mut host_header = host.clone();
-    let mut tls_str = if tls {
-        "tls".to_string()
-    } else {
-        String::new()
-    };
-    let mut sni = String::new();
+    // `type` is left alone here as it carries the header type for tcp/kcp
+    if !param("network").is_empty() {
+        net = param("network").to_string();
+    }
+    match param("tls") {
+        "1" | "true" | "tls" => tls = "tls".to_string(),
+        "0" | "false" => tls.clear(),
+        _ => {}
+    }
+    if param("security") == "tls" {
+        tls = "tls".to_string();
+    }
+    if let Ok(alter_id) = param("alterId").parse::<u16>() {
+        aid = alter_id;
+    }
 
-    // Parse query parameters
-    if !query.is_empty() && query.starts_with("/?") {
-        for param in query[2..].split('&') {
-            let mut kv = param.split('=');
-            if let (Some(k), Some(v)) = (kv.next(), kv.next()) {
-                match k {
-                    "network" => net = v.to_string(),
-                    "host" => host_header = v.to_string(),
-                    "path" => path = v.to_string(),
-                    "tls" => tls_str = v.to_string(),
-                    "sni" => sni = v.to_string(),
-                    _ => {}
-                }
+    let mut fake_type = param("headerType").to_string();
+    let mut host_header = param("host").to_string();
+    let mut path = param("path").to_string();
+    match net.as_str() {
+        "tcp" | "kcp" if fake_type.is_empty() && !matches!(param("type"), "" | "tcp" | "kcp") => {
+            fake_type = param("type").to_string();
+        }
+        "quic" => {
+            host_header = param("quicSecurity").to_string();
+            if host_header.is_empty() && param("security") != "tls" {
+                host_header = param("security").to_string();
             }
+            path = param("key").to_string();
+        }
+        "grpc" if !param("serviceName").is_empty() => {
+            path = param("serviceName").to_string();
         }
+        _ => {}
     }
 
-    // Create formatted remark if empty
-    let formatted_remark = if remark.is_empty() {
+    let cipher = match param("encryption") {
+        "" => "auto",
+        encryption => encryption,
+    };
+    let sni = match param("sni") {
+        "" => param("peer"),
+        sni => sni,
+    };
+
+    let remark = url_decode(url.fragment().unwrap_or(""));
+    let remark = if remark.is_empty() {
         format!("{} ({})", host, port)
     } else {
         remark
     };
 
-    // Create the proxy object
     *node = Proxy::vmess_construct(
-        "VMess",
-        &formatted_remark,
-        &host,
+        V2RAY_DEFAULT_GROUP,
+        &remark,
+        host,
         port,
-        "",
+        &fake_type,
         &id,
         aid,
         &net,
-        "auto",
+        cipher,
         &path,
         &host_header,
         "",
-        &tls_str,
-        &sni,
+        &tls,
+        sni,
         None,
         None,
         None,
@@ -875,3 +892,125 @@ pub fn explode_std_vmess_new(vmess_str: &str, node: &mut Proxy) -> bool {
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "b831381d-6324-4d53-ad4f-8cda48b30811";
+
+    fn parse(link: &str) -> Proxy {
+        let mut node = Proxy::default();
+        assert!(
+            explode_std_vmess(link, &mut node),
+            "failed to parse {}",
+            link
+        );
+        node
+    }
+
+    #[test]
+    fn test_explode_std_vmess_tcp() {
+        let node = parse(&format!(
+            "vmess+tcp://{}-2@example.com:443?type=http&host=cdn.example.com&path=/a#TCP%20Node",
+            UUID
+        ));
+        assert_eq!(node.remark, "TCP Node");
+        assert_eq!(node.hostname, "example.com");
+        assert_eq!(node.port, 443);
+        assert_eq!(node.user_id.as_deref(), Some(UUID));
+        assert_eq!(node.alter_id, 2);
+        assert_eq!(node.transfer_protocol.as_deref(), Some("tcp"));
+        assert_eq!(node.fake_type.as_deref(), Some("http"));
+        assert_eq!(node.host.as_deref(), Some("cdn.example.com"));
+        assert_eq!(node.path.as_deref(), Some("/a"));
+        assert!(!node.tls_secure);
+    }
+
+    #[test]
+    fn test_explode_std_vmess_ws_tls() {
+        let node = parse(&format!(
+            "vmess+ws+tls://{}@example.com:443/?host=ws.example.com&path=%2Fws&sni=sni.example.com",
+            UUID
+        ));
+        assert_eq!(node.remark, "example.com (443)");
+        assert_eq!(node.transfer_protocol.as_deref(), Some("ws"));
+        assert_eq!(node.host.as_deref(), Some("ws.example.com"));
+        assert_eq!(node.path.as_deref(), Some("/ws"));
+        assert_eq!(node.server_name.as_deref(), Some("sni.example.com"));
+        assert!(node.tls_secure);
+    }
+
+    #[test]
+    fn test_explode_std_vmess_http_and_h2() {
+        let node = parse(&format!(
+            "vmess+http://{}@example.com:80?host=a.example.com&path=/h",
+            UUID
+        ));
+        assert_eq!(node.transfer_protocol.as_deref(), Some("http"));
+        assert_eq!(node.host.as_deref(), Some("a.example.com"));
+        assert_eq!(node.path.as_deref(), Some("/h"));
+        assert!(!node.tls_secure);
+
+        let node = parse(&format!(
+            "vmess+h2+tls://{}@example.com:443?host=b.example.com&path=/h2",
+            UUID
+        ));
+        assert_eq!(node.transfer_protocol.as_deref(), Some("h2"));
+        assert_eq!(node.host.as_deref(), Some("b.example.com"));
+        assert_eq!(node.path.as_deref(), Some("/h2"));
+        assert!(node.tls_secure);
+    }
+
+    #[test]
+    fn test_explode_std_vmess_grpc() {
+        let node = parse(&format!(
+            "vmess+grpc+tls://{}@example.com:443?serviceName=svc&peer=grpc.example.com",
+            UUID
+        ));
+        assert_eq!(node.transfer_protocol.as_deref(), Some("grpc"));
+        assert_eq!(node.path.as_deref(), Some("svc"));
+        assert_eq!(node.server_name.as_deref(), Some("grpc.example.com"));
+        assert!(node.tls_secure);
+    }
+
+    #[test]
+    fn test_explode_std_vmess_kcp() {
+        let node = parse(&format!(
+            "vmess+kcp://{}@1.2.3.4:10086?type=wechat-video&encryption=aes-128-gcm",
+            UUID
+        ));
+        assert_eq!(node.transfer_protocol.as_deref(), Some("kcp"));
+        assert_eq!(node.fake_type.as_deref(), Some("wechat-video"));
+        assert_eq!(node.encrypt_method.as_deref(), Some("aes-128-gcm"));
+        assert!(!node.tls_secure);
+    }
+
+    #[test]
+    fn test_explode_std_vmess_quic() {
+        let node = parse(&format!(
+            "vmess+quic://{}@example.com:443?security=aes-128-gcm&key=secret&headerType=srtp",
+            UUID
+        ));
+        assert_eq!(node.transfer_protocol.as_deref(), Some("quic"));
+        assert_eq!(node.quic_secure.as_deref(), Some("aes-128-gcm"));
+        assert_eq!(node.quic_secret.as_deref(), Some("secret"));
+        assert_eq!(node.fake_type.as_deref(), Some("srtp"));
+    }
+
+    #[test]
+    fn test_explode_std_vmess_rejects_unknown_scheme_part() {
+        let mut node = Proxy::default();
+        let link = format!("vmess+foo://{}@example.com:443", UUID);
+        assert!(!explode_std_vmess(&link, &mut node));
+    }
+
+    #[test]
+    fn test_explode_dispatches_vmess_plus() {
+        let mut node = Proxy::default();
+        let link = format!("vmess+ws+tls://{}@example.com:443?path=/ws", UUID);
+        assert!(super::super::explode(&link, &mut node));
+        assert_eq!(node.group, V2RAY_DEFAULT_GROUP);
+        assert_eq!(node.transfer_protocol.as_deref(), Some("ws"));
+    }
+}
@@ -0,0 +1,577 @@
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::vless::VlessProxy;
+use crate::models::{
+    Proxy, ProxyType, SS_DEFAULT_GROUP, TROJAN_DEFAULT_GROUP, V2RAY_DEFAULT_GROUP,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Transport and security options read from an Xray `streamSettings` object
+#[derive(Debug, Default)]
+struct XrayStream {
+    network: String,
+    security: String,
+    header_type: String,
+    host: String,
+    path: String,
+    service_name: String,
+    xhttp_mode: String,
+    xhttp_extra: String,
+    server_name: String,
+    allow_insecure: Option<bool>,
+    alpn: Vec<String>,
+    fingerprint: String,
+    reality_public_key: String,
+    reality_short_id: String,
+}
+
+impl XrayStream {
+    fn is_tls(&self) -> bool {
+        matches!(self.security.as_str(), "tls" | "xtls" | "reality")
+    }
+}
+
+/// Get a string field from a JSON object
+fn get_str<'a>(value: &'a Value, key: &str) -> &'a str {
+    value[key].as_str().unwrap_or("")
+}
+
+/// Read a field that may be either a single string or an array of strings,
+/// returning the first non-empty entry
+fn get_first_str(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|v| v.as_str())
+            .find(|s| !s.is_empty())
+            .unwrap_or("")
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Parse the `streamSettings` object of an Xray outbound
+fn parse_stream_settings(stream: &Value) -> XrayStream {
+    let mut result = XrayStream {
+        network: match get_str(stream, "network").to_lowercase().as_str() {
+            "" | "raw" => "tcp".to_string(),
+            "http" => "h2".to_string(),
+            "splithttp" => "xhttp".to_string(),
+            network => network.to_string(),
+        },
+        security: get_str(stream, "security").to_lowercase(),
+        ..Default::default()
+    };
+
+    match result.network.as_str() {
+        "tcp" => {
+            let settings = if stream["rawSettings"].is_object() {
+                &stream["rawSettings"]
+            } else {
+                &stream["tcpSettings"]
+            };
+            let header = &settings["header"];
+            if get_str(header, "type") == "http" {
+                result.header_type = "http".to_string();
+                result.path = get_first_str(&header["request"]["path"]);
+                result.host = get_first_str(&header["request"]["headers"]["Host"]);
+            }
+        }
+        "ws" => {
+            let settings = &stream["wsSettings"];
+            result.path = get_str(settings, "path").to_string();
+            result.host = get_str(settings, "host").to_string();
+            if result.host.is_empty() {
+                result.host = get_first_str(&settings["headers"]["Host"]);
+            }
+        }
+        "httpupgrade" => {
+            let settings = &stream["httpupgradeSettings"];
+            result.path = get_str(settings, "path").to_string();
+            result.host = get_str(settings, "host").to_string();
+        }
+        "xhttp" => {
+            let settings = if stream["xhttpSettings"].is_object() {
+                &stream["xhttpSettings"]
+            } else {
+                &stream["splithttpSettings"]
+            };
+            result.path = get_str(settings, "path").to_string();
+            result.host = get_str(settings, "host").to_string();
+            result.xhttp_mode = get_str(settings, "mode").to_string();
+            if settings["extra"].is_object() {
+                result.xhttp_extra = settings["extra"].to_string();
+            }
+        }
+        "h2" => {
+            let settings = &stream["httpSettings"];
+            result.path = get_str(settings, "path").to_string();
+            result.host = get_first_str(&settings["host"]);
+        }
+        "grpc" => {
+            result.service_name = get_str(&stream["grpcSettings"], "serviceName").to_string();
+        }
+        _ => {}
+    }
+
+    let tls_settings = match result.security.as_str() {
+        "tls" => &stream["tlsSettings"],
+        "xtls" => &stream["xtlsSettings"],
+        "reality" => &stream["realitySettings"],
+        _ => &Value::Null,
+    };
+    if tls_settings.is_object() {
+        result.server_name = get_str(tls_settings, "serverName").to_string();
+        result.allow_insecure = tls_settings["allowInsecure"].as_bool();
+        result.fingerprint = get_str(tls_settings, "fingerprint").to_string();
+        if let Some(alpn) = tls_settings["alpn"].as_array() {
+            result.alpn = alpn
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_string())
+                .collect();
+        }
+        if result.security == "reality" {
+            result.reality_public_key = get_str(tls_settings, "publicKey").to_string();
+            result.reality_short_id = get_str(tls_settings, "shortId").to_string();
+        }
+    }
+
+    result
+}
+
+/// Collect the servers of an outbound as (address, port, user) triples.
+///
+/// Handles both the `vnext`/`servers` layout and the flattened settings
+/// accepted by recent Xray versions.
+fn collect_servers(settings: &Value) -> Vec<(String, u16, &Value)> {
+    let mut servers = Vec::new();
+
+    let list = settings["vnext"]
+        .as_array()
+        .or_else(|| settings["servers"].as_array());
+    match list {
+        Some(list) => {
+            for server in list {
+                let address = get_str(server, "address").to_string();
+                let port = server["port"].as_u64().unwrap_or(0) as u16;
+                match server["users"].as_array() {
+                    Some(users) => {
+                        for user in users {
+                            servers.push((address.clone(), port, user));
+                        }
+                    }
+                    // Trojan and Shadowsocks keep the credentials on the server
+                    None => servers.push((address, port, server)),
+                }
+            }
+        }
+        None => {
+            let address = get_str(settings, "address").to_string();
+            let port = settings["port"].as_u64().unwrap_or(0) as u16;
+            servers.push((address, port, settings));
+        }
+    }
+
+    servers
+        .into_iter()
+        .filter(|(address, port, _)| !address.is_empty() && *port != 0)
+        .collect()
+}
+
+/// Build a VLESS node from an Xray user entry and its stream settings
+fn build_vless(remark: &str, address: &str, port: u16, user: &Value, stream: &XrayStream) -> Proxy {
+    let mut vless = VlessProxy {
+        uuid: get_str(user, "id").to_string(),
+        tls: stream.is_tls(),
+        alpn: stream.alpn.iter().cloned().collect(),
+        network: Some(stream.network.clone()),
+        skip_cert_verify: stream.allow_insecure,
+        ..Default::default()
+    };
+
+    let flow = get_str(user, "flow");
+    if !flow.is_empty() {
+        vless.flow = Some(flow.to_string());
+    }
+    if !stream.server_name.is_empty() {
+        vless.servername = Some(stream.server_name.clone());
+    }
+    if !stream.fingerprint.is_empty() {
+        vless.client_fingerprint = Some(stream.fingerprint.clone());
+    }
+    if !stream.reality_public_key.is_empty() {
+        vless.reality_public_key = Some(stream.reality_public_key.clone());
+        vless.reality_short_id = Some(stream.reality_short_id.clone());
+    }
+
+    match stream.network.as_str() {
+        "tcp" if stream.header_type == "http" => {
+            let mut headers = HashMap::new();
+            if !stream.host.is_empty() {
+                headers.insert("Host".to_string(), vec![stream.host.clone()]);
+            }
+            vless.http_path = Some(if stream.path.is_empty() {
+                "/".to_string()
+            } else {
+                stream.path.clone()
+            });
+            vless.http_headers = Some(headers);
+        }
+        "ws" | "httpupgrade" => {
+            let mut headers = HashMap::new();
+            if !stream.host.is_empty() {
+                headers.insert("Host".to_string(), stream.host.clone());
+            }
+            vless.ws_path = Some(stream.path.clone());
+            vless.ws_headers = Some(headers);
+        }
+        "xhttp" => {
+            vless.xhttp_path = Some(stream.path.clone());
+            if !stream.host.is_empty() {
+                vless.xhttp_host = Some(stream.host.clone());
+            }
+            if !stream.xhttp_mode.is_empty() {
+                vless.xhttp_mode = Some(stream.xhttp_mode.clone());
+            }
+            if !stream.xhttp_extra.is_empty() {
+                vless.xhttp_extra = Some(stream.xhttp_extra.clone());
+            }
+        }
+        "h2" => {
+            vless.h2_path = Some(stream.path.clone());
+            vless.h2_host = if stream.host.is_empty() {
+                None
+            } else {
+                Some(vec![stream.host.clone()])
+            };
+        }
+        "grpc" => {
+            vless.grpc_service_name = Some(stream.service_name.clone());
+        }
+        _ => {}
+    }
+
+    Proxy {
+        proxy_type: ProxyType::Vless,
+        group: V2RAY_DEFAULT_GROUP.to_string(),
+        remark: remark.to_string(),
+        hostname: address.to_string(),
+        port,
+        allow_insecure: stream.allow_insecure,
+        sni: vless.servername.clone(),
+        combined_proxy: Some(CombinedProxy::Vless(vless)),
+        ..Default::default()
+    }
+}
+
+/// Parse a single Xray outbound into Proxy objects
+fn parse_xray_outbound(outbound: &Value, nodes: &mut Vec<Proxy>) {
+    let protocol = get_str(outbound, "protocol");
+    if !matches!(protocol, "vmess" | "vless" | "trojan" | "shadowsocks") {
+        return;
+    }
+
+    let tag = get_str(outbound, "tag");
+    let stream = parse_stream_settings(&outbound["streamSettings"]);
+
+    for (address, port, user) in collect_servers(&outbound["settings"]) {
+        // Xray tags are usually generic ("proxy"), so only keep meaningful ones
+        let remark = if tag.is_empty() || tag == "proxy" {
+            format!("{} ({})", address, port)
+        } else {
+            tag.to_string()
+        };
+
+        let node = match protocol {
+            "vmess" => {
+                let path = if stream.network == "grpc" {
+                    &stream.service_name
+                } else {
+                    &stream.path
+                };
+                let mut node = Proxy::vmess_construct(
+                    V2RAY_DEFAULT_GROUP,
+                    &remark,
+                    &address,
+                    port,
+                    &stream.header_type,
+                    get_str(user, "id"),
+                    user["alterId"].as_u64().unwrap_or(0) as u16,
+                    &stream.network,
+                    get_str(user, "security"),
+                    path,
+                    &stream.host,
+                    "",
+                    if stream.is_tls() { "tls" } else { "" },
+                    &stream.server_name,
+                    None,
+                    None,
+                    stream.allow_insecure,
+                    None,
+                    "",
+                );
+                node.alpn = stream.alpn.iter().cloned().collect();
+                node
+            }
+            "vless" => build_vless(&remark, &address, port, user, &stream),
+            "trojan" => {
+                let (network, path) = match stream.network.as_str() {
+                    "tcp" => (None, None),
+                    "grpc" => (Some("grpc".to_string()), Some(stream.service_name.clone())),
+                    network => (Some(network.to_string()), Some(stream.path.clone())),
+                };
+                let server_name = if stream.server_name.is_empty() {
+                    None
+                } else {
+                    Some(stream.server_name.clone())
+                };
+                let host = if stream.host.is_empty() {
+                    None
+                } else {
+                    Some(stream.host.clone())
+                };
+                Proxy::trojan_construct(
+                    TROJAN_DEFAULT_GROUP.to_string(),
+                    remark.clone(),
+                    address.clone(),
+                    port,
+                    get_str(user, "password").to_string(),
+                    network,
+                    host,
+                    path,
+                    server_name,
+                    stream.is_tls(),
+                    None,
+                    None,
+                    stream.allow_insecure,
+                    None,
+                    None,
+                )
+            }
+            _ => Proxy::ss_construct(
+                SS_DEFAULT_GROUP,
+                &remark,
+                &address,
+                port,
+                get_str(user, "password"),
+                get_str(user, "method"),
+                "",
+                "",
+                user["uot"].as_bool(),
+                None,
+                None,
+                None,
+                "",
+            ),
+        };
+
+        nodes.push(node);
+    }
+}
+
+/// Parse a complete Xray/V2Ray client `config.json` into a vector of Proxy objects
+///
+/// Every VMess, VLESS, Trojan and Shadowsocks outbound becomes a node; other
+/// outbounds such as freedom, blackhole and dns are ignored.
+pub fn explode_xray_conf(content: &str, nodes: &mut Vec<Proxy>) -> bool {
+    let json: Value = match serde_json::from_str(content) {
+        Ok(json) => json,
+        Err(_) => return false,
+    };
+
+    let outbounds = match json["outbounds"].as_array() {
+        Some(outbounds) => outbounds,
+        None => return false,
+    };
+
+    let orig_size = nodes.len();
+    for outbound in outbounds {
+        parse_xray_outbound(outbound, nodes);
+    }
+
+    nodes.len() > orig_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XRAY_CONFIG: &str = r#"{
+        "log": { "loglevel": "warning" },
+        "inbounds": [{ "port": 10808, "protocol": "socks" }],
+        "outbounds": [
+            {
+                "tag": "vless-reality",
+                "protocol": "vless",
+                "settings": {
+                    "vnext": [{
+                        "address": "reality.example.com",
+                        "port": 443,
+                        "users": [{ "id": "b831381d-6324-4d53-ad4f-8cda48b30811", "flow": "xtls-rprx-vision", "encryption": "none" }]
+                    }]
+                },
+                "streamSettings": {
+                    "network": "tcp",
+                    "security": "reality",
+                    "realitySettings": {
+                        "serverName": "www.microsoft.com",
+                        "fingerprint": "chrome",
+                        "publicKey": "pubkey",
+                        "shortId": "6ba85179e30d4fc2"
+                    }
+                }
+            },
+            {
+                "tag": "vless-xhttp",
+                "protocol": "vless",
+                "settings": {
+                    "vnext": [{
+                        "address": "xhttp.example.com",
+                        "port": 443,
+                        "users": [{ "id": "b831381d-6324-4d53-ad4f-8cda48b30811", "encryption": "none" }]
+                    }]
+                },
+                "streamSettings": {
+                    "network": "xhttp",
+                    "security": "tls",
+                    "tlsSettings": { "serverName": "xhttp.example.com", "alpn": ["h2"] },
+                    "xhttpSettings": { "path": "/xhttp", "host": "cdn.example.com", "mode": "auto" }
+                }
+            },
+            {
+                "tag": "vmess-ws",
+                "protocol": "vmess",
+                "settings": {
+                    "vnext": [{
+                        "address": "vmess.example.com",
+                        "port": 443,
+                        "users": [{ "id": "b831381d-6324-4d53-ad4f-8cda48b30811", "alterId": 0, "security": "auto" }]
+                    }]
+                },
+                "streamSettings": {
+                    "network": "ws",
+                    "security": "tls",
+                    "tlsSettings": { "serverName": "vmess.example.com", "allowInsecure": true },
+                    "wsSettings": { "path": "/ray", "headers": { "Host": "vmess.example.com" } }
+                }
+            },
+            {
+                "tag": "trojan-grpc",
+                "protocol": "trojan",
+                "settings": { "servers": [{ "address": "trojan.example.com", "port": 443, "password": "secret" }] },
+                "streamSettings": {
+                    "network": "grpc",
+                    "security": "tls",
+                    "grpcSettings": { "serviceName": "trojan-grpc" }
+                }
+            },
+            {
+                "tag": "ss",
+                "protocol": "shadowsocks",
+                "settings": { "servers": [{ "address": "1.2.3.4", "port": 8388, "method": "aes-256-gcm", "password": "sspass" }] }
+            },
+            {
+                "tag": "vless-httpupgrade",
+                "protocol": "vless",
+                "settings": { "address": "hu.example.com", "port": 80, "id": "b831381d-6324-4d53-ad4f-8cda48b30811" },
+                "streamSettings": {
+                    "network": "httpupgrade",
+                    "httpupgradeSettings": { "path": "/upgrade", "host": "hu.example.com" }
+                }
+            },
+            { "tag": "direct", "protocol": "freedom" },
+            { "tag": "block", "protocol": "blackhole" }
+        ]
+    }"#;
+
+    fn parse_all() -> Vec<Proxy> {
+        let mut nodes = Vec::new();
+        assert!(explode_xray_conf(XRAY_CONFIG, &mut nodes));
+        nodes
+    }
+
+    fn vless_of(node: &Proxy) -> &VlessProxy {
+        match node.combined_proxy {
+            Some(CombinedProxy::Vless(ref vless)) => vless,
+            _ => panic!("expected a VLESS combined proxy"),
+        }
+    }
+
+    #[test]
+    fn test_explode_xray_conf_protocols() {
+        let nodes = parse_all();
+        assert_eq!(nodes.len(), 6);
+        assert_eq!(nodes[0].proxy_type, ProxyType::Vless);
+        assert_eq!(nodes[2].proxy_type, ProxyType::VMess);
+        assert_eq!(nodes[3].proxy_type, ProxyType::Trojan);
+        assert_eq!(nodes[4].proxy_type, ProxyType::Shadowsocks);
+    }
+
+    #[test]
+    fn test_explode_xray_conf_vless_reality() {
+        let nodes = parse_all();
+        let vless = vless_of(&nodes[0]);
+        assert_eq!(nodes[0].remark, "vless-reality");
+        assert!(vless.tls);
+        assert_eq!(vless.flow.as_deref(), Some("xtls-rprx-vision"));
+        assert_eq!(vless.reality_public_key.as_deref(), Some("pubkey"));
+        assert_eq!(vless.reality_short_id.as_deref(), Some("6ba85179e30d4fc2"));
+        assert_eq!(vless.servername.as_deref(), Some("www.microsoft.com"));
+        assert_eq!(vless.client_fingerprint.as_deref(), Some("chrome"));
+    }
+
+    #[test]
+    fn test_explode_xray_conf_transports() {
+        let nodes = parse_all();
+
+        let xhttp = vless_of(&nodes[1]);
+        assert_eq!(xhttp.network.as_deref(), Some("xhttp"));
+        assert_eq!(xhttp.xhttp_path.as_deref(), Some("/xhttp"));
+        assert_eq!(xhttp.xhttp_host.as_deref(), Some("cdn.example.com"));
+        assert_eq!(xhttp.xhttp_mode.as_deref(), Some("auto"));
+        assert!(xhttp.alpn.contains("h2"));
+
+        let vmess = &nodes[2];
+        assert_eq!(vmess.transfer_protocol.as_deref(), Some("ws"));
+        assert_eq!(vmess.path.as_deref(), Some("/ray"));
+        assert_eq!(vmess.host.as_deref(), Some("vmess.example.com"));
+        assert_eq!(vmess.allow_insecure, Some(true));
+        assert!(vmess.tls_secure);
+
+        let trojan = &nodes[3];
+        assert_eq!(trojan.transfer_protocol.as_deref(), Some("grpc"));
+        assert_eq!(trojan.path.as_deref(), Some("trojan-grpc"));
+        assert!(trojan.tls_secure);
+
+        let upgrade = vless_of(&nodes[5]);
+        assert_eq!(nodes[5].hostname, "hu.example.com");
+        assert_eq!(upgrade.network.as_deref(), Some("httpupgrade"));
+        assert_eq!(upgrade.ws_path.as_deref(), Some("/upgrade"));
+        assert!(!upgrade.tls);
+    }
+
+    #[test]
+    fn test_explode_xray_conf_trojan_without_security() {
+        let config = r#"{
+            "outbounds": [{
+                "tag": "trojan-plain",
+                "protocol": "trojan",
+                "settings": { "servers": [{ "address": "trojan.example.com", "port": 80, "password": "secret" }] },
+                "streamSettings": { "network": "ws", "wsSettings": { "path": "/trojan" } }
+            }]
+        }"#;
+        let mut nodes = Vec::new();
+        assert!(explode_xray_conf(config, &mut nodes));
+        assert_eq!(nodes[0].proxy_type, ProxyType::Trojan);
+        assert!(!nodes[0].tls_secure);
+    }
+
+    #[test]
+    fn test_explode_conf_content_detects_xray() {
+        let mut nodes = Vec::new();
+        let count = crate::parser::explodes::explode_conf_content(XRAY_CONFIG, &mut nodes);
+        assert_eq!(count, 6);
+    }
+}
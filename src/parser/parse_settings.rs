@@ -33,6 +33,10 @@ pub struct ParseSettings {
     /// HTTP request headers
     pub request_header: Option<HashMap<CaseInsensitiveString, String>>,
 
+    /// Whether remote subscriptions referenced by Loon and Quantumult X
+    /// configurations are downloaded
+    pub follow_remote_proxies: bool,
+
     /// JavaScript runtime - optional depending on feature flags
     #[cfg(feature = "js_runtime")]
     pub js_runtime: Option<()>, // Placeholder for actual JS runtime type
@@ -64,6 +68,7 @@ impl Default for ParseSettings {
             sub_info: None,
             authorized: !settings.api_access_token.is_empty(),
             request_header: None,
+            follow_remote_proxies: settings.follow_remote_proxies,
             #[cfg(feature = "js_runtime")]
             js_runtime: None,
             #[cfg(feature = "js_runtime")]
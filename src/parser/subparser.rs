@@ -2,12 +2,17 @@ use crate::models::Proxy;
 use crate::parser::explodes::*;
 use crate::parser::infoparser::{get_sub_info_from_nodes, get_sub_info_from_ssd};
 use crate::parser::parse_settings::ParseSettings;
+use crate::parser::yaml::clash::ClashYamlInput;
 use crate::utils::http::get_sub_info_from_header;
 use crate::utils::matcher::{apply_matcher, reg_find};
 use crate::utils::network::is_link;
 use crate::utils::url::url_decode;
 use crate::utils::{file_exists, file_get_async, web_get_async};
 use log::warn;
+use std::collections::{HashSet, VecDeque};
+
+/// Maximum nesting level when following Clash `proxy-providers`
+const MAX_PROVIDER_DEPTH: u32 = 3;
 
 /// Equivalent to ConfType enum in C++
 #[derive(Debug, PartialEq, Eq)]
@@ -118,7 +123,9 @@ pub async fn add_nodes(
 
             if !sub_content.is_empty() {
                 // Parse the subscription content
-                let result = explode_conf_content(&sub_content, &mut nodes);
+                let mut result = explode_conf_content(&sub_content, &mut nodes);
+                result += explode_clash_providers(&sub_content, &mut nodes, parse_settings).await;
+                result += explode_remote_proxies(&sub_content, &mut nodes, parse_settings).await;
                 if result > 0 {
                     // Get subscription info
                     if sub_content.starts_with("ssd://") {
@@ -174,7 +181,7 @@ pub async fn add_nodes(
             }
 
             // Read and parse local file
-            let result = explode_conf(&link, &mut nodes).await;
+            let result = explode_conf(&link, &mut nodes, parse_settings).await;
             if result > 0 {
                 // The rest is similar to SUB case
                 // Get subscription info
@@ -249,14 +256,167 @@ fn get_url_arg(url: &str, arg_name: &str) -> Option<String> {
 
 /// Parses a configuration file into a vector of Proxy objects
 /// Returns the number of proxies parsed
-async fn explode_conf(path: &str, nodes: &mut Vec<Proxy>) -> i32 {
+async fn explode_conf(path: &str, nodes: &mut Vec<Proxy>, parse_settings: &ParseSettings) -> i32 {
     // TODO: 安全问题，但是旧版subconverter也有……
     match file_get_async(path, None).await {
-        Ok(content) => explode_conf_content(&content, nodes),
+        Ok(content) => {
+            explode_conf_content(&content, nodes)
+                + explode_clash_providers(&content, nodes, parse_settings).await
+                + explode_remote_proxies(&content, nodes, parse_settings).await
+        }
         Err(_) => 0,
     }
 }
 
+/// Follows the `proxy-providers` declared in a Clash configuration
+///
+/// Every provider is downloaded (or read from disk when authorized) and parsed
+/// as a nested subscription, with the provider name used as the node group.
+/// Providers declared by provider contents are followed up to
+/// `MAX_PROVIDER_DEPTH` levels deep, and every URL or path is only read once
+/// so providers including themselves or each other add their nodes once.
+/// Returns the number of proxies added
+async fn explode_clash_providers(
+    content: &str,
+    nodes: &mut Vec<Proxy>,
+    parse_settings: &ParseSettings,
+) -> i32 {
+    let orig_size = nodes.len();
+    let mut pending = VecDeque::from([(content.to_string(), 0)]);
+    let mut visited = HashSet::new();
+
+    while let Some((content, depth)) = pending.pop_front() {
+        if !content.contains("proxy-providers") {
+            continue;
+        }
+        let clash_input: ClashYamlInput = match serde_yaml::from_str(&content) {
+            Ok(input) => input,
+            Err(_) => continue,
+        };
+
+        for (name, provider) in clash_input.extract_proxy_providers() {
+            if depth >= MAX_PROVIDER_DEPTH {
+                warn!(
+                    "Proxy provider '{}' exceeds the maximum depth of {}, skipping",
+                    name, MAX_PROVIDER_DEPTH
+                );
+                continue;
+            }
+
+            let source = match provider.provider_type.as_str() {
+                "file" => &provider.path,
+                _ => &provider.url,
+            };
+            if !visited.insert(format!("{}:{}", provider.provider_type, source)) {
+                warn!("Proxy provider '{}' was already included, skipping", name);
+                continue;
+            }
+
+            let provider_content = match provider.provider_type.as_str() {
+                "http" if is_link(&provider.url) => {
+                    match web_get_async(
+                        &provider.url,
+                        &parse_settings.proxy,
+                        parse_settings.request_header.as_ref(),
+                    )
+                    .await
+                    {
+                        Ok(response) => response.body,
+                        Err(e) => {
+                            warn!("Failed to get proxy provider '{}': {}", name, e);
+                            continue;
+                        }
+                    }
+                }
+                "file" if parse_settings.authorized => {
+                    match file_get_async(&provider.path, None).await {
+                        Ok(content) => content,
+                        Err(e) => {
+                            warn!("Failed to read proxy provider '{}': {}", name, e);
+                            continue;
+                        }
+                    }
+                }
+                _ => {
+                    warn!(
+                        "Proxy provider '{}' of type '{}' is not supported, skipping",
+                        name, provider.provider_type
+                    );
+                    continue;
+                }
+            };
+
+            let mut provider_nodes = Vec::new();
+            explode_conf_content(&provider_content, &mut provider_nodes);
+            for node in &mut provider_nodes {
+                node.group = name.clone();
+            }
+            nodes.append(&mut provider_nodes);
+            pending.push_back((provider_content, depth + 1));
+        }
+    }
+
+    (nodes.len() - orig_size) as i32
+}
+
+/// Follows the remote subscriptions declared in a Loon `[Remote Proxy]` or
+/// Quantumult X `[server_remote]` section
+///
+/// Every remote subscription is downloaded and parsed with the subscription
+/// name used as the node group. Remote contents are not followed any further.
+/// Only http(s) subscriptions are followed, and only when
+/// `follow_remote_proxies` is enabled, as the URLs come from the converted
+/// configuration rather than from the request.
+/// Returns the number of proxies added
+async fn explode_remote_proxies(
+    content: &str,
+    nodes: &mut Vec<Proxy>,
+    parse_settings: &ParseSettings,
+) -> i32 {
+    if !parse_settings.follow_remote_proxies {
+        return 0;
+    }
+
+    let mut remotes = Vec::new();
+    if content.contains("[Remote Proxy]") {
+        remotes.extend(extract_loon_remote_proxies(content));
+    }
+    if content.contains("[server_remote]") {
+        remotes.extend(extract_quanx_remote_servers(content));
+    }
+    let orig_size = nodes.len();
+
+    for (name, url) in remotes {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            warn!("Remote proxy '{}' is not an http(s) link, skipping", name);
+            continue;
+        }
+
+        let remote_content = match web_get_async(
+            &url,
+            &parse_settings.proxy,
+            parse_settings.request_header.as_ref(),
+        )
+        .await
+        {
+            Ok(response) => response.body,
+            Err(e) => {
+                warn!("Failed to get remote proxy '{}': {}", name, e);
+                continue;
+            }
+        };
+
+        let mut remote_nodes = Vec::new();
+        explode_conf_content(&remote_content, &mut remote_nodes);
+        for node in &mut remote_nodes {
+            node.group = name.clone();
+        }
+        nodes.append(&mut remote_nodes);
+    }
+
+    (nodes.len() - orig_size) as i32
+}
+
 /// Filters nodes based on include/exclude rules
 fn filter_nodes(
     nodes: &mut Vec<Proxy>,
@@ -335,3 +495,203 @@ fn should_ignore(
     // A node is ignored if it's excluded OR not included
     excluded || !included
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const PROVIDER_CONTENT: &str = r#"
+proxies:
+  - name: "Provider SS"
+    type: ss
+    server: 1.2.3.4
+    port: 8388
+    cipher: aes-256-gcm
+    password: secret
+"#;
+
+    fn run<F: std::future::Future>(future: F) -> F::Output {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        // The HTTP client spawns local tasks
+        tokio::task::LocalSet::new().block_on(&rt, future)
+    }
+
+    fn write_temp(content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    /// Serves `body` over HTTP on a local port, counting the requests made
+    fn serve(body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/sub", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0; 4096];
+                let _ = stream.read(&mut request);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+        (url, hits)
+    }
+
+    fn provider_config(name: &str, path: &str) -> String {
+        format!(
+            "proxy-providers:\n  {}:\n    type: file\n    path: \"{}\"\n",
+            name, path
+        )
+    }
+
+    #[test]
+    fn test_clash_file_provider_sets_group() {
+        let provider = write_temp(PROVIDER_CONTENT);
+        let config = provider_config("MyProvider", provider.path().to_str().unwrap());
+
+        let mut settings = ParseSettings::default();
+        settings.authorized = true;
+        let mut nodes = Vec::new();
+        let count = run(explode_clash_providers(&config, &mut nodes, &settings));
+
+        assert_eq!(count, 1);
+        assert_eq!(nodes[0].remark, "Provider SS");
+        assert_eq!(nodes[0].group, "MyProvider");
+    }
+
+    #[test]
+    fn test_clash_file_provider_requires_authorization() {
+        let provider = write_temp(PROVIDER_CONTENT);
+        let config = provider_config("MyProvider", provider.path().to_str().unwrap());
+
+        let mut settings = ParseSettings::default();
+        settings.authorized = false;
+        let mut nodes = Vec::new();
+        let count = run(explode_clash_providers(&config, &mut nodes, &settings));
+
+        assert_eq!(count, 0);
+        assert!(nodes.is_empty());
+    }
+
+    #[test]
+    fn test_clash_provider_depth_limit() {
+        // A provider that points back to itself must not loop forever
+        let provider = tempfile::NamedTempFile::new().unwrap();
+        let path = provider.path().to_str().unwrap().to_string();
+        let content = format!("{}{}", PROVIDER_CONTENT, provider_config("Loop", &path));
+        std::fs::write(&path, &content).unwrap();
+
+        let mut settings = ParseSettings::default();
+        settings.authorized = true;
+        let mut nodes = Vec::new();
+        let count = run(explode_clash_providers(&content, &mut nodes, &settings));
+
+        assert_eq!(count, 1);
+        assert_eq!(nodes[0].group, "Loop");
+    }
+
+    #[test]
+    fn test_clash_provider_siblings_read_once() {
+        // Two providers including each other add their nodes once each
+        let first = tempfile::NamedTempFile::new().unwrap();
+        let second = tempfile::NamedTempFile::new().unwrap();
+        let first_path = first.path().to_str().unwrap().to_string();
+        let second_path = second.path().to_str().unwrap().to_string();
+        std::fs::write(
+            &first_path,
+            format!(
+                "{}{}",
+                PROVIDER_CONTENT,
+                provider_config("Second", &second_path)
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            &second_path,
+            format!(
+                "{}{}",
+                PROVIDER_CONTENT.replace("Provider SS", "Sibling SS"),
+                provider_config("First", &first_path)
+            ),
+        )
+        .unwrap();
+
+        let mut settings = ParseSettings::default();
+        settings.authorized = true;
+        let mut nodes = Vec::new();
+        let config = provider_config("First", &first_path);
+        let count = run(explode_clash_providers(&config, &mut nodes, &settings));
+
+        assert_eq!(count, 2);
+        assert_eq!(nodes[0].remark, "Provider SS");
+        assert_eq!(nodes[1].remark, "Sibling SS");
+    }
+
+    #[test]
+    fn test_clash_http_provider() {
+        let (url, hits) = serve(PROVIDER_CONTENT);
+        let config = format!(
+            "proxy-providers:\n  Remote:\n    type: http\n    url: \"{}\"\n    path: ./remote.yaml\n",
+            url
+        );
+
+        // Remote providers do not need authorization
+        let mut settings = ParseSettings::default();
+        settings.authorized = false;
+        let mut nodes = Vec::new();
+        let count = run(explode_clash_providers(&config, &mut nodes, &settings));
+
+        assert_eq!(count, 1);
+        assert_eq!(nodes[0].remark, "Provider SS");
+        assert_eq!(nodes[0].group, "Remote");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_remote_proxies_disabled_by_default() {
+        let (url, hits) = serve(PROVIDER_CONTENT);
+        let config = format!("[Remote Proxy]\nRemote = {}\n", url);
+
+        let settings = ParseSettings::default();
+        assert!(!settings.follow_remote_proxies);
+        let mut nodes = Vec::new();
+        let count = run(explode_remote_proxies(&config, &mut nodes, &settings));
+
+        assert_eq!(count, 0);
+        assert!(nodes.is_empty());
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_remote_proxies_enabled() {
+        let (url, hits) = serve(PROVIDER_CONTENT);
+        let config = format!(
+            "[server_remote]\n{}, tag=Remote\nfile:///etc/passwd, tag=Local\n",
+            url
+        );
+
+        let mut settings = ParseSettings::default();
+        settings.follow_remote_proxies = true;
+        let mut nodes = Vec::new();
+        let count = run(explode_remote_proxies(&config, &mut nodes, &settings));
+
+        assert_eq!(count, 1);
+        assert_eq!(nodes[0].remark, "Provider SS");
+        assert_eq!(nodes[0].group, "Remote");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,16 +1,90 @@
 use crate::parser::yaml::clash::clash_proxy_types::ClashProxyYamlInput;
 use serde::Deserialize;
+use serde_yaml::Value;
 
 /// Represents a Clash configuration input structure
 #[derive(Debug, Clone, Deserialize)]
 pub struct ClashYamlInput {
+    /// Raw proxy entries, `Proxy` is the field name used by older Clash versions
+    #[serde(default, alias = "Proxy")]
+    pub proxies: Option<Vec<Value>>,
+    #[serde(default, rename = "proxy-providers")]
+    pub proxy_providers: Option<serde_yaml::Mapping>,
+}
+
+/// Represents an entry of the `proxy-providers` section
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ClashProxyProvider {
+    #[serde(default, rename = "type")]
+    pub provider_type: String,
     #[serde(default)]
-    pub proxies: Vec<ClashProxyYamlInput>,
+    pub url: String,
+    #[serde(default)]
+    pub path: String,
+}
+
+/// Map alternative spellings of a proxy `type` onto the names used by
+/// `ClashProxyYamlInput`
+fn normalize_proxy_type(proxy: &mut Value) {
+    let map = match proxy.as_mapping_mut() {
+        Some(map) => map,
+        None => return,
+    };
+    let proxy_type = match map.get("type").and_then(|v| v.as_str()) {
+        Some(proxy_type) => proxy_type.to_lowercase(),
+        None => return,
+    };
+
+    let normalized = match proxy_type.as_str() {
+        "shadowsocks" => "ss",
+        "shadowsocksr" => "ssr",
+        "socks" => "socks5",
+        "https" => {
+            map.insert(Value::from("tls"), Value::Bool(true));
+            "http"
+        }
+        other => other,
+    };
+    map.insert(Value::from("type"), Value::from(normalized));
 }
 
 impl ClashYamlInput {
     /// Extract proxies from the configuration
+    ///
+    /// Every entry is decoded on its own, so a single malformed proxy does not
+    /// discard the rest of the list.
     pub fn extract_proxies(self) -> Vec<ClashProxyYamlInput> {
         self.proxies
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|mut proxy| {
+                normalize_proxy_type(&mut proxy);
+                match serde_yaml::from_value(proxy) {
+                    Ok(proxy) => Some(proxy),
+                    Err(e) => {
+                        log::warn!("Skipping invalid Clash proxy: {}", e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Extract proxy providers as (name, provider) pairs, in declaration order
+    pub fn extract_proxy_providers(self) -> Vec<(String, ClashProxyProvider)> {
+        self.proxy_providers
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(name, provider)| {
+                let name = name.as_str()?.to_string();
+                match serde_yaml::from_value(provider) {
+                    Ok(provider) => Some((name, provider)),
+                    Err(e) => {
+                        log::warn!("Invalid proxy provider '{}': {}", name, e);
+                        None
+                    }
+                }
+            })
+            .collect()
     }
 }
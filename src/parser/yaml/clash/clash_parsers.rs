@@ -57,6 +57,12 @@ pub fn parse_clash_yaml(content: &str) -> Result<Vec<Proxy>, String> {
             ClashProxyYamlInput::AnyTls(anytls) => {
                 proxies.push(anytls.into());
             }
+            ClashProxyYamlInput::Tuic(tuic) => {
+                proxies.push(tuic.into());
+            }
+            ClashProxyYamlInput::Ssh(ssh) => {
+                proxies.push(ssh.into());
+            }
             ClashProxyYamlInput::Unknown => {
                 // Skip unknown proxy types
             }
@@ -65,3 +71,183 @@ pub fn parse_clash_yaml(content: &str) -> Result<Vec<Proxy>, String> {
 
     Ok(proxies)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::proxy_node::combined::CombinedProxy;
+    use crate::models::{ProxyType, SS_DEFAULT_GROUP, V2RAY_DEFAULT_GROUP, WG_DEFAULT_GROUP};
+
+    const CLASH_FIXTURE: &str = r#"
+proxies:
+  - { name: ss, type: ss, server: ss.example.com, port: 8388, cipher: aes-256-gcm, password: pw, plugin: obfs, plugin-opts: { mode: tls, host: bing.com } }
+  - { name: ssr, type: ssr, server: ssr.example.com, port: 8389, cipher: aes-256-cfb, password: pw, protocol: auth_aes128_md5, obfs: tls1.2_ticket_auth, protocol-param: "1:2" }
+  - { name: vmess, type: vmess, server: vmess.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000001, alterId: 0, tls: true, network: ws, ws-opts: { path: /ws, headers: { Host: cdn.example.com } } }
+  - { name: trojan, type: trojan, server: trojan.example.com, port: 443, password: pw, network: grpc, grpc-opts: { grpc-service-name: svc } }
+  - { name: http, type: http, server: http.example.com, port: 8080, username: user, password: pw }
+  - { name: socks5, type: socks5, server: socks.example.com, port: 1080 }
+  - { name: snell, type: snell, server: snell.example.com, port: 44046, psk: key, version: 2, obfs-opts: { mode: http, host: bing.com } }
+  - { name: wireguard, type: wireguard, server: wg.example.com, port: 51820, ip: 172.16.0.2, private-key: priv, public-key: pub, reserved: [1, 2, 3] }
+  - { name: hysteria, type: hysteria, server: hy.example.com, port: 443, auth-str: pw, up: 50, down: 100, alpn: [h3] }
+  - { name: hysteria2, type: hysteria2, server: hy2.example.com, port: 443, password: pw, alpn: [h3] }
+  - { name: vless, type: vless, server: vless.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, network: tcp, flow: xtls-rprx-vision }
+  - { name: anytls, type: anytls, server: anytls.example.com, port: 443, password: pw }
+  - { name: tuic, type: tuic, server: tuic.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000003, password: pw }
+  - { name: ssh, type: ssh, server: ssh.example.com, port: 22, username: root, private-key: key, host-key: [ssh-ed25519 AAAA] }
+  - { name: direct, type: direct }
+"#;
+
+    const LEGACY_FIXTURE: &str = r#"
+Proxy:
+  - { name: legacy-ss, type: Shadowsocks, server: 1.2.3.4, port: 8388, cipher: AEAD_CHACHA20_POLY1305, password: pw, obfs: http, obfs-host: bing.com }
+  - { name: legacy-https, type: https, server: https.example.com, port: 443 }
+  - { name: legacy-socks, type: socks, server: socks.example.com, port: 1080 }
+  - { name: legacy-vmess, type: vmess, server: 1.2.3.4, port: 443, uuid: 00000000-0000-0000-0000-000000000001 }
+  - { name: legacy-wg, type: wireguard, server: wg.example.com, port: 51820, privateKey: priv, publicKey: pub }
+  - { name: broken, type: trojan, server: broken.example.com }
+  - { name: legacy-hy2, type: hysteria2, server: hy2.example.com, port: 443, password: pw, alpn: "h3,h2" }
+"#;
+
+    #[test]
+    fn test_parse_clash_yaml_every_type() {
+        let nodes = parse_clash_yaml(CLASH_FIXTURE).unwrap();
+        let types: Vec<ProxyType> = nodes.iter().map(|n| n.proxy_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                ProxyType::Shadowsocks,
+                ProxyType::ShadowsocksR,
+                ProxyType::VMess,
+                ProxyType::Trojan,
+                ProxyType::HTTP,
+                ProxyType::Socks5,
+                ProxyType::Snell,
+                ProxyType::WireGuard,
+                ProxyType::Hysteria,
+                ProxyType::Hysteria2,
+                ProxyType::Vless,
+                ProxyType::AnyTls,
+                ProxyType::Tuic,
+                ProxyType::Ssh,
+            ]
+        );
+
+        let ss = &nodes[0];
+        assert_eq!(ss.group, SS_DEFAULT_GROUP);
+        assert_eq!(ss.plugin.as_deref(), Some("obfs-local"));
+        assert_eq!(
+            ss.plugin_option.as_deref(),
+            Some("obfs=tls;obfs-host=bing.com")
+        );
+
+        let vmess = &nodes[2];
+        assert_eq!(vmess.group, V2RAY_DEFAULT_GROUP);
+        assert_eq!(vmess.encrypt_method.as_deref(), Some("auto"));
+        assert_eq!(vmess.path.as_deref(), Some("/ws"));
+        assert_eq!(vmess.host.as_deref(), Some("cdn.example.com"));
+        assert!(vmess.tls_secure);
+
+        let trojan = &nodes[3];
+        assert_eq!(trojan.transfer_protocol.as_deref(), Some("grpc"));
+        assert_eq!(trojan.path.as_deref(), Some("svc"));
+
+        let snell = &nodes[6];
+        assert_eq!(snell.obfs.as_deref(), Some("http"));
+        assert_eq!(snell.host.as_deref(), Some("bing.com"));
+
+        let wg = &nodes[7];
+        assert_eq!(wg.group, WG_DEFAULT_GROUP);
+        assert_eq!(wg.client_id.as_deref(), Some("1,2,3"));
+
+        assert_eq!(nodes[8].up_speed, 50);
+        assert!(nodes[9].alpn.contains("h3"));
+        match nodes[10].combined_proxy {
+            Some(CombinedProxy::Vless(ref vless)) => {
+                assert_eq!(vless.flow.as_deref(), Some("xtls-rprx-vision"))
+            }
+            _ => panic!("expected a VLESS combined proxy"),
+        }
+        assert!(matches!(
+            nodes[11].combined_proxy,
+            Some(CombinedProxy::AnyTls(_))
+        ));
+        match nodes[13].combined_proxy {
+            Some(CombinedProxy::Ssh(ref ssh)) => {
+                assert_eq!(ssh.username, "root");
+                assert_eq!(ssh.private_key.as_deref(), Some("key"));
+                assert_eq!(ssh.host_key, Some(vec!["ssh-ed25519 AAAA".to_string()]));
+            }
+            _ => panic!("expected an SSH combined proxy"),
+        }
+    }
+
+    #[test]
+    fn test_parse_clash_yaml_legacy_fields() {
+        let nodes = parse_clash_yaml(LEGACY_FIXTURE).unwrap();
+        let remarks: Vec<&str> = nodes.iter().map(|n| n.remark.as_str()).collect();
+        assert_eq!(
+            remarks,
+            vec![
+                "legacy-ss",
+                "legacy-https",
+                "legacy-socks",
+                "legacy-vmess",
+                "legacy-wg",
+                "legacy-hy2"
+            ]
+        );
+
+        assert_eq!(nodes[0].proxy_type, ProxyType::Shadowsocks);
+        assert_eq!(
+            nodes[0].encrypt_method.as_deref(),
+            Some("chacha20-ietf-poly1305")
+        );
+        assert_eq!(
+            nodes[0].plugin_option.as_deref(),
+            Some("obfs=http;obfs-host=bing.com")
+        );
+        assert_eq!(nodes[1].proxy_type, ProxyType::HTTPS);
+        assert_eq!(nodes[2].proxy_type, ProxyType::Socks5);
+        assert_eq!(nodes[3].encrypt_method.as_deref(), Some("auto"));
+        assert_eq!(nodes[4].private_key.as_deref(), Some("priv"));
+        assert!(nodes[5].alpn.contains("h2"));
+    }
+
+    #[test]
+    fn test_parse_clash_yaml_vless_transports() {
+        let yaml = r#"
+proxies:
+  - { name: xhttp, type: vless, server: x.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, tls: true, network: xhttp, xhttp-opts: { path: /xh, host: cdn.example.com, mode: stream-one, no-grpc-header: true } }
+  - { name: upgrade, type: vless, server: u.example.com, port: 443, uuid: 00000000-0000-0000-0000-000000000002, network: ws, ws-opts: { path: /up, v2ray-http-upgrade: true } }
+"#;
+        let nodes = parse_clash_yaml(yaml).unwrap();
+        let vless_of = |node: &Proxy| match node.combined_proxy {
+            Some(CombinedProxy::Vless(ref vless)) => vless.clone(),
+            _ => panic!("expected a VLESS proxy"),
+        };
+
+        let xhttp = vless_of(&nodes[0]);
+        assert_eq!(xhttp.network.as_deref(), Some("xhttp"));
+        assert_eq!(xhttp.xhttp_path.as_deref(), Some("/xh"));
+        assert_eq!(xhttp.xhttp_host.as_deref(), Some("cdn.example.com"));
+        assert_eq!(xhttp.xhttp_mode.as_deref(), Some("stream-one"));
+        assert_eq!(
+            xhttp.xhttp_extra.as_deref(),
+            Some(r#"{"noGRPCHeader":true}"#)
+        );
+
+        let upgrade = vless_of(&nodes[1]);
+        assert_eq!(upgrade.network.as_deref(), Some("httpupgrade"));
+        assert_eq!(upgrade.ws_path.as_deref(), Some("/up"));
+    }
+
+    #[test]
+    fn test_explode_clash_uses_typed_parser() {
+        let mut nodes = Vec::new();
+        assert!(crate::parser::explodes::explode_clash(
+            CLASH_FIXTURE,
+            &mut nodes
+        ));
+        assert_eq!(nodes.len(), 14);
+    }
+}
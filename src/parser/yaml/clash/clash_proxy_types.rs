@@ -5,7 +5,8 @@ use super::input_proxy_types::{
     clash_input_hysteria::ClashInputHysteria, clash_input_hysteria2::ClashInputHysteria2,
     clash_input_shadowsocks::ClashInputShadowsocks,
     clash_input_shadowsocksr::ClashInputShadowsocksR, clash_input_snell::ClashInputSnell,
-    clash_input_socks5::ClashInputSocks5, clash_input_trojan::ClashInputTrojan,
+    clash_input_socks5::ClashInputSocks5, clash_input_ssh::ClashInputSsh,
+    clash_input_trojan::ClashInputTrojan, clash_input_tuic::ClashInputTuic,
     clash_input_vless::ClashInputVLess, clash_input_vmess::ClashInputVMess,
     clash_input_wireguard::ClashInputWireGuard,
 };
@@ -50,6 +51,12 @@ pub enum ClashProxyYamlInput {
     #[serde(rename = "anytls")]
     AnyTls(ClashInputAnyTLS),
 
+    #[serde(rename = "tuic")]
+    Tuic(ClashInputTuic),
+
+    #[serde(rename = "ssh")]
+    Ssh(ClashInputSsh),
+
     // Handle other unknown proxy types
     #[serde(other)]
     Unknown,
@@ -1,9 +1,7 @@
-
 use serde::Deserialize;
 
 use crate::models::proxy::Proxy;
-use crate::models::proxy::ProxyType;
-use crate::utils::tribool::OptionSetExt;
+use crate::models::HTTP_DEFAULT_GROUP;
 
 /// Represents an HTTP/HTTPS proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -20,6 +18,10 @@ pub struct ClashInputHttp {
     tls: Option<bool>,
     #[serde(alias = "skip-cert-verify", default)]
     skip_cert_verify: Option<bool>,
+    #[serde(default)]
+    tfo: Option<bool>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputHttp {
@@ -54,19 +56,18 @@ impl ClashInputHttp {
 
 impl Into<Proxy> for ClashInputHttp {
     fn into(self) -> Proxy {
-        let mut proxy = Proxy::default();
-        proxy.proxy_type = if self.tls.unwrap_or(false) {
-            ProxyType::HTTPS
-        } else {
-            ProxyType::HTTP
-        };
-        proxy.remark = self.name;
-        proxy.hostname = self.server;
-        proxy.port = self.port;
-        proxy.username = self.username;
-        proxy.password = self.password;
-        proxy.allow_insecure.set_if_some(self.skip_cert_verify);
-
-        proxy
+        Proxy::http_construct(
+            HTTP_DEFAULT_GROUP,
+            &self.name,
+            &self.server,
+            self.port,
+            self.username.as_deref().unwrap_or(""),
+            self.password.as_deref().unwrap_or(""),
+            self.tls.unwrap_or(false),
+            self.tfo,
+            self.skip_cert_verify,
+            None,
+            self.underlying_proxy.as_deref().unwrap_or(""),
+        )
     }
 }
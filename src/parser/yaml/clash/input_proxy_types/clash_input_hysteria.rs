@@ -2,7 +2,8 @@ use serde::Deserialize;
 
 use crate::models::proxy::Proxy;
 use crate::models::proxy::ProxyType;
-use crate::utils::deserialize::deserialize_string_or_number;
+use crate::models::HYSTERIA_DEFAULT_GROUP;
+use crate::utils::deserialize::{deserialize_string_or_number, deserialize_string_or_seq};
 use crate::utils::tribool::OptionSetExt;
 
 /// Represents a Hysteria proxy in Clash configuration
@@ -36,7 +37,7 @@ pub struct ClashInputHysteria {
     sni: Option<String>,
     #[serde(default)]
     fingerprint: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_string_or_seq")]
     alpn: Option<Vec<String>>,
     #[serde(default)]
     ca: Option<String>,
@@ -56,6 +57,8 @@ pub struct ClashInputHysteria {
     skip_cert_verify: Option<bool>,
     #[serde(default)]
     tfo: Option<bool>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputHysteria {
@@ -164,6 +167,7 @@ impl Into<Proxy> for ClashInputHysteria {
     fn into(self) -> Proxy {
         let mut proxy = Proxy::default();
         proxy.proxy_type = ProxyType::Hysteria;
+        proxy.group = HYSTERIA_DEFAULT_GROUP.to_string();
         proxy.remark = self.name;
         proxy.hostname = self.server;
         proxy.port = self.port;
@@ -218,6 +222,7 @@ impl Into<Proxy> for ClashInputHysteria {
 
         // Set hop interval
         proxy.hop_interval = self.hop_interval.unwrap_or(0);
+        proxy.underlying_proxy = self.underlying_proxy;
 
         proxy
     }
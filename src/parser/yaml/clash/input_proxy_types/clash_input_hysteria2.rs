@@ -1,10 +1,9 @@
-use std::collections::HashSet;
-
 use serde::Deserialize;
 
 use crate::models::proxy::Proxy;
 use crate::models::proxy::ProxyType;
-use crate::utils::deserialize::deserialize_string_or_number;
+use crate::models::HYSTERIA2_DEFAULT_GROUP;
+use crate::utils::deserialize::{deserialize_string_or_number, deserialize_string_or_seq};
 use crate::utils::tribool::OptionSetExt;
 
 /// Represents a Hysteria2 proxy in Clash configuration
@@ -29,8 +28,8 @@ pub struct ClashInputHysteria2 {
     obfs_password: Option<String>,
     #[serde(default)]
     fingerprint: Option<String>,
-    #[serde(default)]
-    alpn: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_string_or_seq")]
+    alpn: Option<Vec<String>>,
     #[serde(default)]
     ca: Option<String>,
     #[serde(alias = "ca-str", default)]
@@ -47,6 +46,8 @@ pub struct ClashInputHysteria2 {
     fast_open: Option<bool>,
     #[serde(default)]
     tfo: Option<bool>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputHysteria2 {
@@ -94,8 +95,8 @@ impl ClashInputHysteria2 {
         self.fingerprint.as_deref()
     }
 
-    pub fn alpn(&self) -> Option<&str> {
-        self.alpn.as_deref()
+    pub fn alpn(&self) -> Option<&Vec<String>> {
+        self.alpn.as_ref()
     }
 
     pub fn ca(&self) -> Option<&str> {
@@ -135,6 +136,7 @@ impl Into<Proxy> for ClashInputHysteria2 {
     fn into(self) -> Proxy {
         let mut proxy = Proxy::default();
         proxy.proxy_type = ProxyType::Hysteria2;
+        proxy.group = HYSTERIA2_DEFAULT_GROUP.to_string();
         proxy.remark = self.name;
         proxy.hostname = self.server;
         proxy.port = self.port;
@@ -158,15 +160,9 @@ impl Into<Proxy> for ClashInputHysteria2 {
         // Set TLS related fields
         proxy.fingerprint = self.fingerprint;
 
-        // Handle alpn as a comma-separated string to HashSet
-        if let Some(alpn_value) = self.alpn {
-            let mut alpn_set = HashSet::new();
-            for value in alpn_value.split(',').map(|s| s.trim().to_string()) {
-                if !value.is_empty() {
-                    alpn_set.insert(value);
-                }
-            }
-            proxy.alpn = alpn_set;
+        // Handle alpn as a HashSet
+        if let Some(alpn_values) = self.alpn {
+            proxy.alpn = alpn_values.into_iter().collect();
         }
 
         proxy.ca = self.ca;
@@ -180,6 +176,7 @@ impl Into<Proxy> for ClashInputHysteria2 {
         // Set boolean options
         proxy.allow_insecure.set_if_some(self.skip_cert_verify);
         proxy.tcp_fast_open.set_if_some(self.fast_open.or(self.tfo));
+        proxy.underlying_proxy = self.underlying_proxy;
 
         proxy
     }
@@ -3,8 +3,7 @@ use std::collections::HashMap;
 use serde::Deserialize;
 
 use crate::models::proxy::Proxy;
-use crate::models::proxy::ProxyType;
-use crate::utils::tribool::OptionSetExt;
+use crate::models::SS_DEFAULT_GROUP;
 
 /// Represents a Shadowsocks proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -24,7 +23,14 @@ pub struct ClashInputShadowsocks {
     #[serde(default)]
     plugin: Option<String>,
     #[serde(alias = "plugin-opts", default)]
-    plugin_opts: Option<HashMap<String, String>>,
+    plugin_opts: Option<HashMap<String, serde_yaml::Value>>,
+    // Legacy obfs fields used before `plugin-opts` existed
+    #[serde(default)]
+    obfs: Option<String>,
+    #[serde(alias = "obfs-host", default)]
+    obfs_host: Option<String>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputShadowsocks {
@@ -64,38 +70,95 @@ impl ClashInputShadowsocks {
         self.plugin.as_deref()
     }
 
-    pub fn plugin_opts(&self) -> Option<&HashMap<String, String>> {
+    pub fn plugin_opts(&self) -> Option<&HashMap<String, serde_yaml::Value>> {
         self.plugin_opts.as_ref()
     }
 }
 
+/// Read a plugin option as a string, accepting booleans and numbers too
+fn plugin_opt(opts: &HashMap<String, serde_yaml::Value>, key: &str) -> String {
+    match opts.get(key) {
+        Some(serde_yaml::Value::String(s)) => s.clone(),
+        Some(serde_yaml::Value::Bool(b)) => b.to_string(),
+        Some(serde_yaml::Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
 impl Into<Proxy> for ClashInputShadowsocks {
     fn into(self) -> Proxy {
-        let mut proxy = Proxy::default();
-        proxy.proxy_type = ProxyType::Shadowsocks;
-        proxy.remark = self.name;
-        proxy.hostname = self.server;
-        proxy.port = self.port;
-        proxy.encrypt_method = Some(self.cipher);
-        proxy.password = Some(self.password);
-        proxy.udp.set_if_some(self.udp);
-        proxy.tcp_fast_open.set_if_some(self.tfo);
-        proxy.allow_insecure.set_if_some(self.skip_cert_verify);
-
-        if let Some(plugin_name) = self.plugin {
-            proxy.plugin = Some(plugin_name);
-            if let Some(opts) = self.plugin_opts {
-                let mut plugin_opts_str = String::new();
-                for (key, value) in opts {
-                    if !plugin_opts_str.is_empty() {
-                        plugin_opts_str.push(';');
+        let opts = self.plugin_opts.unwrap_or_default();
+
+        // Convert Clash plugin names and options to the SIP003 form used internally
+        let (plugin, plugin_opts) = match self.plugin.as_deref() {
+            Some("obfs") => {
+                let mut plugin_opts = format!("obfs={}", plugin_opt(&opts, "mode"));
+                let host = plugin_opt(&opts, "host");
+                if !host.is_empty() {
+                    plugin_opts.push_str(&format!(";obfs-host={}", host));
+                }
+                ("obfs-local".to_string(), plugin_opts)
+            }
+            Some("v2ray-plugin") => {
+                let mut plugin_opts = format!("mode={}", plugin_opt(&opts, "mode"));
+                if plugin_opt(&opts, "tls") == "true" {
+                    plugin_opts.push_str(";tls");
+                }
+                for key in ["host", "path"] {
+                    let value = plugin_opt(&opts, key);
+                    if !value.is_empty() {
+                        plugin_opts.push_str(&format!(";{}={}", key, value));
                     }
-                    plugin_opts_str.push_str(&format!("{}={}", key, value));
                 }
-                proxy.plugin_option = Some(plugin_opts_str);
+                if plugin_opt(&opts, "mux") == "true" {
+                    plugin_opts.push_str(";mux=4");
+                }
+                ("v2ray-plugin".to_string(), plugin_opts)
             }
-        }
-
-        proxy
+            Some(plugin) => {
+                let mut keys: Vec<&String> = opts.keys().collect();
+                keys.sort();
+                let plugin_opts = keys
+                    .into_iter()
+                    .map(|key| format!("{}={}", key, plugin_opt(&opts, key)))
+                    .collect::<Vec<_>>()
+                    .join(";");
+                (plugin.to_string(), plugin_opts)
+            }
+            None => match self.obfs {
+                Some(obfs) => {
+                    let mut plugin_opts = format!("obfs={}", obfs);
+                    if let Some(host) = self.obfs_host.filter(|h| !h.is_empty()) {
+                        plugin_opts.push_str(&format!(";obfs-host={}", host));
+                    }
+                    ("obfs-local".to_string(), plugin_opts)
+                }
+                None => (String::new(), String::new()),
+            },
+        };
+
+        // go-shadowsocks2 style cipher names
+        let cipher = match self.cipher.as_str() {
+            "AEAD_CHACHA20_POLY1305" => "chacha20-ietf-poly1305".to_string(),
+            "AEAD_AES_128_GCM" => "aes-128-gcm".to_string(),
+            "AEAD_AES_256_GCM" => "aes-256-gcm".to_string(),
+            _ => self.cipher,
+        };
+
+        Proxy::ss_construct(
+            SS_DEFAULT_GROUP,
+            &self.name,
+            &self.server,
+            self.port,
+            &self.password,
+            &cipher,
+            &plugin,
+            &plugin_opts,
+            self.udp,
+            self.tfo,
+            self.skip_cert_verify,
+            None,
+            self.underlying_proxy.as_deref().unwrap_or(""),
+        )
     }
 }
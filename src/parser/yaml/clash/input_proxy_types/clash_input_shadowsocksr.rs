@@ -1,9 +1,7 @@
-
 use serde::Deserialize;
 
 use crate::models::proxy::Proxy;
-use crate::models::proxy::ProxyType;
-use crate::utils::tribool::OptionSetExt;
+use crate::models::SSR_DEFAULT_GROUP;
 
 /// Represents a ShadowsocksR proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -26,6 +24,8 @@ pub struct ClashInputShadowsocksR {
     protocol_param: Option<String>,
     #[serde(alias = "obfs-param", default)]
     obfs_param: Option<String>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputShadowsocksR {
@@ -80,21 +80,21 @@ impl ClashInputShadowsocksR {
 
 impl Into<Proxy> for ClashInputShadowsocksR {
     fn into(self) -> Proxy {
-        let mut proxy = Proxy::default();
-        proxy.proxy_type = ProxyType::ShadowsocksR;
-        proxy.remark = self.name;
-        proxy.hostname = self.server;
-        proxy.port = self.port;
-        proxy.encrypt_method = Some(self.cipher);
-        proxy.password = Some(self.password);
-        proxy.protocol = Some(self.protocol);
-        proxy.obfs = Some(self.obfs);
-        proxy.udp.set_if_some(self.udp);
-        proxy.tcp_fast_open.set_if_some(self.tfo);
-        proxy.allow_insecure.set_if_some(self.skip_cert_verify);
-        proxy.protocol_param = self.protocol_param;
-        proxy.obfs_param = self.obfs_param;
-
-        proxy
+        Proxy::ssr_construct(
+            SSR_DEFAULT_GROUP,
+            &self.name,
+            &self.server,
+            self.port,
+            &self.protocol,
+            &self.cipher,
+            &self.obfs,
+            &self.password,
+            self.obfs_param.as_deref().unwrap_or(""),
+            self.protocol_param.as_deref().unwrap_or(""),
+            self.udp,
+            self.tfo,
+            self.skip_cert_verify,
+            self.underlying_proxy.as_deref().unwrap_or(""),
+        )
     }
 }
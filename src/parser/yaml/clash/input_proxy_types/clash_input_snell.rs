@@ -3,8 +3,7 @@ use std::collections::HashMap;
 use serde::Deserialize;
 
 use crate::models::proxy::Proxy;
-use crate::models::proxy::ProxyType;
-use crate::utils::tribool::OptionSetExt;
+use crate::models::SNELL_DEFAULT_GROUP;
 
 /// Represents a Snell proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -20,10 +19,15 @@ pub struct ClashInputSnell {
     obfs: Option<String>,
     #[serde(rename = "obfs-opts", default)]
     obfs_opts: Option<HashMap<String, String>>,
+    // Legacy obfs host used before `obfs-opts` existed
+    #[serde(alias = "obfs-host", default)]
+    obfs_host: Option<String>,
     #[serde(default)]
     udp: Option<bool>,
     #[serde(default)]
     tfo: Option<bool>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputSnell {
@@ -66,29 +70,40 @@ impl ClashInputSnell {
 
 impl Into<Proxy> for ClashInputSnell {
     fn into(self) -> Proxy {
-        let mut proxy = Proxy::default();
-        proxy.proxy_type = ProxyType::Snell;
-        proxy.remark = self.name;
-        proxy.hostname = self.server;
-        proxy.port = self.port;
-        proxy.password = Some(self.psk);
-        proxy.snell_version = self.version.unwrap_or(1) as u16;
-        proxy.obfs = self.obfs;
-
-        if let Some(opts) = self.obfs_opts {
-            let mut obfs_opts_str = String::new();
-            for (key, value) in opts {
-                if !obfs_opts_str.is_empty() {
-                    obfs_opts_str.push(';');
-                }
-                obfs_opts_str.push_str(&format!("{}={}", key, value));
-            }
-            proxy.plugin_option = Some(obfs_opts_str);
+        let opts = self.obfs_opts.unwrap_or_default();
+        let obfs = opts.get("mode").cloned().or(self.obfs).unwrap_or_default();
+        let host = opts
+            .get("host")
+            .cloned()
+            .or(self.obfs_host)
+            .unwrap_or_default();
+
+        let mut proxy = Proxy::snell_construct(
+            SNELL_DEFAULT_GROUP.to_string(),
+            self.name,
+            self.server,
+            self.port,
+            self.psk,
+            obfs,
+            host,
+            self.version.unwrap_or(1) as u16,
+            self.udp,
+            self.tfo,
+            None,
+            self.underlying_proxy,
+        );
+
+        if !opts.is_empty() {
+            let mut keys: Vec<&String> = opts.keys().collect();
+            keys.sort();
+            proxy.obfs_param = Some(
+                keys.into_iter()
+                    .map(|key| format!("{}={}", key, opts[key]))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+            );
         }
 
-        proxy.udp.set_if_some(self.udp);
-        proxy.tcp_fast_open.set_if_some(self.tfo);
-
         proxy
     }
 }
@@ -1,9 +1,7 @@
-
 use serde::Deserialize;
 
 use crate::models::proxy::Proxy;
-use crate::models::proxy::ProxyType;
-use crate::utils::tribool::OptionSetExt;
+use crate::models::SOCKS_DEFAULT_GROUP;
 
 /// Represents a SOCKS5 proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -22,6 +20,8 @@ pub struct ClashInputSocks5 {
     udp: Option<bool>,
     #[serde(default)]
     tfo: Option<bool>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputSocks5 {
@@ -60,17 +60,17 @@ impl ClashInputSocks5 {
 
 impl Into<Proxy> for ClashInputSocks5 {
     fn into(self) -> Proxy {
-        let mut proxy = Proxy::default();
-        proxy.proxy_type = ProxyType::Socks5;
-        proxy.remark = self.name;
-        proxy.hostname = self.server;
-        proxy.port = self.port;
-        proxy.username = self.username;
-        proxy.password = self.password;
-        proxy.allow_insecure.set_if_some(self.skip_cert_verify);
-        proxy.udp.set_if_some(self.udp);
-        proxy.tcp_fast_open.set_if_some(self.tfo);
-
-        proxy
+        Proxy::socks_construct(
+            SOCKS_DEFAULT_GROUP,
+            &self.name,
+            &self.server,
+            self.port,
+            self.username.as_deref().unwrap_or(""),
+            self.password.as_deref().unwrap_or(""),
+            self.udp,
+            self.tfo,
+            self.skip_cert_verify,
+            self.underlying_proxy.as_deref().unwrap_or(""),
+        )
     }
 }
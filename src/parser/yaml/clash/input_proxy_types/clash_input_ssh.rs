@@ -0,0 +1,51 @@
+use serde::Deserialize;
+
+use crate::models::proxy::Proxy;
+use crate::models::proxy::ProxyType;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::ssh::SshProxy;
+use crate::models::SSH_DEFAULT_GROUP;
+
+/// Represents an SSH proxy in Clash configuration (mihomo extension)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClashInputSsh {
+    name: String,
+    server: String,
+    port: u16,
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(alias = "private-key", default)]
+    private_key: Option<String>,
+    #[serde(alias = "private-key-passphrase", default)]
+    private_key_passphrase: Option<String>,
+    #[serde(alias = "host-key", default)]
+    host_key: Option<Vec<String>>,
+    #[serde(alias = "host-key-algorithms", default)]
+    host_key_algorithms: Option<Vec<String>>,
+}
+
+impl From<ClashInputSsh> for Proxy {
+    fn from(ssh: ClashInputSsh) -> Self {
+        let ssh_proxy = SshProxy {
+            username: ssh.username,
+            password: ssh.password,
+            private_key: ssh.private_key,
+            private_key_passphrase: ssh.private_key_passphrase,
+            host_key: ssh.host_key,
+            host_key_algorithms: ssh.host_key_algorithms,
+        };
+
+        Proxy {
+            proxy_type: ProxyType::Ssh,
+            group: SSH_DEFAULT_GROUP.to_string(),
+            remark: ssh.name,
+            hostname: ssh.server,
+            port: ssh.port,
+            combined_proxy: Some(CombinedProxy::Ssh(ssh_proxy)),
+            ..Default::default()
+        }
+    }
+}
@@ -1,9 +1,8 @@
-
 use serde::Deserialize;
 
+use super::clash_input_vless::{GrpcOptions, WsOptions};
 use crate::models::proxy::Proxy;
-use crate::models::proxy::ProxyType;
-use crate::utils::tribool::OptionSetExt;
+use crate::models::TROJAN_DEFAULT_GROUP;
 
 /// Represents a Trojan proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -23,6 +22,12 @@ pub struct ClashInputTrojan {
     network: Option<String>,
     #[serde(default)]
     sni: Option<String>,
+    #[serde(alias = "ws-opts", default)]
+    ws_opts: Option<WsOptions>,
+    #[serde(alias = "grpc-opts", default)]
+    grpc_opts: Option<GrpcOptions>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputTrojan {
@@ -65,21 +70,37 @@ impl ClashInputTrojan {
 
 impl Into<Proxy> for ClashInputTrojan {
     fn into(self) -> Proxy {
-        let mut proxy = Proxy::default();
-        proxy.proxy_type = ProxyType::Trojan;
-        proxy.remark = self.name;
-        proxy.hostname = self.server;
-        proxy.port = self.port;
-        proxy.password = Some(self.password);
-        proxy.udp.set_if_some(self.udp);
-        proxy.tcp_fast_open.set_if_some(self.tfo);
-        proxy.allow_insecure.set_if_some(self.skip_cert_verify);
-        proxy.sni = self.sni;
-
-        if let Some(net) = self.network {
-            proxy.transfer_protocol = Some(net);
+        let mut host = None;
+        let mut path = None;
+        match self.network.as_deref() {
+            Some("ws") => {
+                if let Some(opts) = self.ws_opts {
+                    path = opts.path;
+                    host = opts.headers.and_then(|h| h.get("Host").cloned());
+                }
+            }
+            Some("grpc") => {
+                path = self.grpc_opts.and_then(|opts| opts.grpc_service_name);
+            }
+            _ => {}
         }
 
-        proxy
+        Proxy::trojan_construct(
+            TROJAN_DEFAULT_GROUP.to_string(),
+            self.name,
+            self.server,
+            self.port,
+            self.password,
+            self.network,
+            host,
+            path,
+            self.sni,
+            true,
+            self.udp,
+            self.tfo,
+            self.skip_cert_verify,
+            None,
+            self.underlying_proxy,
+        )
     }
 }
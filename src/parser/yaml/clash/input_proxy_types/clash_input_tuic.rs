@@ -0,0 +1,78 @@
+use serde::Deserialize;
+
+use crate::models::proxy::Proxy;
+use crate::models::proxy::ProxyType;
+use crate::models::proxy_node::combined::CombinedProxy;
+use crate::models::proxy_node::tuic::TuicProxy;
+use crate::models::TUIC_DEFAULT_GROUP;
+
+/// Represents a TUIC v5 proxy in Clash configuration (mihomo extension)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ClashInputTuic {
+    name: String,
+    server: String,
+    port: u16,
+    #[serde(default)]
+    uuid: String,
+    #[serde(default)]
+    password: String,
+    #[serde(alias = "heartbeat-interval", default)]
+    heartbeat_interval: Option<u32>,
+    #[serde(default)]
+    alpn: Option<Vec<String>>,
+    #[serde(alias = "disable-sni", default)]
+    disable_sni: Option<bool>,
+    #[serde(alias = "reduce-rtt", default)]
+    reduce_rtt: Option<bool>,
+    #[serde(alias = "request-timeout", default)]
+    request_timeout: Option<u32>,
+    #[serde(alias = "udp-relay-mode", default)]
+    udp_relay_mode: Option<String>,
+    #[serde(alias = "congestion-controller", default)]
+    congestion_controller: Option<String>,
+    #[serde(alias = "max-udp-relay-packet-size", default)]
+    max_udp_relay_packet_size: Option<u32>,
+    #[serde(alias = "fast-open", default)]
+    fast_open: Option<bool>,
+    #[serde(alias = "skip-cert-verify", default)]
+    skip_cert_verify: Option<bool>,
+    #[serde(default)]
+    sni: Option<String>,
+    #[serde(default)]
+    udp: Option<bool>,
+}
+
+impl From<ClashInputTuic> for Proxy {
+    fn from(tuic: ClashInputTuic) -> Self {
+        let tuic_proxy = TuicProxy {
+            uuid: tuic.uuid,
+            password: tuic.password,
+            congestion_control: tuic.congestion_controller,
+            udp_relay_mode: tuic.udp_relay_mode,
+            alpn: tuic.alpn.map(|v| v.into_iter().collect()),
+            sni: tuic.sni.clone(),
+            disable_sni: tuic.disable_sni,
+            skip_cert_verify: tuic.skip_cert_verify,
+            reduce_rtt: tuic.reduce_rtt,
+            heartbeat_interval: tuic.heartbeat_interval,
+            request_timeout: tuic.request_timeout,
+            max_udp_relay_packet_size: tuic.max_udp_relay_packet_size,
+            fast_open: tuic.fast_open,
+        };
+
+        Proxy {
+            proxy_type: ProxyType::Tuic,
+            group: TUIC_DEFAULT_GROUP.to_string(),
+            remark: tuic.name,
+            hostname: tuic.server,
+            port: tuic.port,
+            sni: tuic.sni,
+            allow_insecure: tuic.skip_cert_verify,
+            tcp_fast_open: tuic.fast_open,
+            udp: tuic.udp,
+            combined_proxy: Some(CombinedProxy::Tuic(tuic_proxy)),
+            ..Default::default()
+        }
+    }
+}
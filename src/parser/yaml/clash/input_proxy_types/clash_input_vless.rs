@@ -6,6 +6,7 @@ use crate::models::proxy::Proxy;
 use crate::models::proxy::ProxyType;
 use crate::models::proxy_node::combined::CombinedProxy;
 use crate::models::proxy_node::vless::VlessProxy;
+use crate::models::V2RAY_DEFAULT_GROUP;
 
 /// Represents a VLESS proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -45,6 +46,8 @@ pub struct ClashInputVLess {
     ws_path: Option<String>,
     #[serde(alias = "ws-headers", default)]
     ws_headers: Option<HashMap<String, String>>,
+    #[serde(alias = "xhttp-opts", default)]
+    xhttp_opts: Option<XhttpOptions>,
     #[serde(alias = "skip-cert-verify", default)]
     skip_cert_verify: Option<bool>,
     #[serde(default)]
@@ -132,6 +135,10 @@ impl ClashInputVLess {
         self.ws_headers.as_ref()
     }
 
+    pub fn xhttp_opts(&self) -> Option<&XhttpOptions> {
+        self.xhttp_opts.as_ref()
+    }
+
     pub fn skip_cert_verify(&self) -> Option<bool> {
         self.skip_cert_verify
     }
@@ -213,10 +220,51 @@ pub struct WsOptions {
     pub v2ray_http_upgrade_fast_open: Option<bool>,
 }
 
+/// XHTTP (SplitHTTP) options for VLESS proxy
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct XhttpOptions {
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub host: Option<String>,
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub headers: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub no_grpc_header: Option<bool>,
+    #[serde(default)]
+    pub x_padding_bytes: Option<String>,
+}
+
+impl XhttpOptions {
+    /// Options without a dedicated `VlessProxy` field, as an Xray `extra` object
+    fn extra(&self) -> Option<String> {
+        let mut extra = serde_json::Map::new();
+        if let Some(headers) = &self.headers {
+            extra.insert("headers".to_string(), serde_json::json!(headers));
+        }
+        if let Some(no_grpc_header) = self.no_grpc_header {
+            extra.insert("noGRPCHeader".to_string(), no_grpc_header.into());
+        }
+        if let Some(x_padding_bytes) = &self.x_padding_bytes {
+            extra.insert("xPaddingBytes".to_string(), x_padding_bytes.clone().into());
+        }
+
+        if extra.is_empty() {
+            None
+        } else {
+            Some(serde_json::Value::Object(extra).to_string())
+        }
+    }
+}
+
 impl Into<Proxy> for ClashInputVLess {
     fn into(self) -> Proxy {
         let mut proxy = Proxy::default();
         proxy.proxy_type = ProxyType::Vless;
+        proxy.group = V2RAY_DEFAULT_GROUP.to_string();
 
         let mut vless_proxy = VlessProxy::default();
         vless_proxy.uuid = self.uuid;
@@ -242,8 +290,12 @@ impl Into<Proxy> for ClashInputVLess {
         // Handle network-specific options
         if let Some(net) = self.network.as_deref() {
             match net {
-                "ws" => {
+                "ws" | "httpupgrade" => {
                     if let Some(opts) = self.ws_opts {
+                        // mihomo expresses HTTPUpgrade as a websocket option
+                        if opts.v2ray_http_upgrade.unwrap_or(false) {
+                            vless_proxy.network = Some("httpupgrade".to_string());
+                        }
                         vless_proxy.ws_path = opts.path;
                         vless_proxy.ws_headers = opts.headers;
                     } else {
@@ -273,6 +325,14 @@ impl Into<Proxy> for ClashInputVLess {
                         vless_proxy.grpc_service_name = opts.grpc_service_name;
                     }
                 }
+                "xhttp" => {
+                    if let Some(opts) = self.xhttp_opts {
+                        vless_proxy.xhttp_extra = opts.extra();
+                        vless_proxy.xhttp_path = opts.path;
+                        vless_proxy.xhttp_host = opts.host;
+                        vless_proxy.xhttp_mode = opts.mode;
+                    }
+                }
                 _ => {}
             }
         }
@@ -2,9 +2,9 @@ use std::collections::HashMap;
 
 use serde::Deserialize;
 
+use super::clash_input_vless::{GrpcOptions, H2Options, HttpOptions, WsOptions};
 use crate::models::proxy::Proxy;
-use crate::models::proxy::ProxyType;
-use crate::utils::tribool::OptionSetExt;
+use crate::models::V2RAY_DEFAULT_GROUP;
 
 /// Represents a VMess proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -16,6 +16,7 @@ pub struct ClashInputVMess {
     uuid: String,
     #[serde(alias = "alterId", default)]
     alter_id: u32,
+    #[serde(default = "default_cipher")]
     cipher: String,
     #[serde(default)]
     udp: Option<bool>,
@@ -33,6 +34,20 @@ pub struct ClashInputVMess {
     tls: Option<bool>,
     #[serde(alias = "servername", default)]
     servername: Option<String>,
+    #[serde(alias = "ws-opts", default)]
+    ws_opts: Option<WsOptions>,
+    #[serde(alias = "http-opts", default)]
+    http_opts: Option<HttpOptions>,
+    #[serde(alias = "h2-opts", default)]
+    h2_opts: Option<H2Options>,
+    #[serde(alias = "grpc-opts", default)]
+    grpc_opts: Option<GrpcOptions>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
+}
+
+fn default_cipher() -> String {
+    "auto".to_string()
 }
 
 impl ClashInputVMess {
@@ -95,70 +110,85 @@ impl ClashInputVMess {
 
 impl Into<Proxy> for ClashInputVMess {
     fn into(self) -> Proxy {
-        let mut proxy = Proxy::default();
-        proxy.proxy_type = ProxyType::VMess;
-        proxy.remark = self.name;
-        proxy.hostname = self.server.clone();
-        proxy.port = self.port;
-        proxy.user_id = Some(self.uuid);
-        proxy.alter_id = self.alter_id as u16;
-        proxy.encrypt_method = Some(self.cipher);
-        proxy.udp.set_if_some(self.udp);
-        proxy.tcp_fast_open.set_if_some(self.tfo);
-        proxy.allow_insecure.set_if_some(self.skip_cert_verify);
-        proxy.tls_secure = self.tls.unwrap_or(false);
-        proxy.server_name = self.servername;
-
-        // Network protocol handling
-        if let Some(net) = self.network {
-            proxy.transfer_protocol = Some(net.clone());
-            match net.as_str() {
-                "ws" => {
-                    if let Some(path) = self.ws_path {
-                        proxy.path = Some(path);
-                    }
-                    if let Some(headers) = self.ws_headers {
-                        if let Some(host) = headers.get("Host") {
-                            proxy.host = Some(host.clone());
-                        }
-                        if let Some(edge) = headers.get("Edge") {
-                            proxy.edge = Some(edge.clone());
-                        }
-                    }
+        let network = self.network.unwrap_or_else(|| "tcp".to_string());
+        let mut path = String::new();
+        let mut host = String::new();
+        let mut edge = String::new();
+
+        // Network protocol handling, preferring the `*-opts` layout over legacy fields
+        match network.as_str() {
+            "ws" => {
+                let (ws_path, ws_headers) = match self.ws_opts {
+                    Some(opts) => (opts.path, opts.headers),
+                    None => (self.ws_path, self.ws_headers),
+                };
+                path = ws_path.unwrap_or_default();
+                if let Some(headers) = ws_headers {
+                    host = headers.get("Host").cloned().unwrap_or_default();
+                    edge = headers.get("Edge").cloned().unwrap_or_default();
                 }
-                "http" => {
-                    if let Some(path) = self.ws_path {
-                        proxy.path = Some(path);
-                    }
+            }
+            "http" => {
+                if let Some(opts) = self.http_opts {
+                    path = opts
+                        .path
+                        .and_then(|p| p.into_iter().next())
+                        .unwrap_or_default();
+                    host = opts
+                        .headers
+                        .and_then(|h| h.get("Host").and_then(|v| v.first().cloned()))
+                        .unwrap_or_default();
+                } else {
+                    path = self.ws_path.unwrap_or_default();
                     if let Some(headers) = self.ws_headers {
-                        if let Some(host) = headers.get("Host") {
-                            proxy.host = Some(host.clone());
-                        }
-                        if let Some(edge) = headers.get("Edge") {
-                            proxy.edge = Some(edge.clone());
-                        }
+                        host = headers.get("Host").cloned().unwrap_or_default();
                     }
                 }
-                "h2" => {
-                    if let Some(path) = self.ws_path {
-                        proxy.path = Some(path);
-                    }
+            }
+            "h2" => {
+                if let Some(opts) = self.h2_opts {
+                    path = opts.path.unwrap_or_default();
+                    host = opts
+                        .host
+                        .and_then(|h| h.into_iter().next())
+                        .unwrap_or_default();
+                } else {
+                    path = self.ws_path.unwrap_or_default();
                     if let Some(headers) = self.ws_headers {
-                        if let Some(host) = headers.get("Host") {
-                            proxy.host = Some(host.clone());
-                        }
+                        host = headers.get("Host").cloned().unwrap_or_default();
                     }
                 }
-                "grpc" => {
-                    if let Some(path) = self.ws_path {
-                        proxy.path = Some(path);
-                    }
-                    proxy.host = Some(self.server);
-                }
-                _ => {}
             }
+            "grpc" => {
+                path = self
+                    .grpc_opts
+                    .and_then(|opts| opts.grpc_service_name)
+                    .or(self.ws_path)
+                    .unwrap_or_default();
+            }
+            _ => {}
         }
 
-        proxy
+        Proxy::vmess_construct(
+            V2RAY_DEFAULT_GROUP,
+            &self.name,
+            &self.server,
+            self.port,
+            "",
+            &self.uuid,
+            self.alter_id as u16,
+            &network,
+            &self.cipher,
+            &path,
+            &host,
+            &edge,
+            if self.tls.unwrap_or(false) { "tls" } else { "" },
+            self.servername.as_deref().unwrap_or(""),
+            self.udp,
+            self.tfo,
+            self.skip_cert_verify,
+            None,
+            self.underlying_proxy.as_deref().unwrap_or(""),
+        )
     }
 }
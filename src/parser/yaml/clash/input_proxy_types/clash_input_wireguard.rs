@@ -1,10 +1,7 @@
-use std::collections::HashSet;
-
 use serde::Deserialize;
 
 use crate::models::proxy::Proxy;
-use crate::models::proxy::ProxyType;
-use crate::utils::tribool::OptionSetExt;
+use crate::models::WG_DEFAULT_GROUP;
 
 /// Represents a WireGuard proxy in Clash configuration
 #[derive(Debug, Clone, Deserialize)]
@@ -13,15 +10,15 @@ pub struct ClashInputWireGuard {
     name: String,
     server: String,
     port: u16,
-    #[serde(alias = "private-key")]
+    #[serde(alias = "privateKey")]
     private_key: String,
-    #[serde(alias = "public-key")]
+    #[serde(alias = "publicKey")]
     public_key: String,
     #[serde(default)]
     ip: Option<String>,
     #[serde(default)]
     ipv6: Option<String>,
-    #[serde(alias = "preshared-key", default)]
+    #[serde(alias = "presharedKey", default)]
     preshared_key: Option<String>,
     #[serde(default)]
     dns: Option<Vec<String>>,
@@ -33,6 +30,14 @@ pub struct ClashInputWireGuard {
     keepalive: Option<u32>,
     #[serde(default)]
     udp: Option<bool>,
+    #[serde(default)]
+    reserved: Option<serde_yaml::Value>,
+    #[serde(alias = "clientId", default)]
+    client_id: Option<String>,
+    #[serde(alias = "testUrl", default)]
+    test_url: Option<String>,
+    #[serde(alias = "underlying-proxy", default)]
+    underlying_proxy: Option<String>,
 }
 
 impl ClashInputWireGuard {
@@ -91,30 +96,39 @@ impl ClashInputWireGuard {
 
 impl Into<Proxy> for ClashInputWireGuard {
     fn into(self) -> Proxy {
-        let mut proxy = Proxy::default();
-        proxy.proxy_type = ProxyType::WireGuard;
-        proxy.remark = self.name;
-        proxy.hostname = self.server;
-        proxy.port = self.port;
-        proxy.private_key = Some(self.private_key);
-        proxy.public_key = Some(self.public_key);
-        proxy.self_ip = self.ip;
-        proxy.self_ipv6 = self.ipv6;
-        proxy.pre_shared_key = self.preshared_key;
-
-        // Convert Vec<String> to HashSet<String> for dns_servers
-        let mut dns_set = HashSet::new();
-        if let Some(dns_servers) = self.dns {
-            for dns_server in dns_servers {
-                dns_set.insert(dns_server);
-            }
+        // `reserved` may be a list of bytes or a base64 string
+        let client_id = match self.reserved {
+            Some(serde_yaml::Value::Sequence(values)) => values
+                .iter()
+                .filter_map(|v| v.as_u64())
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            Some(serde_yaml::Value::String(value)) => value,
+            _ => self.client_id.unwrap_or_default(),
+        };
+
+        let mut proxy = Proxy::wireguard_construct(
+            WG_DEFAULT_GROUP.to_string(),
+            self.name,
+            self.server,
+            self.port,
+            self.ip.unwrap_or_default(),
+            self.ipv6.unwrap_or_default(),
+            self.private_key,
+            self.public_key,
+            self.preshared_key.unwrap_or_default(),
+            self.dns.unwrap_or_default(),
+            self.mtu.map(|v| v as u16),
+            self.keepalive.map(|v| v as u16),
+            self.test_url.unwrap_or_default(),
+            client_id,
+            self.udp,
+            self.underlying_proxy,
+        );
+        if let Some(allowed_ips) = self.allowed_ips {
+            proxy.allowed_ips = allowed_ips.join(",");
         }
-        proxy.dns_servers = dns_set;
-
-        proxy.mtu = self.mtu.unwrap_or(0) as u16;
-        proxy.allowed_ips = self.allowed_ips.unwrap_or_default().join(",");
-        proxy.keep_alive = self.keepalive.unwrap_or(0) as u16;
-        proxy.udp.set_if_some(self.udp);
 
         proxy
     }
@@ -6,7 +6,9 @@ pub mod clash_input_shadowsocks;
 pub mod clash_input_shadowsocksr;
 pub mod clash_input_snell;
 pub mod clash_input_socks5;
+pub mod clash_input_ssh;
 pub mod clash_input_trojan;
+pub mod clash_input_tuic;
 pub mod clash_input_vless;
 pub mod clash_input_vmess;
 pub mod clash_input_wireguard;
@@ -3,6 +3,6 @@ mod clash_parsers;
 mod clash_proxy_types;
 mod input_proxy_types;
 
-pub use clash_input::ClashYamlInput;
+pub use clash_input::{ClashProxyProvider, ClashYamlInput};
 pub use clash_parsers::parse_clash_yaml;
 pub use clash_proxy_types::ClashProxyYamlInput;
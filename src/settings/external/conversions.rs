@@ -20,8 +20,10 @@ impl From<YamlExternalSettings> for ExternalSettings {
         settings.quan_rule_base = yaml_settings.custom.rule_bases.quan_rule_base;
         settings.quanx_rule_base = yaml_settings.custom.rule_bases.quanx_rule_base;
         settings.loon_rule_base = yaml_settings.custom.rule_bases.loon_rule_base;
+        settings.shadowrocket_rule_base = yaml_settings.custom.rule_bases.shadowrocket_rule_base;
         settings.sssub_rule_base = yaml_settings.custom.rule_bases.sssub_rule_base;
         settings.singbox_rule_base = yaml_settings.custom.rule_bases.singbox_rule_base;
+        settings.xray_rule_base = yaml_settings.custom.rule_bases.xray_rule_base;
 
         // Rule generation options
         settings.enable_rule_generator =
@@ -67,8 +69,10 @@ impl From<TomlExternalSettings> for ExternalSettings {
         settings.quan_rule_base = toml_settings.custom.rule_bases.quan_rule_base;
         settings.quanx_rule_base = toml_settings.custom.rule_bases.quanx_rule_base;
         settings.loon_rule_base = toml_settings.custom.rule_bases.loon_rule_base;
+        settings.shadowrocket_rule_base = toml_settings.custom.rule_bases.shadowrocket_rule_base;
         settings.sssub_rule_base = toml_settings.custom.rule_bases.sssub_rule_base;
         settings.singbox_rule_base = toml_settings.custom.rule_bases.singbox_rule_base;
+        settings.xray_rule_base = toml_settings.custom.rule_bases.xray_rule_base;
 
         // Rule generation options
         settings.enable_rule_generator =
@@ -114,8 +118,10 @@ impl From<IniExternalSettings> for ExternalSettings {
         settings.quan_rule_base = ini_settings.quan_rule_base;
         settings.quanx_rule_base = ini_settings.quanx_rule_base;
         settings.loon_rule_base = ini_settings.loon_rule_base;
+        settings.shadowrocket_rule_base = ini_settings.shadowrocket_rule_base;
         settings.sssub_rule_base = ini_settings.sssub_rule_base;
         settings.singbox_rule_base = ini_settings.singbox_rule_base;
+        settings.xray_rule_base = ini_settings.xray_rule_base;
 
         // Rule generation options
         settings.enable_rule_generator = Some(ini_settings.enable_rule_generator);
@@ -22,8 +22,10 @@ pub struct ExternalSettings {
     pub quan_rule_base: String,
     pub quanx_rule_base: String,
     pub loon_rule_base: String,
+    pub shadowrocket_rule_base: String,
     pub sssub_rule_base: String,
     pub singbox_rule_base: String,
+    pub xray_rule_base: String,
 
     // Rule generation options
     pub enable_rule_generator: Option<bool>,
@@ -19,8 +19,10 @@ pub struct IniExternalSettings {
     pub quan_rule_base: String,
     pub quanx_rule_base: String,
     pub loon_rule_base: String,
+    pub shadowrocket_rule_base: String,
     pub sssub_rule_base: String,
     pub singbox_rule_base: String,
+    pub xray_rule_base: String,
 
     // Rule generation options
     pub enable_rule_generator: bool,
@@ -104,8 +106,10 @@ impl IniExternalSettings {
             "quan_rule_base" => self.quan_rule_base = value.to_string(),
             "quanx_rule_base" => self.quanx_rule_base = value.to_string(),
             "loon_rule_base" => self.loon_rule_base = value.to_string(),
+            "shadowrocket_rule_base" => self.shadowrocket_rule_base = value.to_string(),
             "sssub_rule_base" => self.sssub_rule_base = value.to_string(),
             "singbox_rule_base" => self.singbox_rule_base = value.to_string(),
+            "xray_rule_base" => self.xray_rule_base = value.to_string(),
             "enable_rule_generator" => {
                 self.enable_rule_generator = parse_bool_with_true_default(value)
             }
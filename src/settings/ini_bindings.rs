@@ -96,6 +96,10 @@ impl FromIni<ProxyGroupConfigs> for ProxyGroupConfigs {
                     for provider in provider_list {
                         conf.using_provider.push(provider.to_string());
                     }
+                } else if starts_with(v_array[i], "!!FILTER=") {
+                    conf.filter = v_array[i][9..].to_string();
+                } else if starts_with(v_array[i], "!!EXCLUDE-FILTER=") {
+                    conf.exclude_filter = v_array[i][17..].to_string();
                 } else {
                     conf.proxies.push(v_array[i].to_string());
                 }
@@ -32,8 +32,10 @@ impl From<YamlSettings> for Settings {
         settings.quan_base = yaml_settings.common.quan_rule_base;
         settings.quanx_base = yaml_settings.common.quanx_rule_base;
         settings.loon_base = yaml_settings.common.loon_rule_base;
+        settings.shadowrocket_base = yaml_settings.common.shadowrocket_rule_base;
         settings.ssub_base = yaml_settings.common.sssub_rule_base;
         settings.singbox_base = yaml_settings.common.singbox_rule_base;
+        settings.xray_base = yaml_settings.common.xray_rule_base;
         settings.proxy_config = yaml_settings.common.proxy_config;
         settings.proxy_ruleset = yaml_settings.common.proxy_ruleset;
         settings.proxy_subscription = yaml_settings.common.proxy_subscription;
@@ -64,6 +66,7 @@ impl From<YamlSettings> for Settings {
         settings.clash_proxies_style = yaml_settings.node_pref.clash_proxies_style;
         settings.clash_proxy_groups_style = yaml_settings.node_pref.clash_proxy_groups_style;
         settings.singbox_add_clash_modes = yaml_settings.node_pref.singbox_add_clash_modes;
+        settings.singbox_version = yaml_settings.node_pref.singbox_version;
         // Managed config
         settings.write_managed_config = yaml_settings.managed_config.write_managed_config;
         settings.managed_config_prefix = yaml_settings.managed_config.managed_config_prefix;
@@ -106,6 +109,7 @@ impl From<YamlSettings> for Settings {
         settings.script_clean_context = yaml_settings.advanced.script_clean_context;
         settings.async_fetch_ruleset = yaml_settings.advanced.async_fetch_ruleset;
         settings.skip_failed_links = yaml_settings.advanced.skip_failed_links;
+        settings.follow_remote_proxies = yaml_settings.advanced.follow_remote_proxies;
 
         // Template
         settings.template_path = yaml_settings.template.template_path;
@@ -116,6 +120,9 @@ impl From<YamlSettings> for Settings {
         settings.enable_rule_gen = yaml_settings.rulesets.enabled;
         settings.overwrite_original_rules = yaml_settings.rulesets.overwrite_original_rules;
         settings.update_ruleset_on_request = yaml_settings.rulesets.update_ruleset_on_request;
+        if !yaml_settings.rulesets.rules_path.is_empty() {
+            settings.rules_path = yaml_settings.rulesets.rules_path;
+        }
 
         // update fields processed
         settings.renames = yaml_settings.parsed_rename;
@@ -150,8 +157,10 @@ impl From<TomlSettings> for Settings {
         settings.quan_base = common.quan_rule_base;
         settings.quanx_base = common.quanx_rule_base;
         settings.loon_base = common.loon_rule_base;
+        settings.shadowrocket_base = common.shadowrocket_rule_base;
         settings.ssub_base = common.sssub_rule_base;
         settings.singbox_base = common.singbox_rule_base;
+        settings.xray_base = common.xray_rule_base;
         settings.proxy_config = common.proxy_config;
         settings.proxy_ruleset = common.proxy_ruleset;
         settings.proxy_subscription = common.proxy_subscription;
@@ -186,6 +195,7 @@ impl From<TomlSettings> for Settings {
         settings.clash_proxies_style = node_pref.clash_proxies_style.clone();
         settings.clash_proxy_groups_style = node_pref.clash_proxy_groups_style.clone();
         settings.singbox_add_clash_modes = node_pref.singbox_add_clash_modes;
+        settings.singbox_version = node_pref.singbox_version.clone();
 
         // Managed config
         settings.write_managed_config = toml_settings.managed_config.write_managed_config;
@@ -230,6 +240,7 @@ impl From<TomlSettings> for Settings {
         settings.script_clean_context = toml_settings.advanced.script_clean_context;
         settings.async_fetch_ruleset = toml_settings.advanced.async_fetch_ruleset;
         settings.skip_failed_links = toml_settings.advanced.skip_failed_links;
+        settings.follow_remote_proxies = toml_settings.advanced.follow_remote_proxies;
 
         // Template
         settings.template_path = toml_settings.template.template_path.clone();
@@ -241,6 +252,9 @@ impl From<TomlSettings> for Settings {
             settings.overwrite_original_rules = toml_settings.ruleset.overwrite_original_rules;
             settings.update_ruleset_on_request = toml_settings.ruleset.update_ruleset_on_request;
         }
+        if !toml_settings.ruleset.rules_path.is_empty() {
+            settings.rules_path = toml_settings.ruleset.rules_path.clone();
+        }
 
         // Ensure listen_address is not empty
         if settings.listen_address.trim().is_empty() {
@@ -301,8 +315,10 @@ impl From<IniSettings> for Settings {
         settings.quan_base = ini_settings.quan_base.clone();
         settings.quanx_base = ini_settings.quanx_base.clone();
         settings.loon_base = ini_settings.loon_base.clone();
+        settings.shadowrocket_base = ini_settings.shadowrocket_base.clone();
         settings.ssub_base = ini_settings.ssub_base.clone();
         settings.singbox_base = ini_settings.singbox_base.clone();
+        settings.xray_base = ini_settings.xray_base.clone();
         settings.default_ext_config = ini_settings.default_ext_config.clone();
         settings.append_type = ini_settings.append_type;
         settings.proxy_config = ini_settings.proxy_config.clone();
@@ -327,6 +343,7 @@ impl From<IniSettings> for Settings {
         settings.clash_proxies_style = ini_settings.clash_proxies_style.clone();
         settings.clash_proxy_groups_style = ini_settings.clash_proxy_groups_style.clone();
         settings.singbox_add_clash_modes = ini_settings.singbox_add_clash_modes;
+        settings.singbox_version = ini_settings.singbox_version.clone();
         // Set rename_node from parsed_rename
         settings.renames = ini_settings.parsed_rename;
 
@@ -344,6 +361,9 @@ impl From<IniSettings> for Settings {
 
         // RULESET SECTION
         settings.enable_rule_gen = ini_settings.enable_rule_gen;
+        if !ini_settings.rules_path.is_empty() {
+            settings.rules_path = ini_settings.rules_path.clone();
+        }
         if ini_settings.enable_rule_gen {
             settings.overwrite_original_rules = ini_settings.overwrite_original_rules;
             settings.update_ruleset_on_request = ini_settings.update_ruleset_on_request;
@@ -397,6 +417,7 @@ impl From<IniSettings> for Settings {
         settings.script_clean_context = ini_settings.script_clean_context;
         settings.async_fetch_ruleset = ini_settings.async_fetch_ruleset;
         settings.skip_failed_links = ini_settings.skip_failed_links;
+        settings.follow_remote_proxies = ini_settings.follow_remote_proxies;
 
         // EMOJIS SECTION
         settings.add_emoji = ini_settings.add_emoji;
@@ -52,12 +52,16 @@ pub struct IniSettings {
     #[serde(default)]
     pub skip_failed_links: bool,
     #[serde(default)]
+    pub follow_remote_proxies: bool,
+    #[serde(default)]
     pub write_managed_config: bool,
     #[serde(default = "default_true")]
     pub enable_rule_gen: bool,
     #[serde(default)]
     pub update_ruleset_on_request: bool,
     #[serde(default)]
+    pub rules_path: String,
+    #[serde(default)]
     pub overwrite_original_rules: bool,
     #[serde(default)]
     pub print_dbg_info: bool,
@@ -109,6 +113,8 @@ pub struct IniSettings {
     #[serde(default)]
     pub singbox_add_clash_modes: bool,
     #[serde(default)]
+    pub singbox_version: String,
+    #[serde(default)]
     pub rename_node: Vec<String>,
     #[serde(default)]
     pub stream_rule: Vec<String>,
@@ -135,8 +141,10 @@ pub struct IniSettings {
     pub quan_base: String,
     pub quanx_base: String,
     pub loon_base: String,
+    pub shadowrocket_base: String,
     pub ssub_base: String,
     pub singbox_base: String,
+    pub xray_base: String,
     pub surge_ssr_path: String,
     pub quanx_dev_id: String,
 
@@ -379,8 +387,10 @@ impl IniSettings {
             "quan_rule_base" => self.quan_base = value.to_string(),
             "quanx_rule_base" => self.quanx_base = value.to_string(),
             "loon_rule_base" => self.loon_base = value.to_string(),
+            "shadowrocket_rule_base" => self.shadowrocket_base = value.to_string(),
             "sssub_rule_base" => self.ssub_base = value.to_string(),
             "singbox_rule_base" => self.singbox_base = value.to_string(),
+            "xray_rule_base" => self.xray_base = value.to_string(),
             "default_external_config" => self.default_ext_config = value.to_string(),
             "append_proxy_type" => self.append_type = parse_bool(value),
             "proxy_config" => self.proxy_config = value.to_string(),
@@ -413,6 +423,7 @@ impl IniSettings {
             "clash_proxies_style" => self.clash_proxies_style = value.to_string(),
             "clash_proxy_groups_style" => self.clash_proxy_groups_style = value.to_string(),
             "singbox_add_clash_modes" => self.singbox_add_clash_modes = parse_bool(value),
+            "singbox_version" => self.singbox_version = value.to_string(),
             "rename_node" => self.rename_node.push(value.to_string()),
             _ => {}
         }
@@ -455,6 +466,7 @@ impl IniSettings {
             "enabled" => self.enable_rule_gen = parse_bool(value),
             "overwrite_original_rules" => self.overwrite_original_rules = parse_bool(value),
             "update_ruleset_on_request" => self.update_ruleset_on_request = parse_bool(value),
+            "rules_path" => self.rules_path = value.to_string(),
             "ruleset" | "surge_ruleset" => {
                 self.rulesets.push(value.to_string());
             }
@@ -575,6 +587,7 @@ impl IniSettings {
             "script_clean_context" => self.script_clean_context = parse_bool(value),
             "async_fetch_ruleset" => self.async_fetch_ruleset = parse_bool(value),
             "skip_failed_links" => self.skip_failed_links = parse_bool(value),
+            "follow_remote_proxies" => self.follow_remote_proxies = parse_bool(value),
             _ => {}
         }
     }
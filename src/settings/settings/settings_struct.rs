@@ -140,11 +140,16 @@ pub struct Settings {
     pub max_concur_threads: u32,
     pub prepend_insert: bool,
     pub skip_failed_links: bool,
+    /// Whether Loon `[Remote Proxy]` and Quantumult X `[server_remote]`
+    /// subscriptions found in fetched configurations are downloaded too
+    pub follow_remote_proxies: bool,
     pub api_mode: bool,
     pub write_managed_config: bool,
     pub enable_rule_gen: bool,
     pub update_ruleset_on_request: bool,
     pub overwrite_original_rules: bool,
+    /// Directory local rulesets served by `/getruleset` must resolve into
+    pub rules_path: String,
     pub append_userinfo: bool,
     pub async_fetch_ruleset: bool,
     pub surge_resolve_hostname: bool,
@@ -179,6 +184,7 @@ pub struct Settings {
     pub update_strict: bool,
     pub clash_use_new_field: bool,
     pub singbox_add_clash_modes: bool,
+    pub singbox_version: String,
     pub clash_proxies_style: String,
     pub clash_proxy_groups_style: String,
     pub proxy_config: String,
@@ -197,8 +203,10 @@ pub struct Settings {
     pub quan_base: String,
     pub quanx_base: String,
     pub loon_base: String,
+    pub shadowrocket_base: String,
     pub ssub_base: String,
     pub singbox_base: String,
+    pub xray_base: String,
     pub surge_ssr_path: String,
     pub quanx_dev_id: String,
 
@@ -273,6 +281,10 @@ pub fn default_max_rules() -> usize {
     32768
 }
 
+pub fn default_rules_path() -> String {
+    "rules".to_string()
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Settings {
@@ -295,11 +307,13 @@ impl Default for Settings {
             max_concur_threads: default_max_concur_threads(),
             prepend_insert: false,
             skip_failed_links: false,
+            follow_remote_proxies: false,
             api_mode: false,
             write_managed_config: false,
             enable_rule_gen: default_true(),
             update_ruleset_on_request: false,
             overwrite_original_rules: false,
+            rules_path: default_rules_path(),
             append_userinfo: default_true(),
             async_fetch_ruleset: false,
             surge_resolve_hostname: false,
@@ -331,6 +345,7 @@ impl Default for Settings {
             update_strict: false,
             clash_use_new_field: default_true(),
             singbox_add_clash_modes: false,
+            singbox_version: String::new(),
             clash_proxies_style: String::new(),
             clash_proxy_groups_style: String::new(),
             proxy_config: String::new(),
@@ -348,8 +363,10 @@ impl Default for Settings {
             quan_base: String::new(),
             quanx_base: String::new(),
             loon_base: String::new(),
+            shadowrocket_base: String::new(),
             ssub_base: String::new(),
             singbox_base: String::new(),
+            xray_base: String::new(),
             surge_ssr_path: String::new(),
             quanx_dev_id: String::new(),
 
@@ -32,6 +32,10 @@ fn default_empty_string() -> String {
     String::new()
 }
 
+fn default_rules_path() -> String {
+    "rules".to_string()
+}
+
 fn default_system() -> String {
     "SYSTEM".to_string()
 }
@@ -119,8 +123,10 @@ pub struct CommonSettings {
     pub quan_rule_base: String,
     pub quanx_rule_base: String,
     pub loon_rule_base: String,
+    pub shadowrocket_rule_base: String,
     pub sssub_rule_base: String,
     pub singbox_rule_base: String,
+    pub xray_rule_base: String,
     #[serde(default = "default_system")]
     pub proxy_config: String,
     #[serde(default = "default_system")]
@@ -151,6 +157,7 @@ pub struct NodePreferences {
     #[serde(default = "default_empty_string")]
     pub clash_proxy_groups_style: String,
     pub singbox_add_clash_modes: bool,
+    pub singbox_version: String,
     pub rename_node: Vec<RegexMatchRuleInToml>,
 }
 
@@ -198,6 +205,8 @@ pub struct RulesetSettings {
     pub enabled: bool,
     pub overwrite_original_rules: bool,
     pub update_ruleset_on_request: bool,
+    #[serde(default = "default_rules_path")]
+    pub rules_path: String,
 }
 
 /// Template variable
@@ -271,6 +280,7 @@ pub struct AdvancedSettings {
     pub script_clean_context: bool,
     pub async_fetch_ruleset: bool,
     pub skip_failed_links: bool,
+    pub follow_remote_proxies: bool,
 }
 
 /// Main TOML settings structure
@@ -25,6 +25,10 @@ fn default_empty_string() -> String {
     String::new()
 }
 
+fn default_rules_path() -> String {
+    "rules".to_string()
+}
+
 fn default_system() -> String {
     "SYSTEM".to_string()
 }
@@ -111,8 +115,10 @@ pub struct CommonSettings {
     pub quan_rule_base: String,
     pub quanx_rule_base: String,
     pub loon_rule_base: String,
+    pub shadowrocket_rule_base: String,
     pub sssub_rule_base: String,
     pub singbox_rule_base: String,
+    pub xray_rule_base: String,
     #[serde(default = "default_system")]
     pub proxy_config: String,
     #[serde(default = "default_system")]
@@ -141,6 +147,7 @@ pub struct NodePreferences {
     pub clash_proxies_style: String,
     pub clash_proxy_groups_style: String,
     pub singbox_add_clash_modes: bool,
+    pub singbox_version: String,
     pub rename_node: Vec<RegexMatchRuleInYaml>,
 }
 
@@ -189,6 +196,8 @@ pub struct RulesetSettings {
     pub enabled: bool,
     pub overwrite_original_rules: bool,
     pub update_ruleset_on_request: bool,
+    #[serde(default = "default_rules_path")]
+    pub rules_path: String,
     #[serde(alias = "surge_ruleset")]
     pub rulesets: Vec<RulesetConfigInYaml>,
 }
@@ -272,6 +281,7 @@ pub struct AdvancedSettings {
     pub script_clean_context: bool,
     pub async_fetch_ruleset: bool,
     pub skip_failed_links: bool,
+    pub follow_remote_proxies: bool,
 }
 
 /// Main YAML settings structure
@@ -109,6 +109,8 @@ pub struct ProxyGroupConfigInToml {
     pub tolerance: Option<u32>,
     pub timeout: Option<u32>,
     pub disable_udp: Option<bool>,
+    pub filter: Option<String>,
+    pub exclude_filter: Option<String>,
     pub import: Option<String>,
 }
 
@@ -158,6 +160,8 @@ impl Into<ProxyGroupConfig> for ProxyGroupConfigInToml {
             persistent: false,
             evaluate_before_use: false,
             using_provider: Vec::new(),
+            filter: self.filter.unwrap_or_default(),
+            exclude_filter: self.exclude_filter.unwrap_or_default(),
         };
 
         // 根据不同的代理组类型设置特定属性
@@ -119,6 +119,9 @@ pub struct ProxyGroupConfigInYaml {
     pub interval: Option<u32>,
     pub tolerance: Option<u32>,
     pub timeout: Option<u32>,
+    pub filter: Option<String>,
+    #[serde(rename = "exclude-filter")]
+    pub exclude_filter: Option<String>,
     pub import: Option<String>,
 }
 
@@ -139,6 +142,14 @@ impl ToIni for ProxyGroupConfigInYaml {
             temp_array.push(rule.clone());
         }
 
+        // Add mihomo provider filters
+        if let Some(filter) = self.filter.as_ref().filter(|f| !f.is_empty()) {
+            temp_array.push(format!("!!FILTER={}", filter));
+        }
+        if let Some(exclude_filter) = self.exclude_filter.as_ref().filter(|f| !f.is_empty()) {
+            temp_array.push(format!("!!EXCLUDE-FILTER={}", exclude_filter));
+        }
+
         // Check if we have enough elements based on group type
         match self.group_type.as_str() {
             "select" => {
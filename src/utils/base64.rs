@@ -13,12 +13,14 @@ pub fn base64_encode(input: &str) -> String {
 /// # Arguments
 /// * `input` - The Base64 encoded string.
 /// * `accept_urlsafe` - A boolean indicating whether to accept URL-safe Base64 encoding.
+/// * `allow_trailing_bits` - A boolean indicating whether to ignore non-zero trailing bits.
 ///
 /// # Returns
 /// The decoded string, or an empty string if the input is invalid.
-fn base64_decode(input: &str, accept_urlsafe: bool) -> Option<String> {
+fn base64_decode(input: &str, accept_urlsafe: bool, allow_trailing_bits: bool) -> Option<String> {
     let purpose_config = general_purpose::GeneralPurposeConfig::new()
-        .with_decode_padding_mode(DecodePaddingMode::Indifferent);
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent)
+        .with_decode_allow_trailing_bits(allow_trailing_bits);
     let engine = general_purpose::GeneralPurpose::new(
         if accept_urlsafe {
             &base64::alphabet::URL_SAFE
@@ -46,9 +48,40 @@ pub fn url_safe_base64_apply(input: &str) -> String {
     input.replace('+', "-").replace('/', "_").replace('=', "") // Remove padding
 }
 
+/// Strips whitespace and stray padding that real-world subscriptions often
+/// contain, so the remaining data can be decoded regardless of padding.
+fn base64_normalize(input: &str) -> String {
+    let mut normalized: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '=')
+        .collect();
+    // A single dangling character cannot carry a full byte
+    if normalized.len() % 4 == 1 {
+        normalized.pop();
+    }
+    normalized
+}
+
 /// Decodes a URL-safe Base64 string to its original form.
 pub fn url_safe_base64_decode(input: &str) -> String {
-    match base64_decode(&url_safe_base64_reverse(input), false) {
+    match base64_decode(&url_safe_base64_reverse(input), false, false) {
+        Some(decoded) => decoded,
+        None => input.to_string(),
+    }
+}
+
+/// Decodes a URL-safe Base64 string like [`url_safe_base64_decode`], but also
+/// tolerates whitespace, missing, extra or misplaced padding and non-zero
+/// trailing bits.
+///
+/// Only meant for subscription bodies and SSR links, which are often sloppily
+/// encoded; everything else should go through the strict decoder.
+pub fn url_safe_base64_decode_lenient(input: &str) -> String {
+    match base64_decode(
+        &base64_normalize(&url_safe_base64_reverse(input)),
+        false,
+        true,
+    ) {
         Some(decoded) => decoded,
         None => input.to_string(),
     }
@@ -67,3 +67,31 @@ where
 
     deserializer.deserialize_any(StringOrNumberVisitor)
 }
+
+/// Helper function to deserialize fields that can be either a comma-separated
+/// string or a sequence of strings
+pub fn deserialize_string_or_seq<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum StringOrSeq {
+        String(String),
+        Seq(Vec<String>),
+    }
+
+    Ok(
+        match <Option<StringOrSeq> as serde::Deserialize>::deserialize(deserializer)? {
+            Some(StringOrSeq::String(value)) => Some(
+                value
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            ),
+            Some(StringOrSeq::Seq(values)) => Some(values),
+            None => None,
+        },
+    )
+}
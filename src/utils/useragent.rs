@@ -105,6 +105,40 @@ pub fn match_user_agent(
 ) {
     // Define user agent profiles to match C++ UAMatchList
     let ua_profiles = vec![
+        // Stash and mihomo (Clash.Meta) based clients, matched before the generic
+        // Clash profiles since their user agents usually contain "clash" as well
+        UAProfile::new(
+            "stash",
+            "",
+            "",
+            SubconverterTarget::Stash,
+            Some(true), // True
+            -1,
+        ),
+        UAProfile::new(
+            "mihomo",
+            "",
+            "",
+            SubconverterTarget::Mihomo,
+            Some(true), // True
+            -1,
+        ),
+        UAProfile::new(
+            "clash.meta",
+            "",
+            "",
+            SubconverterTarget::Mihomo,
+            Some(true), // True
+            -1,
+        ),
+        UAProfile::new(
+            "clashmeta",
+            "",
+            "",
+            SubconverterTarget::Mihomo,
+            Some(true), // True
+            -1,
+        ),
         // ClashForAndroid profiles
         UAProfile::new(
             "clashforandroid",
@@ -152,7 +186,7 @@ pub fn match_user_agent(
             "clash-verge",
             "",
             "",
-            SubconverterTarget::Clash,
+            SubconverterTarget::Mihomo,
             Some(true), // True
             -1,
         ),
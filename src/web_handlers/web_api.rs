@@ -3,7 +3,9 @@ use std::collections::HashMap;
 use actix_web::{web, HttpRequest, HttpResponse};
 use log::error;
 
-use crate::api::{sub_process, SubResponse, SubconverterQuery};
+use crate::api::{
+    getruleset_process, sub_process, GetRulesetQuery, SubResponse, SubconverterQuery,
+};
 impl SubResponse {
     /// Convert SubResponse to HttpResponse
     pub fn to_http_response(self) -> HttpResponse {
@@ -62,8 +64,9 @@ pub async fn simple_handler(
 
     // Set appropriate target based on path
     match target_type.as_str() {
-        "clash" | "clashr" | "surge" | "quan" | "quanx" | "loon" | "ss" | "ssr" | "ssd"
-        | "v2ray" | "trojan" | "mixed" | "singbox" => {
+        "clash" | "clashr" | "mihomo" | "stash" | "surge" | "quan" | "quanx" | "loon"
+        | "shadowrocket" | "ss" | "ssr" | "ssd" | "v2ray" | "trojan" | "mixed" | "singbox"
+        | "xray" => {
             // Create a modified query with the target set
             let mut modified_query = query.into_inner();
             modified_query.target = Some(target_type.clone());
@@ -106,9 +109,21 @@ pub async fn surge_to_clash_handler(
     }
 }
 
+/// Handler serving a single ruleset converted for a client
+pub async fn getruleset_handler(query: web::Query<GetRulesetQuery>) -> HttpResponse {
+    match getruleset_process(query.into_inner()).await {
+        Ok(response) => response.to_http_response(),
+        Err(e) => {
+            error!("Ruleset process error: {}", e);
+            HttpResponse::InternalServerError().body(format!("Internal server error: {}", e))
+        }
+    }
+}
+
 /// Register the API endpoints with Actix Web
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.route("/sub", web::get().to(sub_handler))
         .route("/surge2clash", web::get().to(surge_to_clash_handler))
+        .route("/getruleset", web::get().to(getruleset_handler))
         .route("/{target_type}", web::get().to(simple_handler));
 }
@@ -0,0 +1,50 @@
+import { NextRequest, NextResponse } from 'next/server';
+import { loadWasmSingleton } from '@/lib/wasm';
+
+/**
+ * Serve a single ruleset converted for the client referencing it
+ * Generated configurations point here instead of inlining the rules
+ */
+export async function GET(request: NextRequest) {
+    let wasmModule;
+    try {
+        wasmModule = await loadWasmSingleton('RulesetAPI');
+    } catch (error) {
+        console.error("Failed to load WASM module:", error);
+        return NextResponse.json(
+            {
+                error: 'Failed to load WASM module',
+                details: error instanceof Error ? error.message : String(error)
+            },
+            { status: 500 }
+        );
+    }
+
+    const params: any = Object.fromEntries(request.nextUrl.searchParams);
+    if (typeof params.type === 'string') {
+        params.type = Number(params.type);
+    }
+
+    console.log(`Ruleset API request: GET /api/getruleset with type=${params.type}`);
+
+    try {
+        const responseJsonString = await wasmModule.getruleset_process_wasm(JSON.stringify(params));
+        const response = JSON.parse(responseJsonString);
+
+        return new NextResponse(response.content, {
+            status: response.status_code || 200,
+            headers: {
+                'Content-Type': response.content_type || 'text/plain',
+                ...(response.headers || {})
+            }
+        });
+    } catch (error: any) {
+        console.error(`Error processing ruleset:`, error);
+        const errorMessage = typeof error === 'string' ? error : (error.message || 'Unknown WASM error');
+
+        return NextResponse.json(
+            { error: 'Failed to process ruleset', details: errorMessage },
+            { status: 500 }
+        );
+    }
+}
@@ -149,6 +149,7 @@ overwrite_original_rules=true
                                         <option value="quantumult">Quantumult</option>
                                         <option value="quanx">Quantumult X</option>
                                         <option value="loon">Loon</option>
+                                        <option value="shadowrocket">Shadowrocket</option>
                                         <option value="ss">SS</option>
                                         <option value="ssr">SSR</option>
                                         <option value="v2ray">V2Ray</option>
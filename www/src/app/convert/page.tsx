@@ -13,10 +13,10 @@ import {
 
 // Define supported targets
 const SUPPORTED_TARGETS = [
-    'auto', 'clash', 'clashr', 'surge', 'quan', 'quanx',
-    'mellow', 'surfboard', 'loon', 'ss', 'ssr', 'sssub',
+    'auto', 'clash', 'clashr', 'mihomo', 'stash', 'surge', 'quan', 'quanx',
+    'mellow', 'surfboard', 'loon', 'shadowrocket', 'ss', 'ssr', 'sssub',
     'v2ray', 'trojan', 'trojan-go', 'hysteria', 'hysteria2',
-    'ssd', 'mixed', 'singbox'
+    'ssd', 'mixed', 'singbox', 'xray'
 ];
 
 export default function ConvertPage() {
@@ -495,7 +495,7 @@ export default function ConvertPage() {
                             </div>
                         )}
                         {/* Clash Specific */}
-                        {(formData.target === 'clash' || formData.target === 'clashr') && (
+                        {(formData.target === 'clash' || formData.target === 'clashr' || formData.target === 'mihomo' || formData.target === 'stash') && (
                             <>
                                 <div className="flex items-center space-x-2">
                                     <input
@@ -175,10 +175,10 @@ export default function Home() {
 
   // The supported target formats from the convert page
   const SUPPORTED_TARGETS = [
-    'clash', 'singbox', 'surge', 'quan', 'quanx',
-    'mellow', 'surfboard', 'loon', 'ss', 'ssr', 'sssub',
+    'clash', 'mihomo', 'stash', 'singbox', 'surge', 'quan', 'quanx',
+    'mellow', 'surfboard', 'loon', 'shadowrocket', 'ss', 'ssr', 'sssub',
     'v2ray', 'trojan', 'trojan-go', 'hysteria', 'hysteria2',
-    'ssd', 'mixed', 'clashr'
+    'ssd', 'mixed', 'clashr', 'xray'
   ];
 
   return (
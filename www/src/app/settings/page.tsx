@@ -29,8 +29,10 @@ interface SubconverterSettings {
         quan_rule_base?: string;
         quanx_rule_base?: string;
         loon_rule_base?: string;
+        shadowrocket_rule_base?: string;
         sssub_rule_base?: string;
         singbox_rule_base?: string;
+        xray_rule_base?: string;
         proxy_config?: string;
         proxy_ruleset?: string;
         proxy_subscription?: string;
@@ -54,6 +56,7 @@ interface SubconverterSettings {
         clash_proxies_style?: string;
         clash_proxy_groups_style?: string;
         singbox_add_clash_modes?: boolean;
+        singbox_version?: string;
         rename_node?: Array<{ match?: string; replace?: string; script?: string; import?: string }>;
     };
     managed_config?: {
@@ -651,6 +654,17 @@ export default function SettingsPage() {
                             <option value="false">Disabled</option>
                         </select>
                     </div>
+
+                    <div>
+                        <label className="block text-sm font-medium mb-1">SingBox Version</label>
+                        <input
+                            type="text"
+                            className="w-full p-2 border border-gray-300 rounded bg-white/10"
+                            value={nodePref?.singbox_version || ''}
+                            placeholder="latest"
+                            onChange={(e) => handleInputChange('node_pref', 'singbox_version', e.target.value)}
+                        />
+                    </div>
                 </div>
 
                 <div className="grid grid-cols-1 md:grid-cols-2 gap-4">